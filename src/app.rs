@@ -22,9 +22,13 @@ use crate::components::{
     edit_name_textinput::EditNameTextInput,
     edit_value_textinput::EditValueTextInput,
     floating_windows::{
-        app_theme_selector::AppThemeSelector, code_gen::CodeGen, commands::Commands,
-        edit_endpoint_name::EditEndpointName, edit_project_name::EditProjectName,
-        endpoints_selector::EndpointsSelector, syntax_theme_selector::SyntaxThemeSelector,
+        app_theme_selector::AppThemeSelector, code_gen::CodeGen, command_palette::CommandPalette,
+        commands::Commands, curl_import::CurlImport, edit_endpoint_name::EditEndpointName,
+        edit_project_name::EditProjectName, endpoint_settings::EndpointSettingsWindow,
+        endpoints_selector::EndpointsSelector, grpc_method_selector::GrpcMethodSelector,
+        openapi_import::OpenApiImport, output_path::OutputPathWindow,
+        project_recovery::ProjectRecoveryWindow, project_settings::ProjectSettingsWindow,
+        proxy_settings::ProxySettingsWindow, syntax_theme_selector::SyntaxThemeSelector,
     },
     focusable_section::FocusableSection,
     header_name_textinput::HeaderNameTextInput,
@@ -33,7 +37,7 @@ use crate::components::{
     method_selector::{MethodSelector, MethodSelectorState, METHOD_SELECTOR_TEMPLATE},
     options::OptionsView,
     project_window::ProjectWindow,
-    request_body_section::REQUEST_BODY_SECTION_TEMPLATE,
+    request_body_section::{GRAPHQL_BODY_SECTION_TEMPLATE, REQUEST_BODY_SECTION_TEMPLATE},
     request_headers_editor::{
         RequestHeadersEditor, RequestHeadersEditorState, REQUEST_HEADERS_EDITOR_TEMPLATE,
     },
@@ -41,7 +45,9 @@ use crate::components::{
     row::{Row, RowState, ROW_TEMPLATE},
     textarea::{TextArea, TextAreaInputState, TEXTAREA_TEMPLATE},
     textinput::{InputState, TextInput, TEXTINPUT_TEMPLATE},
+    websocket_console::WebSocketConsole,
 };
+use crate::keymap::get_keymap;
 
 const RESPONSE_FILTER_INPUT: &str = "./src/components/templates/response_filter_input.aml";
 const NO_BORDER_INPUT: &str = "./src/components/templates/no_border_input.aml";
@@ -125,6 +131,7 @@ impl App {
         )?;
 
         component_ids = self.component_ids.clone();
+        let keymap = get_keymap();
         builder.register_prototype(
             "response_body_area",
             TEXTAREA_TEMPLATE,
@@ -132,11 +139,13 @@ impl App {
                 component_ids: component_ids.clone(),
                 listeners: vec![],
                 input_for: None,
+                keymap: keymap.clone(),
             },
             TextAreaInputState::new,
         )?;
 
         component_ids = self.component_ids.clone();
+        let keymap = get_keymap();
         builder.register_prototype(
             "textarea",
             TEXTAREA_TEMPLATE,
@@ -144,6 +153,7 @@ impl App {
                 component_ids: component_ids.clone(),
                 listeners: vec![],
                 input_for: None,
+                keymap: keymap.clone(),
             },
             TextAreaInputState::new,
         )?;
@@ -217,6 +227,24 @@ impl App {
             vec!["dashboard".to_string()],
         )?;
 
+        TextArea::register(
+            &self.component_ids,
+            builder,
+            "graphql_query_input",
+            Some(TEXTAREA_TEMPLATE),
+            Some("endpoint_graphql_query".to_string()),
+            vec!["dashboard".to_string()],
+        )?;
+
+        TextArea::register(
+            &self.component_ids,
+            builder,
+            "graphql_variables_input",
+            Some(TEXTAREA_TEMPLATE),
+            Some("endpoint_graphql_variables".to_string()),
+            vec!["dashboard".to_string()],
+        )?;
+
         EditInput::register(
             &self.component_ids,
             builder,
@@ -233,6 +261,42 @@ impl App {
             None,
             vec![],
         )?;
+        EditInput::register(
+            &self.component_ids,
+            builder,
+            "openapi_import_path_input",
+            None,
+            None,
+            vec![],
+        )?;
+        EditInput::register(
+            &self.component_ids,
+            builder,
+            "endpoints_selector_input",
+            None,
+            None,
+            vec![],
+        )?;
+
+        for settings_input in [
+            "tls_ca_certificates_input",
+            "tls_client_certificate_input",
+            "tls_client_key_input",
+            "proto_files_input",
+            "proxy_url_input",
+            "proxy_username_input",
+            "proxy_password_input",
+            "proxy_no_proxy_input",
+        ] {
+            EditInput::register(
+                &self.component_ids,
+                builder,
+                settings_input,
+                None,
+                None,
+                vec![],
+            )?;
+        }
 
         EditInput::register(
             &self.component_ids,
@@ -271,6 +335,10 @@ impl App {
 
         ProjectWindow::register(&self.component_ids, builder)?;
         EndpointsSelector::register(&self.component_ids, builder)?;
+        GrpcMethodSelector::register(&self.component_ids, builder)?;
+        OpenApiImport::register(&self.component_ids, builder)?;
+        CurlImport::register(&self.component_ids, builder)?;
+        OutputPathWindow::register(&self.component_ids, builder)?;
 
         ConfirmActionWindow::register(&self.component_ids, builder)?;
         DashboardComponent::register(&self.component_ids, builder)?;
@@ -280,7 +348,49 @@ impl App {
         SyntaxThemeSelector::register(&self.component_ids, builder)?;
         AppThemeSelector::register(&self.component_ids, builder)?;
         Commands::register(&self.component_ids, builder)?;
+        CommandPalette::register(&self.component_ids, builder)?;
+        ProjectRecoveryWindow::register(&self.component_ids, builder)?;
         CodeGen::register(&self.component_ids, builder)?;
+        EndpointSettingsWindow::register(&self.component_ids, builder)?;
+        ProjectSettingsWindow::register(&self.component_ids, builder)?;
+        ProxySettingsWindow::register(&self.component_ids, builder)?;
+        WebSocketConsole::register(&self.component_ids, builder)?;
+
+        TextArea::register(
+            &self.component_ids,
+            builder,
+            "curl_command_input",
+            Some(TEXTAREA_TEMPLATE),
+            Some("curl_command".to_string()),
+            vec!["curl_import".to_string()],
+        )?;
+
+        TextArea::register(
+            &self.component_ids,
+            builder,
+            "folder_headers_input",
+            Some(TEXTAREA_TEMPLATE),
+            None,
+            vec![],
+        )?;
+
+        TextArea::register(
+            &self.component_ids,
+            builder,
+            "project_variables_input",
+            Some(TEXTAREA_TEMPLATE),
+            None,
+            vec![],
+        )?;
+
+        TextArea::register(
+            &self.component_ids,
+            builder,
+            "websocket_message_input",
+            Some(TEXTAREA_TEMPLATE),
+            Some("websocket_message".to_string()),
+            vec!["websocket_console".to_string()],
+        )?;
 
         TextArea::register(
             &self.component_ids,
@@ -298,6 +408,13 @@ impl App {
             REQUEST_BODY_SECTION_TEMPLATE,
         )?;
 
+        FocusableSection::register(
+            &self.component_ids,
+            builder,
+            "graphql_body_section",
+            GRAPHQL_BODY_SECTION_TEMPLATE,
+        )?;
+
         // dbg!(&self.component_ids);
 
         Ok(())
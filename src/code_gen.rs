@@ -1,8 +1,25 @@
-use std::fs;
+pub mod models;
+pub mod sanitize;
+pub mod template;
+
+use std::{fs, path::PathBuf};
+
+use serde_json::{json, Value};
 
 use crate::{
-    fs::{get_app_dir, get_documents_dir},
-    projects::{Header, PersistedProject},
+    code_gen::{
+        models::Models,
+        sanitize::{escape_string, namespace_name, package_name, Casing, Identifiers, Language},
+        template::Template,
+    },
+    fs::get_app_dir,
+    output::OutputFile,
+    projects::{BodyMode, EndpointKind, Header, PersistedEndpoint, PersistedProject},
+    requests::{
+        last_response::{load_last_response, StoredResponse},
+        request_body,
+        variables::resolve_endpoint,
+    },
 };
 
 const JS_METHOD_TEMPLATE: &str = "
@@ -28,7 +45,7 @@ export async function [NAME][RETURN_GENERIC]([BODY_VAR]): Promise<[RETURN_TYPE]>
 ";
 
 const RUST_METHOD_TEMPLATE: &str = "
-pub fn [NAME]([ARGS]) -> Result<String, Box<dyn Error>> {
+pub fn [NAME]([ARGS]) -> Result<[RETURN_TYPE], Box<dyn Error>> {
     let mut request = ureq::request(\"[METHOD]\", \"[URL]\");
 
 [HEADERS]
@@ -37,137 +54,943 @@ pub fn [NAME]([ARGS]) -> Result<String, Box<dyn Error>> {
 }
 ";
 
+const PYTHON_REQUESTS_METHOD_TEMPLATE: &str = "
+
+def [NAME]([ARGS]):
+    with requests.Session() as session:
+        session.max_redirects = [MAX_REDIRECTS]
+        response = session.request(
+            \"[METHOD]\",
+            \"[URL]\",
+            headers={[HEADERS]},[BODY]
+            allow_redirects=[FOLLOW_REDIRECTS],
+        )
+        response.raise_for_status()
+
+        return response.[RESPONSE]
+";
+
+const PYTHON_HTTPX_METHOD_TEMPLATE: &str = "
+
+async def [NAME]([ARGS]):
+    async with httpx.AsyncClient(
+        follow_redirects=[FOLLOW_REDIRECTS], max_redirects=[MAX_REDIRECTS]
+    ) as client:
+        response = await client.request(
+            \"[METHOD]\",
+            \"[URL]\",
+            headers={[HEADERS]},[BODY]
+        )
+        response.raise_for_status()
+
+        return response.[RESPONSE]
+";
+
+const GO_METHOD_TEMPLATE: &str = "
+func [NAME]([ARGS]) (string, error) {
+\trequest, err := http.NewRequest(\"[METHOD]\", \"[URL]\", [BODY])
+\tif err != nil {
+\t\treturn \"\", err
+\t}
+[HEADERS]
+\tclient := &http.Client{
+\t\tCheckRedirect: func(_ *http.Request, via []*http.Request) error {
+\t\t\tif len(via) > [MAX_REDIRECTS] {
+\t\t\t\treturn http.ErrUseLastResponse
+\t\t\t}
+
+\t\t\treturn nil
+\t\t},
+\t}
+
+\tresponse, err := client.Do(request)
+\tif err != nil {
+\t\treturn \"\", err
+\t}
+\tdefer response.Body.Close()
+
+\tresponseBody, err := io.ReadAll(response.Body)
+\tif err != nil {
+\t\treturn \"\", err
+\t}
+
+\tif response.StatusCode >= 400 {
+\t\treturn string(responseBody), fmt.Errorf(\"request failed with %s\", response.Status)
+\t}
+
+\treturn string(responseBody), nil
+}
+";
+
+const PHP_GUZZLE_METHOD_TEMPLATE: &str = "
+function [NAME]([ARGS]): string
+{
+    $client = new Client();
+    $response = $client->request('[METHOD]', '[URL]', [
+        'headers' => [[HEADERS]],[BODY]
+        'allow_redirects' => [REDIRECTS],
+    ]);
+
+    return (string) $response->getBody();
+}
+";
+
+const RUST_REQWEST_METHOD_TEMPLATE: &str = "
+pub async fn [NAME]([ARGS]) -> Result<[RETURN_TYPE], Box<dyn Error>> {
+    let client = Client::builder().redirect([REDIRECT_POLICY]).build()?;
+    let response = client
+        .request(\"[METHOD]\".parse()?, \"[URL]\")[CALLS]
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(response.[RESPONSE].await?)
+}
+";
+
 pub enum WebType {
     JavaScript,
     TypeScript,
 }
 
-pub fn generate_rust(project: PersistedProject) -> anyhow::Result<()> {
-    let mut module = "use ureq::Response;
-use std::error::Error;
-"
-    .to_string();
+pub enum ShellType {
+    Curl,
+    HTTPie,
+    Wget,
+}
+
+pub enum PythonType {
+    Requests,
+    Httpx,
+}
+
+/// A whole module template from the code_templates app dir, named
+/// {name}.{extension}.template like kotlin.kt.template
+#[derive(Clone, Debug)]
+pub struct ModuleTemplate {
+    pub name: String,
+    pub extension: String,
+    pub path: PathBuf,
+}
+
+impl ModuleTemplate {
+    pub fn from_path(path: PathBuf) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        let stem = file_name.strip_suffix(".template")?;
+
+        let (name, extension) = stem.split_once('.').unwrap_or((stem, "txt"));
+        if name.is_empty() || !path.is_file() {
+            return None;
+        }
+
+        Some(ModuleTemplate {
+            name: name.to_string(),
+            extension: extension.to_string(),
+            path,
+        })
+    }
+}
 
+pub fn generate_rust(project: PersistedProject) -> anyhow::Result<OutputFile> {
     let rust_method_template = get_rust_method_template()?;
+    let module = rust_module(&project, &rust_method_template, |endpoint| {
+        load_last_response(&project.name, &endpoint.name)
+    });
+
+    Ok(output_file(&project.name, &module, "rs"))
+}
+
+fn rust_module(
+    project: &PersistedProject,
+    method_template: &str,
+    last_response: impl Fn(&PersistedEndpoint) -> Option<StoredResponse>,
+) -> String {
+    let mut functions = String::new();
+    let mut models = Models::default();
+
+    let mut identifiers = Identifiers::new(Language::Rust, &[]);
+    let escape = |text: &str| escape_string(text, Language::Rust);
+
+    for endpoint in http_endpoints(project) {
+        let has_body = !request_body(&endpoint).unwrap_or_default().is_empty();
+        let name = identifiers.function_name(&endpoint.name);
+
+        let (request_sample, response_sample) = json_samples(&endpoint, &last_response);
+        let request_type = request_sample
+            .filter(|_| has_body)
+            .map(|sample| models.infer(&format!("{name} request"), &sample));
+        let response_type =
+            response_sample.map(|sample| models.infer(&format!("{name} response"), &sample));
 
-    project.endpoints.iter().for_each(|endpoint| {
         let mut headers: Vec<String> = vec![];
         endpoint.headers.iter().for_each(|header| {
             headers.push(format!(
                 "    request = request.set(\"{}\", \"{}\");",
-                header.name, header.value
+                escape(&header.name),
+                escape(&header.value)
             ));
         });
         let headers = headers.join("\n");
 
-        let call = match endpoint.body.is_empty() {
-            true => "request.call()",
-            false => "request.send_string(body)",
+        let (args, send) = match (has_body, &request_type) {
+            (false, _) => (String::new(), "request.call()?"),
+            (true, None) => (String::from("body: &str"), "request.send_string(body)?"),
+            (true, Some(request_type)) => (
+                format!("body: &{}", request_type.rust()),
+                "request.send_json(body)?",
+            ),
         };
 
-        let args = match endpoint.body.is_empty() {
-            true => "",
-            false => "body: &str",
+        let (return_type, read) = match &response_type {
+            Some(response_type) => (response_type.rust(), "into_json()?"),
+            None => (String::from("String"), "into_string()?"),
         };
 
-        let method_code = rust_method_template
-            .replace("[NAME]", &endpoint.name.replace(" ", "_"))
-            .replace("[ARGS]", args)
-            .replace("[METHOD]", &endpoint.method)
-            .replace("[URL]", &endpoint.url)
+        let method_code = method_template
+            .replace("[NAME]", &name)
+            .replace("[ARGS]", &args)
+            .replace("[RETURN_TYPE]", &return_type)
+            .replace("[METHOD]", &escape(&endpoint.method))
+            .replace("[URL]", &escape(&endpoint.url))
             .replace("[HEADERS]", &headers)
-            .replace("[CALL]", call);
+            .replace("[CALL]", &format!("Ok({send}.{read})"));
 
-        module.push_str(&method_code);
-    });
+        functions.push_str(&method_code);
+    }
 
-    write_code(&project.name, &module, "rs")
+    let mut module = String::from("use std::error::Error;\n");
+    if !models.is_empty() {
+        module.push_str("\nuse serde::{Deserialize, Serialize};\n");
+        module.push_str(&models.rust_structs());
+    }
+
+    module.push_str(&functions);
+    module
 }
 
-pub fn generate_web(project: PersistedProject, web_type: WebType) -> anyhow::Result<()> {
-    let mut module = String::new();
+pub fn generate_web(project: PersistedProject, web_type: WebType) -> anyhow::Result<OutputFile> {
     let method_template = get_method_template(&web_type)?;
+    let module = web_module(&project, &web_type, &method_template, |endpoint| {
+        load_last_response(&project.name, &endpoint.name)
+    });
 
-    project.endpoints.iter().for_each(|endpoint| {
-        let content_type = endpoint
-            .headers
-            .iter()
-            .find(|header| header.name == "content-type");
+    let extension = match web_type {
+        WebType::JavaScript => "js",
+        WebType::TypeScript => "ts",
+    };
+
+    Ok(output_file(&project.name, &module, extension))
+}
+
+fn web_module(
+    project: &PersistedProject,
+    web_type: &WebType,
+    method_template: &str,
+    last_response: impl Fn(&PersistedEndpoint) -> Option<StoredResponse>,
+) -> String {
+    let mut functions = String::new();
+    let mut models = Models::default();
 
-        let default_header = Header::default();
-        let content_type = content_type.unwrap_or(&default_header);
+    let language = match web_type {
+        WebType::JavaScript => Language::JavaScript,
+        WebType::TypeScript => Language::TypeScript,
+    };
+
+    // NOTE: A function named fetch would call itself instead of the global one
+    let mut identifiers = Identifiers::new(language, &["fetch"]);
+    let escape = |text: &str| escape_string(text, language);
+
+    for endpoint in http_endpoints(project) {
+        let has_body = !request_body(&endpoint).unwrap_or_default().is_empty();
+        let name = identifiers.function_name(&endpoint.name);
+
+        // NOTE: JavaScript has no types to put the models in
+        let (request_sample, response_sample) = match web_type {
+            WebType::JavaScript => (None, None),
+            WebType::TypeScript => json_samples(&endpoint, &last_response),
+        };
+
+        let request_type = request_sample
+            .filter(|_| has_body)
+            .map(|sample| models.infer(&format!("{name} request"), &sample));
+        let response_type =
+            response_sample.map(|sample| models.infer(&format!("{name} response"), &sample));
 
         let mut return_generic = "";
-        let mut return_type = "string";
+        let mut return_type = String::from("string");
         let mut response_func = "text";
-        let mut return_generic_cast = "";
+        let mut return_generic_cast = String::new();
 
-        if content_type.value == "application/json" {
+        if let Some(response_type) = &response_type {
+            return_type = response_type.typescript();
+            response_func = "json";
+            return_generic_cast = format!("as {return_type}");
+        } else if is_json(&endpoint) {
             return_generic = "<T>";
-            return_type = "T";
+            return_type = String::from("T");
             response_func = "json";
-            return_generic_cast = "as T";
+            return_generic_cast = String::from("as T");
         }
 
-        let mut body_var = "";
+        let mut body_var = String::new();
         let mut body = "";
 
-        if !endpoint.body.is_empty() {
-            body_var = match web_type {
-                WebType::JavaScript => "body",
-                WebType::TypeScript => "body: BodyInit",
+        if has_body {
+            body_var = match (web_type, &request_type) {
+                (WebType::JavaScript, _) => String::from("body"),
+                (WebType::TypeScript, None) => String::from("body: BodyInit"),
+                (WebType::TypeScript, Some(request_type)) => {
+                    format!("body: {}", request_type.typescript())
+                }
             };
 
-            body = "\n    body,";
+            body = match request_type {
+                Some(_) => "\n    body: JSON.stringify(body),",
+                None => "\n    body,",
+            };
         }
 
         let mut headers: Vec<String> = vec![];
         endpoint.headers.iter().for_each(|h| {
-            let header = format!("\"{}\": \"{}\"", h.name, h.value);
+            let header = format!("\"{}\": \"{}\"", escape(&h.name), escape(&h.value));
             headers.push(header);
         });
         let headers = headers.join(", ");
 
         let method_code = method_template
-            .replace("[NAME]", &endpoint.name.replace(" ", "_"))
+            .replace("[NAME]", &name)
             .replace("[RETURN_GENERIC]", return_generic)
-            .replace("[BODY_VAR]", body_var)
-            .replace("[RETURN_TYPE]", return_type)
-            .replace("[URL]", &endpoint.url)
+            .replace("[BODY_VAR]", &body_var)
+            .replace("[RETURN_TYPE]", &return_type)
+            .replace("[URL]", &escape(&endpoint.url))
             .replace("[HEADERS]", &headers)
             .replace("[BODY]", body)
-            .replace("[METHOD]", &endpoint.method)
+            .replace("[METHOD]", &escape(&endpoint.method))
             .replace("[RESPONSE_FUNC]", response_func)
-            .replace("[RETURN_GENERIC_CAST]", return_generic_cast);
+            .replace("[RETURN_GENERIC_CAST]", &return_generic_cast);
+
+        functions.push_str(&method_code);
+    }
+
+    let mut module = models.typescript_interfaces();
+    module.push_str(&functions);
+    module
+}
+
+pub fn generate_python(
+    project: PersistedProject,
+    python_type: PythonType,
+) -> anyhow::Result<OutputFile> {
+    let (template_file_name, default_template, module_suffix) = match python_type {
+        PythonType::Requests => (
+            "python_requests_method_template.txt",
+            PYTHON_REQUESTS_METHOD_TEMPLATE,
+            "requests",
+        ),
+        PythonType::Httpx => (
+            "python_httpx_method_template.txt",
+            PYTHON_HTTPX_METHOD_TEMPLATE,
+            "httpx",
+        ),
+    };
+
+    let method_template = get_code_template(template_file_name, default_template)?;
+    let module = python_module(&project, &python_type, &method_template);
+
+    // NOTE: Python module names can't have dots, so the client goes in the name
+    Ok(output_file(
+        &format!("{}_{module_suffix}", project.name),
+        &module,
+        "py",
+    ))
+}
+
+fn python_module(
+    project: &PersistedProject,
+    python_type: &PythonType,
+    method_template: &str,
+) -> String {
+    let mut module = match python_type {
+        PythonType::Requests => String::from("import requests\n"),
+        PythonType::Httpx => String::from("import httpx\n"),
+    };
+
+    // NOTE: A function named after the client module would hide it
+    let mut identifiers = Identifiers::new(Language::Python, &["requests", "httpx"]);
+    let escape = |text: &str| escape_string(text, Language::Python);
+
+    for endpoint in http_endpoints(project) {
+        let has_body = !request_body(&endpoint).unwrap_or_default().is_empty();
+
+        let headers = endpoint
+            .headers
+            .iter()
+            .map(|header| {
+                format!(
+                    "\"{}\": \"{}\"",
+                    escape(&header.name),
+                    escape(&header.value)
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let (args, body) = match (has_body, python_type) {
+            (false, _) => ("", ""),
+            (true, PythonType::Requests) => ("body", "\n            data=body,"),
+            (true, PythonType::Httpx) => ("body", "\n            content=body,"),
+        };
+
+        // NOTE: text is a property on Python responses, json is a method
+        let response = match is_json(&endpoint) {
+            true => "json()",
+            false => "text",
+        };
+
+        let follow_redirects = match endpoint.follow_redirects {
+            true => "True",
+            false => "False",
+        };
+
+        let method_code = method_template
+            .replace("[NAME]", &identifiers.function_name(&endpoint.name))
+            .replace("[ARGS]", args)
+            .replace("[METHOD]", &escape(&endpoint.method))
+            .replace("[URL]", &escape(&endpoint.url))
+            .replace("[HEADERS]", &headers)
+            .replace("[BODY]", body)
+            .replace("[FOLLOW_REDIRECTS]", follow_redirects)
+            .replace("[MAX_REDIRECTS]", &endpoint.max_redirects.to_string())
+            .replace("[RESPONSE]", response);
+
+        module.push_str(&method_code);
+    }
+
+    module
+}
+
+pub fn generate_go(project: PersistedProject) -> anyhow::Result<OutputFile> {
+    let method_template = get_code_template("go_method_template.txt", GO_METHOD_TEMPLATE)?;
+    let module = go_module(&project, &method_template);
+
+    Ok(output_file(&project.name, &module, "go"))
+}
+
+fn go_module(project: &PersistedProject, method_template: &str) -> String {
+    let endpoints = http_endpoints(project);
+    let bodies: Vec<bool> = endpoints
+        .iter()
+        .map(|endpoint| !request_body(endpoint).unwrap_or_default().is_empty())
+        .collect();
+
+    let package = package_name(&project.name);
+
+    // NOTE: Go refuses to compile unused imports, strings is only used for bodies
+    let strings_import = match bodies.contains(&true) {
+        true => "\t\"strings\"\n",
+        false => "",
+    };
+
+    let mut module = format!(
+        "package {package}\n\nimport (\n\t\"fmt\"\n\t\"io\"\n\t\"net/http\"\n{strings_import})\n"
+    );
+
+    let mut identifiers = Identifiers::new(Language::Go, &[]);
+    let escape = |text: &str| escape_string(text, Language::Go);
+
+    for (endpoint, has_body) in endpoints.iter().zip(bodies) {
+        let headers: String = endpoint
+            .headers
+            .iter()
+            .map(|header| {
+                format!(
+                    "\trequest.Header.Set(\"{}\", \"{}\")\n",
+                    escape(&header.name),
+                    escape(&header.value)
+                )
+            })
+            .collect();
+
+        let (args, body) = match has_body {
+            true => ("body string", "strings.NewReader(body)"),
+            false => ("", "nil"),
+        };
+
+        let max_redirects = match endpoint.follow_redirects {
+            true => endpoint.max_redirects,
+            false => 0,
+        };
+
+        let method_code = method_template
+            .replace("[NAME]", &identifiers.function_name(&endpoint.name))
+            .replace("[ARGS]", args)
+            .replace("[METHOD]", &escape(&endpoint.method))
+            .replace("[URL]", &escape(&endpoint.url))
+            .replace("[BODY]", body)
+            .replace("[HEADERS]", &headers)
+            .replace("[MAX_REDIRECTS]", &max_redirects.to_string());
+
+        module.push_str(&method_code);
+    }
+
+    module
+}
+
+pub fn generate_php(project: PersistedProject) -> anyhow::Result<OutputFile> {
+    let method_template =
+        get_code_template("php_guzzle_method_template.txt", PHP_GUZZLE_METHOD_TEMPLATE)?;
+    let module = php_module(&project, &method_template);
+
+    Ok(output_file(&project.name, &module, "php"))
+}
+
+fn php_module(project: &PersistedProject, method_template: &str) -> String {
+    // NOTE: The namespace keeps the functions from clashing with PHP's own
+    let mut module = format!(
+        "<?php\n\nnamespace {};\n\nuse GuzzleHttp\\Client;\n",
+        namespace_name(&project.name)
+    );
+
+    let mut identifiers = Identifiers::new(Language::Php, &[]);
+    let escape = |text: &str| escape_string(text, Language::Php);
+
+    for endpoint in http_endpoints(project) {
+        let has_body = !request_body(&endpoint).unwrap_or_default().is_empty();
+
+        let headers = endpoint
+            .headers
+            .iter()
+            .map(|header| format!("'{}' => '{}'", escape(&header.name), escape(&header.value)))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let (args, body) = match has_body {
+            true => ("string $body", "\n        'body' => $body,"),
+            false => ("", ""),
+        };
+
+        let redirects = match endpoint.follow_redirects {
+            true => format!("['max' => {}]", endpoint.max_redirects),
+            false => String::from("false"),
+        };
+
+        let method_code = method_template
+            .replace("[NAME]", &identifiers.function_name(&endpoint.name))
+            .replace("[ARGS]", args)
+            .replace("[METHOD]", &escape(&endpoint.method))
+            .replace("[URL]", &escape(&endpoint.url))
+            .replace("[HEADERS]", &headers)
+            .replace("[BODY]", body)
+            .replace("[REDIRECTS]", &redirects);
 
         module.push_str(&method_code);
+    }
+
+    module
+}
+
+pub fn generate_reqwest(project: PersistedProject) -> anyhow::Result<OutputFile> {
+    let method_template = get_code_template(
+        "rust_reqwest_method_template.txt",
+        RUST_REQWEST_METHOD_TEMPLATE,
+    )?;
+    let module = reqwest_module(&project, &method_template, |endpoint| {
+        load_last_response(&project.name, &endpoint.name)
     });
 
-    let extension = match web_type {
-        WebType::JavaScript => "js",
-        WebType::TypeScript => "ts",
+    // NOTE: The ureq module is named {name}.rs, so this one gets its own name
+    Ok(output_file(
+        &format!("{}_reqwest", project.name),
+        &module,
+        "rs",
+    ))
+}
+
+fn reqwest_module(
+    project: &PersistedProject,
+    method_template: &str,
+    last_response: impl Fn(&PersistedEndpoint) -> Option<StoredResponse>,
+) -> String {
+    let mut functions = String::new();
+    let mut models = Models::default();
+
+    let mut identifiers = Identifiers::new(Language::Rust, &[]);
+    let escape = |text: &str| escape_string(text, Language::Rust);
+
+    for endpoint in http_endpoints(project) {
+        let has_body = !request_body(&endpoint).unwrap_or_default().is_empty();
+        let name = identifiers.function_name(&endpoint.name);
+
+        let (request_sample, response_sample) = json_samples(&endpoint, &last_response);
+        let request_type = request_sample
+            .filter(|_| has_body)
+            .map(|sample| models.infer(&format!("{name} request"), &sample));
+        let response_type =
+            response_sample.map(|sample| models.infer(&format!("{name} response"), &sample));
+
+        let mut calls: String = endpoint
+            .headers
+            .iter()
+            .map(|header| {
+                format!(
+                    "\n        .header(\"{}\", \"{}\")",
+                    escape(&header.name),
+                    escape(&header.value)
+                )
+            })
+            .collect();
+
+        let args = match (has_body, &request_type) {
+            (false, _) => String::new(),
+            (true, None) => {
+                calls.push_str("\n        .body(body.to_string())");
+                String::from("body: &str")
+            }
+            (true, Some(request_type)) => {
+                calls.push_str("\n        .json(body)");
+                format!("body: &{}", request_type.rust())
+            }
+        };
+
+        let (return_type, response) = match &response_type {
+            Some(response_type) => (response_type.rust(), "json()"),
+            None => (String::from("String"), "text()"),
+        };
+
+        let redirect_policy = match endpoint.follow_redirects {
+            true => format!("Policy::limited({})", endpoint.max_redirects),
+            false => String::from("Policy::none()"),
+        };
+
+        let method_code = method_template
+            .replace("[NAME]", &name)
+            .replace("[ARGS]", &args)
+            .replace("[RETURN_TYPE]", &return_type)
+            .replace("[METHOD]", &escape(&endpoint.method))
+            .replace("[URL]", &escape(&endpoint.url))
+            .replace("[CALLS]", &calls)
+            .replace("[REDIRECT_POLICY]", &redirect_policy)
+            .replace("[RESPONSE]", response);
+
+        functions.push_str(&method_code);
+    }
+
+    let mut module =
+        String::from("use std::error::Error;\n\nuse reqwest::{redirect::Policy, Client};\n");
+    if !models.is_empty() {
+        module.push_str("use serde::{Deserialize, Serialize};\n");
+        module.push_str(&models.rust_structs());
+    }
+
+    module.push_str(&functions);
+    module
+}
+
+/// The project's HTTP endpoints with its variables filled in
+fn http_endpoints(project: &PersistedProject) -> Vec<PersistedEndpoint> {
+    project
+        .endpoints
+        .iter()
+        .filter(|endpoint| endpoint.kind == EndpointKind::Http)
+        .map(|endpoint| resolve_endpoint(project, endpoint))
+        .collect()
+}
+
+/// The endpoint's JSON request body and last successful JSON response, the
+/// samples its models are inferred from
+fn json_samples(
+    endpoint: &PersistedEndpoint,
+    last_response: &impl Fn(&PersistedEndpoint) -> Option<StoredResponse>,
+) -> (Option<Value>, Option<Value>) {
+    let is_structured = |sample: &Value| sample.is_object() || sample.is_array();
+
+    // NOTE: GraphQL bodies are built from the query, callers never pass them in
+    let request = match endpoint.body_mode {
+        BodyMode::Raw => serde_json::from_str::<Value>(&endpoint.body)
+            .ok()
+            .filter(is_structured),
+        BodyMode::GraphQL => None,
     };
 
-    write_code(&project.name, &module, extension)
+    let response = last_response(endpoint)
+        .filter(|response| (200..300).contains(&response.status))
+        .and_then(|response| serde_json::from_str::<Value>(&response.body).ok())
+        .filter(is_structured);
+
+    (request, response)
 }
 
-fn get_rust_method_template() -> anyhow::Result<String> {
-    let mut app_dir = get_app_dir("code_templates")?;
-    app_dir.push("rust_method_template.txt");
+fn is_json(endpoint: &PersistedEndpoint) -> bool {
+    endpoint.body_mode == BodyMode::GraphQL
+        || endpoint.headers.iter().any(|header| {
+            header.name.eq_ignore_ascii_case("content-type")
+                && header.value.starts_with("application/json")
+        })
+}
 
-    match fs::read_to_string(app_dir.clone()) {
-        Ok(template) => Ok(template),
-        Err(_) => {
-            fs::write(app_dir, RUST_METHOD_TEMPLATE)?;
+/// A shell script with a command for each of the project's HTTP endpoints
+pub fn generate_shell(
+    project: PersistedProject,
+    shell_type: ShellType,
+) -> anyhow::Result<OutputFile> {
+    let script = shell_script(&project, &shell_type)?;
 
-            Ok(RUST_METHOD_TEMPLATE.to_string())
-        }
+    let extension = match shell_type {
+        ShellType::Curl => "curl.sh",
+        ShellType::HTTPie => "httpie.sh",
+        ShellType::Wget => "wget.sh",
+    };
+
+    Ok(output_file(&project.name, &script, extension))
+}
+
+fn shell_script(project: &PersistedProject, shell_type: &ShellType) -> anyhow::Result<String> {
+    let mut script = String::from("#!/bin/sh\n");
+
+    for endpoint in http_endpoints(project) {
+        // NOTE: A line break in the name would end the comment
+        let name = endpoint.name.replace(['\n', '\r'], " ");
+        script.push_str(&format!("\n# {name}\n"));
+        script.push_str(&shell_snippet(&endpoint, shell_type)?);
+        script.push('\n');
     }
+
+    Ok(script)
 }
 
-fn get_method_template(web_type: &WebType) -> anyhow::Result<String> {
-    let mut app_dir = get_app_dir("code_templates")?;
+/// A command line that sends the endpoint's request, one option per line
+pub fn shell_snippet(
+    endpoint: &PersistedEndpoint,
+    shell_type: &ShellType,
+) -> anyhow::Result<String> {
+    let body = request_body(endpoint)?;
+
+    let mut headers: Vec<&Header> = endpoint
+        .headers
+        .iter()
+        .filter(|header| !header.name.trim().is_empty())
+        .collect();
+
+    // NOTE: GraphQL payloads are always JSON, even without a content type header
+    let graphql_content_type = Header {
+        name: String::from("content-type"),
+        value: String::from("application/json"),
+    };
+
+    let has_content_type = headers
+        .iter()
+        .any(|header| header.name.eq_ignore_ascii_case("content-type"));
+
+    if endpoint.body_mode == BodyMode::GraphQL && !has_content_type {
+        headers.push(&graphql_content_type);
+    }
+
+    let arguments = match shell_type {
+        ShellType::Curl => curl_arguments(endpoint, &headers, &body),
+        ShellType::HTTPie => httpie_arguments(endpoint, &headers, &body),
+        ShellType::Wget => wget_arguments(endpoint, &headers, &body),
+    };
+
+    Ok(arguments.join(" \\\n  "))
+}
+
+fn curl_arguments(endpoint: &PersistedEndpoint, headers: &[&Header], body: &str) -> Vec<String> {
+    let mut arguments = vec![String::from("curl")];
+
+    // NOTE: curl sends a GET without a body and a POST with one, so the
+    // method is only needed when it is something else
+    match (endpoint.method.as_str(), body.is_empty()) {
+        ("GET", true) | ("POST", false) => {}
+        ("HEAD", true) => arguments.push(String::from("--head")),
+        (method, _) => arguments.push(format!("-X {}", shell_quote(method))),
+    }
+
+    arguments.push(shell_quote(&endpoint.url));
 
+    for header in headers {
+        // NOTE: "Name;" is how curl sends a header without a value
+        let header = match header.value.is_empty() {
+            true => format!("{};", header.name),
+            false => format!("{}: {}", header.name, header.value),
+        };
+
+        arguments.push(format!("-H {}", shell_quote(&header)));
+    }
+
+    if !body.is_empty() {
+        arguments.push(format!("--data-raw {}", shell_quote(body)));
+    }
+
+    if endpoint.follow_redirects {
+        arguments.push(String::from("-L"));
+        arguments.push(format!("--max-redirs {}", endpoint.max_redirects));
+    }
+
+    arguments
+}
+
+fn httpie_arguments(endpoint: &PersistedEndpoint, headers: &[&Header], body: &str) -> Vec<String> {
+    let mut arguments = vec![String::from("http")];
+
+    if endpoint.follow_redirects {
+        arguments.push(String::from("--follow"));
+        arguments.push(format!("--max-redirects {}", endpoint.max_redirects));
+    }
+
+    if !body.is_empty() {
+        arguments.push(format!("--raw {}", shell_quote(body)));
+    }
+
+    arguments.push(shell_quote(&endpoint.method));
+    arguments.push(shell_quote(&endpoint.url));
+
+    for header in headers {
+        // NOTE: HTTPie removes headers written as "Name:", "Name;" sends an empty one
+        let header = match header.value.is_empty() {
+            true => format!("{};", header.name),
+            false => format!("{}:{}", header.name, header.value),
+        };
+
+        arguments.push(shell_quote(&header));
+    }
+
+    arguments
+}
+
+fn wget_arguments(endpoint: &PersistedEndpoint, headers: &[&Header], body: &str) -> Vec<String> {
+    let mut arguments = vec![
+        String::from("wget"),
+        String::from("--quiet"),
+        String::from("--output-document=-"),
+    ];
+
+    if endpoint.method != "GET" || !body.is_empty() {
+        arguments.push(format!("--method={}", shell_quote(&endpoint.method)));
+    }
+
+    for header in headers {
+        let header = format!("{}: {}", header.name, header.value);
+        arguments.push(format!("--header={}", shell_quote(&header)));
+    }
+
+    if !body.is_empty() {
+        arguments.push(format!("--body-data={}", shell_quote(body)));
+    }
+
+    // NOTE: wget follows up to 20 redirects unless it is told otherwise
+    let max_redirects = match endpoint.follow_redirects {
+        true => endpoint.max_redirects,
+        false => 0,
+    };
+    arguments.push(format!("--max-redirect={max_redirects}"));
+
+    arguments.push(shell_quote(&endpoint.url));
+
+    arguments
+}
+
+/// Quotes text for POSIX shells, nothing inside single quotes is expanded
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// The module templates in the code_templates app dir, sorted by name
+pub fn module_templates() -> anyhow::Result<Vec<ModuleTemplate>> {
+    let templates_dir = get_app_dir("code_templates")?;
+
+    let mut templates: Vec<ModuleTemplate> = fs::read_dir(templates_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| ModuleTemplate::from_path(entry.path()))
+        .collect();
+
+    templates.sort_by(|first, second| first.name.cmp(&second.name));
+
+    Ok(templates)
+}
+
+pub fn generate_from_template(
+    project: PersistedProject,
+    module_template: &ModuleTemplate,
+) -> anyhow::Result<OutputFile> {
+    let source = fs::read_to_string(&module_template.path)?;
+    let module = Template::parse(&source)?.render(&template_context(&project))?;
+
+    Ok(output_file(
+        &format!("{}_{}", project.name, module_template.name),
+        &module,
+        &module_template.extension,
+    ))
+}
+
+/// What module templates can use, the project and its HTTP endpoints with
+/// variables filled in
+fn template_context(project: &PersistedProject) -> Value {
+    let mut snake_case = Identifiers::with_casing(Casing::Snake);
+    let mut camel_case = Identifiers::with_casing(Casing::Camel);
+    let mut pascal_case = Identifiers::with_casing(Casing::Pascal);
+
+    let endpoints: Vec<Value> = http_endpoints(project)
+        .iter()
+        .map(|endpoint| {
+            let body = request_body(endpoint).unwrap_or_default();
+
+            let content_type = endpoint
+                .headers
+                .iter()
+                .find(|header| header.name.eq_ignore_ascii_case("content-type"))
+                .map(|header| header.value.clone())
+                .unwrap_or_default();
+
+            let headers: Vec<Value> = endpoint
+                .headers
+                .iter()
+                .filter(|header| !header.name.trim().is_empty())
+                .map(|header| json!({ "name": header.name, "value": header.value }))
+                .collect();
+
+            // NOTE: Casing filters can turn two names into the same identifier,
+            // these are unique within the module
+            let identifiers = json!({
+                "snake_case": snake_case.function_name(&endpoint.name),
+                "camel_case": camel_case.function_name(&endpoint.name),
+                "pascal_case": pascal_case.function_name(&endpoint.name),
+            });
+
+            json!({
+                "name": endpoint.name,
+                "identifiers": identifiers,
+                "method": endpoint.method,
+                "url": endpoint.url,
+                "headers": headers,
+                "body": body,
+                "has_body": !body.is_empty(),
+                "content_type": content_type,
+                "is_json": is_json(endpoint),
+                "follow_redirects": endpoint.follow_redirects,
+                "max_redirects": endpoint.max_redirects,
+            })
+        })
+        .collect();
+
+    // NOTE: Lets templates import what bodies need once, at the top
+    let has_bodies = endpoints
+        .iter()
+        .any(|endpoint| endpoint["has_body"] == true);
+
+    json!({
+        "project": { "name": project.name },
+        "endpoints": endpoints,
+        "has_bodies": has_bodies,
+    })
+}
+
+fn get_rust_method_template() -> anyhow::Result<String> {
+    // NOTE: The file name is versioned since templates written before typed
+    // responses return a String, which no longer matches the generated calls
+    get_code_template("rust_method_template_v2.txt", RUST_METHOD_TEMPLATE)
+}
+
+fn get_method_template(web_type: &WebType) -> anyhow::Result<String> {
     let template = match web_type {
         WebType::JavaScript => JS_METHOD_TEMPLATE,
         WebType::TypeScript => TS_METHOD_TEMPLATE,
@@ -178,24 +1001,266 @@ fn get_method_template(web_type: &WebType) -> anyhow::Result<String> {
         WebType::TypeScript => "typescript_method_template.txt",
     };
 
+    get_code_template(template_file_name, template)
+}
+
+/// Reads a method template from the code_templates app dir, the default
+/// template is written there the first time so it can be customized
+fn get_code_template(template_file_name: &str, default_template: &str) -> anyhow::Result<String> {
+    let mut app_dir = get_app_dir("code_templates")?;
     app_dir.push(template_file_name);
 
     match fs::read_to_string(app_dir.clone()) {
         Ok(template) => Ok(template),
         Err(_) => {
-            fs::write(app_dir, template)?;
+            fs::write(app_dir, default_template)?;
 
-            Ok(template.to_string())
+            Ok(default_template.to_string())
         }
     }
 }
 
-fn write_code(name: &str, module: &str, extension: &str) -> anyhow::Result<()> {
-    let mut docs_dir = get_documents_dir()?;
+fn output_file(name: &str, module: &str, extension: &str) -> OutputFile {
     let module_name = name.replace(" ", "_");
-    docs_dir.push(format!("{module_name}.{extension}"));
 
-    fs::write(docs_dir, module)?;
+    OutputFile::new(format!("{module_name}.{extension}"), module)
+}
+
+#[test]
+fn test_shell_snippets() {
+    let endpoint = PersistedEndpoint {
+        url: String::from("https://api.example.com/users?sort=name&limit=5"),
+        method: String::from("PUT"),
+        headers: vec![
+            Header {
+                name: String::from("content-type"),
+                value: String::from("application/json"),
+            },
+            Header {
+                name: String::from("x-note"),
+                value: String::from("it's \"quoted\" $HOME"),
+            },
+        ],
+        body: String::from("{\"name\": \"O'Brien\"}"),
+        ..PersistedEndpoint::default()
+    };
+
+    assert_eq!(
+        shell_snippet(&endpoint, &ShellType::Curl).unwrap(),
+        r#"curl \
+  -X 'PUT' \
+  'https://api.example.com/users?sort=name&limit=5' \
+  -H 'content-type: application/json' \
+  -H 'x-note: it'\''s "quoted" $HOME' \
+  --data-raw '{"name": "O'\''Brien"}' \
+  -L \
+  --max-redirs 5"#
+    );
+
+    assert_eq!(
+        shell_snippet(&endpoint, &ShellType::HTTPie).unwrap(),
+        r#"http \
+  --follow \
+  --max-redirects 5 \
+  --raw '{"name": "O'\''Brien"}' \
+  'PUT' \
+  'https://api.example.com/users?sort=name&limit=5' \
+  'content-type:application/json' \
+  'x-note:it'\''s "quoted" $HOME'"#
+    );
+
+    assert_eq!(
+        shell_snippet(&endpoint, &ShellType::Wget).unwrap(),
+        r#"wget \
+  --quiet \
+  --output-document=- \
+  --method='PUT' \
+  --header='content-type: application/json' \
+  --header='x-note: it'\''s "quoted" $HOME' \
+  --body-data='{"name": "O'\''Brien"}' \
+  --max-redirect=5 \
+  'https://api.example.com/users?sort=name&limit=5'"#
+    );
+}
+
+#[test]
+fn test_go_module_imports() {
+    let project = PersistedProject {
+        name: String::from("Pet Store 2"),
+        endpoints: vec![PersistedEndpoint {
+            name: String::from("list_pets"),
+            url: String::from("{{baseUrl}}/pets"),
+            ..PersistedEndpoint::default()
+        }],
+        tls: Default::default(),
+        proxy: None,
+        proto_files: vec![],
+        variables: vec![crate::projects::Variable {
+            name: String::from("baseUrl"),
+            value: String::from("https://api.example.com"),
+        }],
+        folders: vec![],
+    };
+
+    let module = go_module(&project, GO_METHOD_TEMPLATE);
+
+    assert!(module
+        .starts_with("package petstore2\n\nimport (\n\t\"fmt\"\n\t\"io\"\n\t\"net/http\"\n)\n"));
+    assert!(module.contains(
+        "func ListPets() (string, error) {\n\trequest, err := http.NewRequest(\"GET\", \"https://api.example.com/pets\", nil)"
+    ));
+}
+
+#[cfg(test)]
+fn golden_project() -> PersistedProject {
+    let header = |name: &str, value: &str| Header {
+        name: String::from(name),
+        value: String::from(value),
+    };
+
+    let endpoint = |name: &str, method: &str, url: &str| PersistedEndpoint {
+        name: String::from(name),
+        method: String::from(method),
+        url: String::from(url),
+        ..PersistedEndpoint::default()
+    };
+
+    PersistedProject {
+        name: String::from("Pet Store"),
+        endpoints: vec![
+            PersistedEndpoint {
+                headers: vec![header("x-note", "say \"hi\" to C:\\pets, it's $HOME")],
+                ..endpoint("get-user (v2)", "GET", "{{baseUrl}}/users?name=\"O'Brien\"")
+            },
+            endpoint("Get User V2", "GET", "{{baseUrl}}/v2/users"),
+            endpoint("delete", "DELETE", "{{baseUrl}}/users/1"),
+            PersistedEndpoint {
+                headers: vec![header("content-type", "application/json")],
+                body: String::from("{\"code\": \"123\"}"),
+                follow_redirects: false,
+                ..endpoint("2fa check", "POST", "{{baseUrl}}/2fa")
+            },
+        ],
+        tls: Default::default(),
+        proxy: None,
+        proto_files: vec![],
+        variables: vec![crate::projects::Variable {
+            name: String::from("baseUrl"),
+            value: String::from("https://api.example.com"),
+        }],
+        folders: vec![],
+    }
+}
+
+#[cfg(test)]
+fn golden_response(endpoint: &PersistedEndpoint) -> Option<StoredResponse> {
+    let body = match endpoint.name.as_str() {
+        "get-user (v2)" => {
+            r#"{"id": 1, "first-name": "Ada", "type": "admin", "address": {"city": "London", "zip": null}, "friends": [{"id": 2}, {"id": 3, "nickname": "Bob"}], "score": 1.5}"#
+        }
+        "2fa check" => r#"{"verified": true}"#,
+        _ => return None,
+    };
+
+    Some(StoredResponse::new(200, Some("application/json"), body))
+}
+
+/// Compares generated code with its file in src/code_gen/golden, running the
+/// tests with UPDATE_GOLDEN=1 writes the files instead
+#[cfg(test)]
+fn assert_golden(file_name: &str, generated: &str) {
+    let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("src/code_gen/golden");
+    path.push(file_name);
+
+    if std::env::var("UPDATE_GOLDEN").is_ok() {
+        fs::write(&path, generated).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap();
+    assert_eq!(
+        generated, expected,
+        "{file_name} does not match its golden file"
+    );
+}
+
+#[test]
+fn test_golden_modules() {
+    let project = golden_project();
+
+    assert_golden(
+        "ureq.rs.golden",
+        &rust_module(&project, RUST_METHOD_TEMPLATE, golden_response),
+    );
+    assert_golden(
+        "reqwest.rs.golden",
+        &reqwest_module(&project, RUST_REQWEST_METHOD_TEMPLATE, golden_response),
+    );
+    assert_golden(
+        "fetch.js.golden",
+        &web_module(
+            &project,
+            &WebType::JavaScript,
+            JS_METHOD_TEMPLATE,
+            golden_response,
+        ),
+    );
+    assert_golden(
+        "fetch.ts.golden",
+        &web_module(
+            &project,
+            &WebType::TypeScript,
+            TS_METHOD_TEMPLATE,
+            golden_response,
+        ),
+    );
+    assert_golden(
+        "requests.py.golden",
+        &python_module(
+            &project,
+            &PythonType::Requests,
+            PYTHON_REQUESTS_METHOD_TEMPLATE,
+        ),
+    );
+    assert_golden(
+        "httpx.py.golden",
+        &python_module(&project, &PythonType::Httpx, PYTHON_HTTPX_METHOD_TEMPLATE),
+    );
+    assert_golden(
+        "net_http.go.golden",
+        &go_module(&project, GO_METHOD_TEMPLATE),
+    );
+    assert_golden(
+        "guzzle.php.golden",
+        &php_module(&project, PHP_GUZZLE_METHOD_TEMPLATE),
+    );
+    assert_golden(
+        "curl.sh.golden",
+        &shell_script(&project, &ShellType::Curl).unwrap(),
+    );
+    assert_golden(
+        "httpie.sh.golden",
+        &shell_script(&project, &ShellType::HTTPie).unwrap(),
+    );
+    assert_golden(
+        "wget.sh.golden",
+        &shell_script(&project, &ShellType::Wget).unwrap(),
+    );
+}
+
+#[test]
+fn test_golden_module_template() {
+    let source = fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/src/code_gen/golden/okhttp.kt.template"
+    ))
+    .unwrap();
+
+    let module = Template::parse(&source)
+        .unwrap()
+        .render(&template_context(&golden_project()))
+        .unwrap();
 
-    Ok(())
+    assert_golden("okhttp.kt.golden", &module);
 }
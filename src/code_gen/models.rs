@@ -0,0 +1,343 @@
+use serde_json::Value;
+
+use super::sanitize::{escape_string, Casing, Identifiers, Language};
+
+/// The structure of one or more JSON samples, before it is named
+#[derive(Clone, Debug, PartialEq)]
+enum Shape {
+    Null,
+    Boolean,
+    Integer,
+    Float,
+    String,
+    Array(Box<Shape>),
+    Object(Vec<(String, Shape, bool)>),
+    Nullable(Box<Shape>),
+
+    // NOTE: The items of an empty array
+    Empty,
+    Mixed,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ModelType {
+    Unknown,
+    Null,
+    Boolean,
+    Integer,
+    Float,
+    String,
+    Array(Box<ModelType>),
+    Nullable(Box<ModelType>),
+    Model(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    pub key: String,
+    pub model_type: ModelType,
+    pub required: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Model {
+    pub name: String,
+    pub fields: Vec<Field>,
+}
+
+/// Models inferred from the JSON bodies of a generated module, named so they
+/// don't collide with each other
+pub struct Models {
+    models: Vec<Model>,
+    names: Identifiers,
+}
+
+impl Default for Models {
+    fn default() -> Self {
+        Models {
+            models: vec![],
+            names: Identifiers::with_casing(Casing::Pascal),
+        }
+    }
+}
+
+impl Models {
+    pub fn is_empty(&self) -> bool {
+        self.models.is_empty()
+    }
+
+    /// The type of a JSON value, objects in it become models named after `name`
+    pub fn infer(&mut self, name: &str, value: &Value) -> ModelType {
+        let shape = shape(value);
+        self.model_type(name, &shape)
+    }
+
+    fn model_type(&mut self, name: &str, shape: &Shape) -> ModelType {
+        match shape {
+            Shape::Null => ModelType::Null,
+            Shape::Boolean => ModelType::Boolean,
+            Shape::Integer => ModelType::Integer,
+            Shape::Float => ModelType::Float,
+            Shape::String => ModelType::String,
+            Shape::Empty | Shape::Mixed => ModelType::Unknown,
+            Shape::Array(item) => {
+                ModelType::Array(Box::new(self.model_type(&format!("{name} item"), item)))
+            }
+            Shape::Nullable(shape) => ModelType::Nullable(Box::new(self.model_type(name, shape))),
+            Shape::Object(properties) => {
+                let model_name = self.names.function_name(name);
+
+                // NOTE: Parents are listed before the models of their fields
+                let index = self.models.len();
+                self.models.push(Model {
+                    name: model_name.clone(),
+                    fields: vec![],
+                });
+
+                let fields = properties
+                    .iter()
+                    .map(|(key, shape, required)| Field {
+                        key: key.clone(),
+                        model_type: self.model_type(&format!("{model_name} {key}"), shape),
+                        required: *required,
+                    })
+                    .collect();
+
+                self.models[index].fields = fields;
+
+                ModelType::Model(model_name)
+            }
+        }
+    }
+
+    pub fn typescript_interfaces(&self) -> String {
+        let mut interfaces = String::new();
+
+        for model in &self.models {
+            interfaces.push_str(&format!("\nexport interface {} {{\n", model.name));
+
+            for field in &model.fields {
+                let key = match is_javascript_identifier(&field.key) {
+                    true => field.key.clone(),
+                    false => format!("\"{}\"", escape_string(&field.key, Language::TypeScript)),
+                };
+
+                let optional = match field.required {
+                    true => "",
+                    false => "?",
+                };
+
+                interfaces.push_str(&format!(
+                    "  {key}{optional}: {};\n",
+                    field.model_type.typescript()
+                ));
+            }
+
+            interfaces.push_str("}\n");
+        }
+
+        interfaces
+    }
+
+    pub fn rust_structs(&self) -> String {
+        let mut structs = String::new();
+
+        for model in &self.models {
+            structs.push_str(&format!(
+                "\n#[derive(Debug, Clone, Serialize, Deserialize)]\npub struct {} {{\n",
+                model.name
+            ));
+
+            let mut field_names = Identifiers::new(Language::Rust, &[]);
+
+            for field in &model.fields {
+                let field_name = field_names.function_name(&field.key);
+                if field_name != field.key {
+                    structs.push_str(&format!(
+                        "    #[serde(rename = \"{}\")]\n",
+                        escape_string(&field.key, Language::Rust)
+                    ));
+                }
+
+                // NOTE: Missing fields are read as None, nullable ones already are options
+                let rust_type = match (&field.model_type, field.required) {
+                    (ModelType::Nullable(_) | ModelType::Null, _) | (_, true) => {
+                        field.model_type.rust()
+                    }
+                    (model_type, false) => format!("Option<{}>", model_type.rust()),
+                };
+
+                structs.push_str(&format!("    pub {field_name}: {rust_type},\n"));
+            }
+
+            structs.push_str("}\n");
+        }
+
+        structs
+    }
+}
+
+impl ModelType {
+    pub fn typescript(&self) -> String {
+        match self {
+            ModelType::Unknown => String::from("unknown"),
+            ModelType::Null => String::from("null"),
+            ModelType::Boolean => String::from("boolean"),
+            ModelType::Integer | ModelType::Float => String::from("number"),
+            ModelType::String => String::from("string"),
+            ModelType::Array(item) => match item.as_ref() {
+                ModelType::Nullable(_) => format!("({})[]", item.typescript()),
+                item => format!("{}[]", item.typescript()),
+            },
+            ModelType::Nullable(model_type) => format!("{} | null", model_type.typescript()),
+            ModelType::Model(name) => name.clone(),
+        }
+    }
+
+    pub fn rust(&self) -> String {
+        match self {
+            ModelType::Unknown => String::from("serde_json::Value"),
+            ModelType::Null => String::from("Option<serde_json::Value>"),
+            ModelType::Boolean => String::from("bool"),
+            ModelType::Integer => String::from("i64"),
+            ModelType::Float => String::from("f64"),
+            ModelType::String => String::from("String"),
+            ModelType::Array(item) => format!("Vec<{}>", item.rust()),
+            ModelType::Nullable(model_type) => format!("Option<{}>", model_type.rust()),
+            ModelType::Model(name) => name.clone(),
+        }
+    }
+}
+
+fn shape(value: &Value) -> Shape {
+    match value {
+        Value::Null => Shape::Null,
+        Value::Bool(_) => Shape::Boolean,
+        Value::Number(number) if number.is_f64() => Shape::Float,
+        Value::Number(_) => Shape::Integer,
+        Value::String(_) => Shape::String,
+        Value::Array(items) => {
+            Shape::Array(Box::new(items.iter().map(shape).fold(Shape::Empty, merge)))
+        }
+        Value::Object(properties) => Shape::Object(
+            properties
+                .iter()
+                .map(|(key, value)| (key.clone(), shape(value), true))
+                .collect(),
+        ),
+    }
+}
+
+/// One shape that fits both samples, array items are merged into a single
+/// shape so fields missing from some of them become optional
+fn merge(first: Shape, second: Shape) -> Shape {
+    match (first, second) {
+        (Shape::Empty, shape) | (shape, Shape::Empty) => shape,
+        (first, second) if first == second => first,
+
+        (Shape::Integer, Shape::Float) | (Shape::Float, Shape::Integer) => Shape::Float,
+
+        (Shape::Null, shape) | (shape, Shape::Null) => match shape {
+            Shape::Nullable(shape) => Shape::Nullable(shape),
+            shape => Shape::Nullable(Box::new(shape)),
+        },
+
+        (Shape::Nullable(first), second) | (second, Shape::Nullable(first)) => {
+            match merge(*first, second) {
+                Shape::Nullable(shape) => Shape::Nullable(shape),
+                shape => Shape::Nullable(Box::new(shape)),
+            }
+        }
+
+        (Shape::Array(first), Shape::Array(second)) => {
+            Shape::Array(Box::new(merge(*first, *second)))
+        }
+
+        (Shape::Object(first), Shape::Object(second)) => {
+            let mut properties: Vec<(String, Shape, bool)> = first
+                .into_iter()
+                .map(|(key, shape, required)| {
+                    let required = required && second.iter().any(|(other, _, _)| *other == key);
+                    (key, shape, required)
+                })
+                .collect();
+
+            for (key, shape, required) in second {
+                match properties.iter_mut().find(|(other, _, _)| *other == key) {
+                    Some(property) => {
+                        property.1 = merge(std::mem::replace(&mut property.1, Shape::Empty), shape);
+                        property.2 = property.2 && required;
+                    }
+                    None => properties.push((key, shape, false)),
+                }
+            }
+
+            Shape::Object(properties)
+        }
+
+        _ => Shape::Mixed,
+    }
+}
+
+fn is_javascript_identifier(key: &str) -> bool {
+    key.starts_with(|char: char| char.is_ascii_alphabetic() || char == '_' || char == '$')
+        && key
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '_' || char == '$')
+}
+
+#[test]
+fn test_infer_models() {
+    let mut models = Models::default();
+    let sample = serde_json::json!([
+        { "id": 1, "first-name": "Ada", "tags": [], "score": 1, "type": "user" },
+        { "id": 2, "tags": ["admin"], "score": 2.5, "type": "user", "address": { "zip": null } },
+    ]);
+
+    assert_eq!(
+        models.infer("list users response", &sample),
+        ModelType::Array(Box::new(ModelType::Model(String::from(
+            "ListUsersResponseItem"
+        ))))
+    );
+
+    assert_eq!(
+        models.typescript_interfaces(),
+        "
+export interface ListUsersResponseItem {
+  \"first-name\"?: string;
+  id: number;
+  score: number;
+  tags: string[];
+  type: string;
+  address?: ListUsersResponseItemAddress;
+}
+
+export interface ListUsersResponseItemAddress {
+  zip: null;
+}
+"
+    );
+
+    assert_eq!(
+        models.rust_structs(),
+        "
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListUsersResponseItem {
+    #[serde(rename = \"first-name\")]
+    pub first_name: Option<String>,
+    pub id: i64,
+    pub score: f64,
+    pub tags: Vec<String>,
+    #[serde(rename = \"type\")]
+    pub type_: String,
+    pub address: Option<ListUsersResponseItemAddress>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListUsersResponseItemAddress {
+    pub zip: Option<serde_json::Value>,
+}
+"
+    );
+}
@@ -0,0 +1,500 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    TypeScript,
+    JavaScript,
+    Python,
+    Go,
+    Php,
+    Kotlin,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Casing {
+    Snake,
+    Camel,
+    Pascal,
+}
+
+const RUST_KEYWORDS: [&str; 53] = [
+    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "union", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+const JAVASCRIPT_KEYWORDS: [&str; 48] = [
+    "arguments",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "eval",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+const PYTHON_KEYWORDS: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+const PHP_KEYWORDS: [&str; 72] = [
+    "__halt_compiler",
+    "abstract",
+    "and",
+    "array",
+    "as",
+    "break",
+    "callable",
+    "case",
+    "catch",
+    "class",
+    "clone",
+    "const",
+    "continue",
+    "declare",
+    "default",
+    "die",
+    "do",
+    "echo",
+    "else",
+    "elseif",
+    "empty",
+    "enddeclare",
+    "endfor",
+    "endforeach",
+    "endif",
+    "endswitch",
+    "endwhile",
+    "enum",
+    "eval",
+    "exit",
+    "extends",
+    "final",
+    "finally",
+    "fn",
+    "for",
+    "foreach",
+    "function",
+    "global",
+    "goto",
+    "if",
+    "implements",
+    "include",
+    "include_once",
+    "instanceof",
+    "insteadof",
+    "interface",
+    "isset",
+    "list",
+    "match",
+    "namespace",
+    "new",
+    "or",
+    "print",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "require",
+    "require_once",
+    "return",
+    "static",
+    "switch",
+    "throw",
+    "trait",
+    "try",
+    "unset",
+    "use",
+    "var",
+    "while",
+    "xor",
+    "yield",
+    "parent",
+];
+
+const KOTLIN_KEYWORDS: [&str; 28] = [
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+impl Language {
+    fn casing(&self) -> Casing {
+        match self {
+            Language::Rust | Language::Python => Casing::Snake,
+            Language::TypeScript | Language::JavaScript | Language::Php | Language::Kotlin => {
+                Casing::Camel
+            }
+
+            // NOTE: Capitalized names are exported from Go packages
+            Language::Go => Casing::Pascal,
+        }
+    }
+
+    fn is_keyword(&self, identifier: &str) -> bool {
+        match self {
+            Language::Rust => RUST_KEYWORDS.contains(&identifier),
+            Language::TypeScript | Language::JavaScript => {
+                JAVASCRIPT_KEYWORDS.contains(&identifier)
+            }
+            Language::Python => PYTHON_KEYWORDS.contains(&identifier),
+            Language::Kotlin => KOTLIN_KEYWORDS.contains(&identifier),
+            Language::Php => PHP_KEYWORDS.contains(&identifier.to_lowercase().as_str()),
+
+            // NOTE: Go keywords are all lowercase, so exported names can't be one
+            Language::Go => false,
+        }
+    }
+
+    /// PHP function names are case insensitive
+    fn same_identifier(&self, first: &str, second: &str) -> bool {
+        match self {
+            Language::Php => first.eq_ignore_ascii_case(second),
+            _ => first == second,
+        }
+    }
+}
+
+/// Function names for one generated module, each endpoint name becomes a
+/// valid identifier that doesn't collide with keywords or the other names
+pub struct Identifiers {
+    language: Option<Language>,
+    casing: Casing,
+    taken: Vec<String>,
+}
+
+impl Identifiers {
+    /// Reserved names are the ones the generated module already uses, like
+    /// the HTTP client it imports
+    pub fn new(language: Language, reserved: &[&str]) -> Self {
+        Identifiers {
+            language: Some(language),
+            casing: language.casing(),
+            taken: reserved.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    /// Names for a language tome doesn't know, only kept apart from each other
+    pub fn with_casing(casing: Casing) -> Self {
+        Identifiers {
+            language: None,
+            casing,
+            taken: vec![],
+        }
+    }
+
+    pub fn function_name(&mut self, name: &str) -> String {
+        let casing = self.casing;
+        let mut identifier = convert_case(name, casing);
+
+        if self
+            .language
+            .is_some_and(|language| language.is_keyword(&identifier))
+        {
+            identifier.push('_');
+        }
+
+        let is_taken = |candidate: &str, taken: &[String]| {
+            taken.iter().any(|name| match self.language {
+                Some(language) => language.same_identifier(name, candidate),
+                None => name == candidate,
+            })
+        };
+
+        if is_taken(&identifier, &self.taken) {
+            // NOTE: getUserV2 and getUserV2_2 are easier to tell apart than getUserV22
+            let separator = match casing {
+                Casing::Snake => "_",
+                Casing::Camel | Casing::Pascal
+                    if identifier.ends_with(|char: char| char.is_ascii_digit()) =>
+                {
+                    "_"
+                }
+                Casing::Camel | Casing::Pascal => "",
+            };
+
+            identifier = (2..)
+                .map(|count| format!("{identifier}{separator}{count}"))
+                .find(|candidate| !is_taken(candidate, &self.taken))
+                .unwrap_or_default();
+        }
+
+        self.taken.push(identifier.clone());
+        identifier
+    }
+}
+
+/// Converts a name to an identifier in the given casing
+pub fn convert_case(name: &str, casing: Casing) -> String {
+    let mut words = split_words(name);
+
+    // NOTE: Identifiers can't start with a digit, or be empty
+    if words
+        .first()
+        .is_none_or(|word| word.starts_with(|char: char| char.is_ascii_digit()))
+    {
+        words.insert(0, String::from("request"));
+    }
+
+    join_words(&words, &casing)
+}
+
+/// Lowercase words of a name, split at anything that isn't an ASCII letter or
+/// digit and at camelCase boundaries, "getHTTPUser (v2)" is get, http, user, v2
+fn split_words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words: Vec<String> = vec![];
+    let mut word = String::new();
+
+    for (index, char) in chars.iter().enumerate() {
+        if !char.is_ascii_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+
+            continue;
+        }
+
+        let previous = index.checked_sub(1).map(|index| chars[index]);
+        let next = chars.get(index + 1);
+
+        let starts_word = char.is_ascii_uppercase()
+            && !word.is_empty()
+            && previous.is_some_and(|previous| {
+                previous.is_ascii_lowercase()
+                    || (!previous.is_ascii_lowercase()
+                        && next.is_some_and(|next| next.is_ascii_lowercase()))
+            });
+
+        if starts_word {
+            words.push(std::mem::take(&mut word));
+        }
+
+        word.push(char.to_ascii_lowercase());
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn join_words(words: &[String], casing: &Casing) -> String {
+    let capitalize = |word: &String| {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+            None => String::new(),
+        }
+    };
+
+    match casing {
+        Casing::Snake => words.join("_"),
+        Casing::Pascal => words.iter().map(capitalize).collect(),
+        Casing::Camel => words
+            .iter()
+            .enumerate()
+            .map(|(index, word)| match index {
+                0 => word.clone(),
+                _ => capitalize(word),
+            })
+            .collect(),
+    }
+}
+
+/// Go package name for a project, lowercase letters and digits only
+pub fn package_name(name: &str) -> String {
+    let package = split_words(name).concat();
+
+    match package.starts_with(|char: char| char.is_ascii_alphabetic()) {
+        true => package,
+        false => format!("api{package}"),
+    }
+}
+
+/// PHP namespace for a project, which keeps its functions away from PHP's own
+pub fn namespace_name(name: &str) -> String {
+    let mut identifiers = Identifiers::new(Language::Go, &[]);
+    identifiers.function_name(name)
+}
+
+/// Escapes text for the inside of a string literal, double quoted in every
+/// language but PHP where the templates use single quotes
+pub fn escape_string(text: &str, language: Language) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for char in text.chars() {
+        if language == Language::Php {
+            match char {
+                '\\' => escaped.push_str("\\\\"),
+                '\'' => escaped.push_str("\\'"),
+                char => escaped.push(char),
+            }
+
+            continue;
+        }
+
+        match char {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+
+            // NOTE: Kotlin interpolates $name inside string literals
+            '$' if language == Language::Kotlin => escaped.push_str("\\$"),
+
+            // NOTE: Line and paragraph separators end a line in older JavaScript
+            '\u{2028}' | '\u{2029}'
+                if matches!(language, Language::JavaScript | Language::TypeScript) =>
+            {
+                escaped.push_str(&format!("\\u{:04x}", char as u32))
+            }
+
+            char if char.is_control() => match language {
+                Language::Rust => escaped.push_str(&format!("\\u{{{:x}}}", char as u32)),
+                Language::JavaScript | Language::TypeScript => {
+                    escaped.push_str(&format!("\\u{:04x}", char as u32))
+                }
+                Language::Python | Language::Go if (char as u32) < 0x100 => {
+                    escaped.push_str(&format!("\\x{:02x}", char as u32))
+                }
+                _ => escaped.push_str(&format!("\\u{:04x}", char as u32)),
+            },
+
+            char => escaped.push(char),
+        }
+    }
+
+    escaped
+}
+
+#[test]
+fn test_function_names() {
+    let mut rust = Identifiers::new(Language::Rust, &[]);
+    assert_eq!(rust.function_name("get-user (v2)"), "get_user_v2");
+    assert_eq!(rust.function_name("Get User V2"), "get_user_v2_2");
+    assert_eq!(rust.function_name("type"), "type_");
+    assert_eq!(rust.function_name("2FA check"), "request_2fa_check");
+    assert_eq!(rust.function_name("   "), "request");
+
+    let mut typescript = Identifiers::new(Language::TypeScript, &["fetch"]);
+    assert_eq!(
+        typescript.function_name("getHTTPUser (v2)"),
+        "getHttpUserV2"
+    );
+    assert_eq!(typescript.function_name("delete"), "delete_");
+    assert_eq!(typescript.function_name("Fetch"), "fetch2");
+    assert_eq!(
+        typescript.function_name("get HTTP user v2"),
+        "getHttpUserV2_2"
+    );
+
+    let mut go = Identifiers::new(Language::Go, &[]);
+    assert_eq!(go.function_name("list pets"), "ListPets");
+    assert_eq!(
+        go.function_name("GetUserV2Response address"),
+        "GetUserV2ResponseAddress"
+    );
+
+    let mut php = Identifiers::new(Language::Php, &[]);
+    assert_eq!(php.function_name("List"), "list_");
+    assert_eq!(php.function_name("list pets"), "listPets");
+    assert_eq!(php.function_name("ListPets"), "listPets2");
+
+    assert_eq!(package_name("2nd Pet-Store"), "api2ndpetstore");
+    assert_eq!(namespace_name("pet store"), "PetStore");
+}
+
+#[test]
+fn test_escape_string() {
+    let text = "say \"hi\"\\\n\t\u{1}'";
+
+    assert_eq!(
+        escape_string(text, Language::Rust),
+        "say \\\"hi\\\"\\\\\\n\\t\\u{1}'"
+    );
+    assert_eq!(
+        escape_string(text, Language::TypeScript),
+        "say \\\"hi\\\"\\\\\\n\\t\\u0001'"
+    );
+    assert_eq!(
+        escape_string(text, Language::Python),
+        "say \\\"hi\\\"\\\\\\n\\t\\x01'"
+    );
+    assert_eq!(
+        escape_string(text, Language::Php),
+        "say \"hi\"\\\\\n\t\u{1}\\'"
+    );
+}
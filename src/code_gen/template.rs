@@ -0,0 +1,537 @@
+use anyhow::{anyhow, bail};
+use serde_json::Value;
+
+use super::{
+    sanitize::{convert_case, escape_string, Casing, Language},
+    shell_quote,
+};
+
+pub const FILTERS: [&str; 16] = [
+    "snake_case",
+    "camel_case",
+    "pascal_case",
+    "upper",
+    "lower",
+    "trim",
+    "json",
+    "shell_quote",
+    "escape_rust",
+    "escape_typescript",
+    "escape_javascript",
+    "escape_python",
+    "escape_go",
+    "escape_php",
+    "escape_kotlin",
+    "length",
+];
+
+enum Token {
+    Text(String),
+    Output(String, usize),
+    Block(String, usize),
+}
+
+struct Expression {
+    path: Vec<String>,
+    filters: Vec<String>,
+    line: usize,
+}
+
+enum Condition {
+    Value(Expression),
+    Not(Box<Condition>),
+    Equals(Expression, String),
+    NotEquals(Expression, String),
+}
+
+/// A block tag that ends the nodes before it, with its line
+type EndTag = (String, usize);
+
+enum Node {
+    Text(String),
+    Output(Expression),
+    If(Condition, Vec<Node>, Vec<Node>),
+    For(String, Expression, Vec<Node>),
+}
+
+/// Templates for user defined generators, rendered once per project.
+///
+/// `{{ endpoint.name | snake_case }}` prints a value through any number of
+/// filters, `{% for header in endpoint.headers %}…{% endfor %}` loops over a
+/// list with `loop.index`, `loop.first` and `loop.last` set, and
+/// `{% if endpoint.has_body %}…{% elif … %}…{% else %}…{% endif %}` takes a
+/// value, `not value`, `value == "text"` or `value != "text"`. `{# … #}` is a
+/// comment. Block tags that sit on a line of their own don't leave an empty
+/// line behind.
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> anyhow::Result<Self> {
+        let tokens = tokenize(source)?;
+        let mut position = 0;
+        let (nodes, end) = parse_nodes(&tokens, &mut position)?;
+
+        if let Some((tag, line)) = end {
+            bail!("Unexpected {{% {tag} %}} on line {line}");
+        }
+
+        Ok(Template { nodes })
+    }
+
+    pub fn render(&self, context: &Value) -> anyhow::Result<String> {
+        let mut output = String::new();
+        let mut scope: Vec<(String, Value)> = vec![];
+        render_nodes(&self.nodes, context, &mut scope, &mut output)?;
+
+        Ok(output)
+    }
+}
+
+fn tokenize(source: &str) -> anyhow::Result<Vec<Token>> {
+    let mut tokens: Vec<Token> = vec![];
+    let mut rest = source;
+    let mut at_line_start = true;
+
+    while let Some(start) = rest
+        .find("{{")
+        .into_iter()
+        .chain(rest.find("{%"))
+        .chain(rest.find("{#"))
+        .min()
+    {
+        let line = source[..source.len() - rest.len() + start]
+            .matches('\n')
+            .count()
+            + 1;
+        let opening = &rest[start..start + 2];
+        let closing = match opening {
+            "{{" => "}}",
+            "{%" => "%}",
+            _ => "#}",
+        };
+
+        let end = rest[start..]
+            .find(closing)
+            .map(|end| start + end)
+            .ok_or_else(|| anyhow!("Unclosed {opening} on line {line}"))?;
+
+        let mut text = &rest[..start];
+        let tag = rest[start + 2..end].trim().to_string();
+        let mut after = &rest[end + 2..];
+
+        // NOTE: A block tag alone on its line takes the whole line with it
+        if opening != "{{" {
+            let (line_start, indent) = match text.rfind('\n') {
+                Some(index) => (true, &text[index + 1..]),
+                None => (at_line_start, text),
+            };
+
+            let line_end = after.is_empty() || after.starts_with('\n') || after.starts_with("\r\n");
+
+            if line_start && line_end && indent.trim().is_empty() {
+                text = &text[..text.len() - indent.len()];
+                after = after
+                    .strip_prefix('\n')
+                    .or_else(|| after.strip_prefix("\r\n"))
+                    .unwrap_or(after);
+                at_line_start = true;
+            } else {
+                at_line_start = false;
+            }
+        } else {
+            at_line_start = false;
+        }
+
+        if !text.is_empty() {
+            tokens.push(Token::Text(text.to_string()));
+        }
+
+        match opening {
+            "{{" => tokens.push(Token::Output(tag, line)),
+            "{%" => tokens.push(Token::Block(tag, line)),
+            _ => {}
+        }
+
+        rest = after;
+    }
+
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest.to_string()));
+    }
+
+    Ok(tokens)
+}
+
+/// Parses nodes up to the first block tag that closes or continues a block,
+/// which is returned with its line for the caller to check
+fn parse_nodes(
+    tokens: &[Token],
+    position: &mut usize,
+) -> anyhow::Result<(Vec<Node>, Option<EndTag>)> {
+    let mut nodes: Vec<Node> = vec![];
+
+    while let Some(token) = tokens.get(*position) {
+        *position += 1;
+
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text.clone())),
+            Token::Output(expression, line) => {
+                nodes.push(Node::Output(parse_expression(expression, *line)?))
+            }
+            Token::Block(tag, line) => {
+                let (keyword, arguments) = tag.split_once(' ').unwrap_or((tag, ""));
+                let arguments = arguments.trim();
+
+                match keyword {
+                    "if" => nodes.push(parse_if(arguments, *line, tokens, position)?),
+                    "for" => nodes.push(parse_for(arguments, *line, tokens, position)?),
+                    "elif" | "else" | "endif" | "endfor" => {
+                        return Ok((nodes, Some((tag.clone(), *line))))
+                    }
+                    _ => bail!("Unknown tag {{% {keyword} %}} on line {line}"),
+                }
+            }
+        }
+    }
+
+    Ok((nodes, None))
+}
+
+fn parse_if(
+    condition: &str,
+    line: usize,
+    tokens: &[Token],
+    position: &mut usize,
+) -> anyhow::Result<Node> {
+    let condition = parse_condition(condition, line)?;
+    let (then, end) = parse_nodes(tokens, position)?;
+
+    let otherwise = match end {
+        Some((tag, _)) if tag == "endif" => vec![],
+        Some((tag, line)) if tag.starts_with("elif ") => {
+            vec![parse_if(&tag["elif ".len()..], line, tokens, position)?]
+        }
+        Some((tag, line)) if tag == "else" => match parse_nodes(tokens, position)? {
+            (otherwise, Some((tag, _))) if tag == "endif" => otherwise,
+            _ => bail!("{{% else %}} on line {line} is missing its {{% endif %}}"),
+        },
+        _ => bail!("{{% if %}} on line {line} is missing its {{% endif %}}"),
+    };
+
+    Ok(Node::If(condition, then, otherwise))
+}
+
+fn parse_for(
+    arguments: &str,
+    line: usize,
+    tokens: &[Token],
+    position: &mut usize,
+) -> anyhow::Result<Node> {
+    let (variable, list) = arguments
+        .split_once(" in ")
+        .ok_or_else(|| anyhow!("Expected {{% for item in list %}} on line {line}"))?;
+
+    let variable = variable.trim();
+    if !is_identifier(variable) {
+        bail!("Invalid loop variable {variable} on line {line}");
+    }
+
+    let list = parse_expression(list, line)?;
+
+    match parse_nodes(tokens, position)? {
+        (body, Some((tag, _))) if tag == "endfor" => {
+            Ok(Node::For(variable.to_string(), list, body))
+        }
+        _ => bail!("{{% for %}} on line {line} is missing its {{% endfor %}}"),
+    }
+}
+
+fn parse_condition(condition: &str, line: usize) -> anyhow::Result<Condition> {
+    let condition = condition.trim();
+
+    if let Some(condition) = condition.strip_prefix("not ") {
+        return Ok(Condition::Not(Box::new(parse_condition(condition, line)?)));
+    }
+
+    for operator in ["==", "!="] {
+        if let Some((expression, text)) = condition.split_once(operator) {
+            let expression = parse_expression(expression, line)?;
+            let text = parse_string(text.trim()).ok_or_else(|| {
+                anyhow!("Expected a quoted string after {operator} on line {line}")
+            })?;
+
+            return Ok(match operator {
+                "==" => Condition::Equals(expression, text),
+                _ => Condition::NotEquals(expression, text),
+            });
+        }
+    }
+
+    Ok(Condition::Value(parse_expression(condition, line)?))
+}
+
+fn parse_string(text: &str) -> Option<String> {
+    ['"', '\''].into_iter().find_map(|quote| {
+        text.strip_prefix(quote)
+            .and_then(|text| text.strip_suffix(quote))
+            .map(String::from)
+    })
+}
+
+fn parse_expression(expression: &str, line: usize) -> anyhow::Result<Expression> {
+    let mut parts = expression.split('|').map(str::trim);
+    let path: Vec<String> = parts
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(String::from)
+        .collect();
+
+    if !path.iter().all(|segment| is_identifier(segment)) {
+        bail!("Invalid value {} on line {line}", expression.trim());
+    }
+
+    let filters: Vec<String> = parts.map(String::from).collect();
+    if let Some(filter) = filters
+        .iter()
+        .find(|filter| !FILTERS.contains(&filter.as_str()))
+    {
+        bail!("Unknown filter {filter} on line {line}");
+    }
+
+    Ok(Expression {
+        path,
+        filters,
+        line,
+    })
+}
+
+fn is_identifier(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '_')
+}
+
+fn render_nodes(
+    nodes: &[Node],
+    context: &Value,
+    scope: &mut Vec<(String, Value)>,
+    output: &mut String,
+) -> anyhow::Result<()> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+
+            Node::Output(expression) => {
+                let value = evaluate(expression, context, scope)?;
+                if value.is_null() {
+                    bail!(
+                        "{} has no value on line {}",
+                        expression.path.join("."),
+                        expression.line
+                    );
+                }
+
+                output.push_str(&to_text(&value));
+            }
+
+            Node::If(condition, then, otherwise) => {
+                let nodes = match test(condition, context, scope)? {
+                    true => then,
+                    false => otherwise,
+                };
+
+                render_nodes(nodes, context, scope, output)?;
+            }
+
+            Node::For(variable, list, body) => {
+                let items = match evaluate(list, context, scope)? {
+                    Value::Array(items) => items,
+                    Value::Null => vec![],
+                    _ => bail!(
+                        "{} is not a list on line {}",
+                        list.path.join("."),
+                        list.line
+                    ),
+                };
+
+                for (index, item) in items.iter().enumerate() {
+                    let info = serde_json::json!({
+                        "index": index + 1,
+                        "first": index == 0,
+                        "last": index + 1 == items.len(),
+                    });
+
+                    scope.push((String::from("loop"), info));
+                    scope.push((variable.clone(), item.clone()));
+                    let rendered = render_nodes(body, context, scope, output);
+                    scope.truncate(scope.len() - 2);
+                    rendered?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn test(condition: &Condition, context: &Value, scope: &[(String, Value)]) -> anyhow::Result<bool> {
+    Ok(match condition {
+        Condition::Value(expression) => is_truthy(&evaluate(expression, context, scope)?),
+        Condition::Not(condition) => !test(condition, context, scope)?,
+        Condition::Equals(expression, text) => {
+            to_text(&evaluate(expression, context, scope)?) == *text
+        }
+        Condition::NotEquals(expression, text) => {
+            to_text(&evaluate(expression, context, scope)?) != *text
+        }
+    })
+}
+
+/// Looks the value up, innermost loop variables first, and runs its filters
+fn evaluate(
+    expression: &Expression,
+    context: &Value,
+    scope: &[(String, Value)],
+) -> anyhow::Result<Value> {
+    let (first, rest) = expression
+        .path
+        .split_first()
+        .ok_or_else(|| anyhow!("Missing value on line {}", expression.line))?;
+
+    let value = scope
+        .iter()
+        .rev()
+        .find(|(name, _)| name == first)
+        .map(|(_, value)| value)
+        .unwrap_or_else(|| &context[first.as_str()]);
+
+    let mut value = rest
+        .iter()
+        .fold(value, |value, segment| &value[segment.as_str()])
+        .clone();
+
+    for filter in &expression.filters {
+        value = apply_filter(filter, value);
+    }
+
+    Ok(value)
+}
+
+fn apply_filter(filter: &str, value: Value) -> Value {
+    let escape = |language| Value::String(escape_string(&to_text(&value), language));
+
+    match filter {
+        "snake_case" => Value::String(convert_case(&to_text(&value), Casing::Snake)),
+        "camel_case" => Value::String(convert_case(&to_text(&value), Casing::Camel)),
+        "pascal_case" => Value::String(convert_case(&to_text(&value), Casing::Pascal)),
+        "upper" => Value::String(to_text(&value).to_uppercase()),
+        "lower" => Value::String(to_text(&value).to_lowercase()),
+        "trim" => Value::String(to_text(&value).trim().to_string()),
+        "json" => Value::String(value.to_string()),
+        "shell_quote" => Value::String(shell_quote(&to_text(&value))),
+        "escape_rust" => escape(Language::Rust),
+        "escape_typescript" => escape(Language::TypeScript),
+        "escape_javascript" => escape(Language::JavaScript),
+        "escape_python" => escape(Language::Python),
+        "escape_go" => escape(Language::Go),
+        "escape_php" => escape(Language::Php),
+        "escape_kotlin" => escape(Language::Kotlin),
+        "length" => match &value {
+            Value::Array(items) => Value::from(items.len()),
+            _ => Value::from(to_text(&value).chars().count()),
+        },
+        _ => value,
+    }
+}
+
+fn to_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(value) => *value,
+        Value::Number(number) => number.as_f64() != Some(0.0),
+        Value::String(text) => !text.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(_) => true,
+    }
+}
+
+#[test]
+fn test_render() {
+    let template = Template::parse(
+        "{# headers #}
+const headers = {
+  {% for header in endpoint.headers %}
+  {{ header.name | json }}: \"{{ header.value | escape_javascript }}\"{% if not loop.last %},{% endif %}
+  {% endfor %}
+}
+{% if endpoint.content_type == \"application/json\" %}
+fun {{ endpoint.name | camel_case }}(body: String) = {{ endpoint.name | length }}
+{% elif endpoint.has_body %}
+raw
+{% else %}
+{{ endpoint.method | lower }}
+{% endif %}",
+    )
+    .unwrap();
+
+    let context = serde_json::json!({
+        "endpoint": {
+            "name": "get-user (v2)",
+            "method": "GET",
+            "has_body": false,
+            "content_type": "application/json",
+            "headers": [
+                { "name": "accept", "value": "*/*" },
+                { "name": "x-note", "value": "say \"hi\"" },
+            ],
+        },
+    });
+
+    assert_eq!(
+        template.render(&context).unwrap(),
+        "const headers = {
+  \"accept\": \"*/*\",
+  \"x-note\": \"say \\\"hi\\\"\"
+}
+fun getUserV2(body: String) = 13
+"
+    );
+}
+
+#[test]
+fn test_template_errors() {
+    let error = |source: &str| match Template::parse(source) {
+        Ok(template) => template
+            .render(&serde_json::json!({}))
+            .unwrap_err()
+            .to_string(),
+        Err(error) => error.to_string(),
+    };
+
+    assert_eq!(
+        error("a\n{% if x %}\nb"),
+        "{% if %} on line 2 is missing its {% endif %}"
+    );
+    assert_eq!(
+        error("{{ name | kebab }}"),
+        "Unknown filter kebab on line 1"
+    );
+    assert_eq!(error("{% endfor %}"), "Unexpected {% endfor %} on line 1");
+    assert_eq!(error("\n\n{{ project.name"), "Unclosed {{ on line 3");
+    assert_eq!(
+        error("{{ project.name }}"),
+        "project.name has no value on line 1"
+    );
+}
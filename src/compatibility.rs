@@ -1 +1,3 @@
+pub mod curl;
+pub mod openapi;
 pub mod postman;
@@ -0,0 +1,721 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use url::{form_urlencoded::byte_serialize, Url};
+
+use crate::projects::{Header, PersistedEndpoint};
+
+const FORM_BOUNDARY: &str = "----TomeFormBoundary7MA4YWxkTrZu0gW";
+
+/// Options that only change what curl prints, or how it transfers the
+/// response, they have no meaning for an endpoint
+// NOTE: --compressed is here because Tome always asks for compressed
+// responses and decodes them
+const IGNORED_OPTIONS: [&str; 14] = [
+    "-s",
+    "--silent",
+    "-S",
+    "--show-error",
+    "-v",
+    "--verbose",
+    "-i",
+    "--include",
+    "-#",
+    "--progress-bar",
+    "--no-progress-meter",
+    "-N",
+    "--no-buffer",
+    "--compressed",
+];
+
+/// Options with a value that are read into the endpoint
+const VALUE_OPTIONS: [&str; 27] = [
+    "-X",
+    "--request",
+    "-H",
+    "--header",
+    "-d",
+    "--data",
+    "--data-ascii",
+    "--data-binary",
+    "--data-raw",
+    "--data-urlencode",
+    "--json",
+    "-F",
+    "--form",
+    "--form-string",
+    "-u",
+    "--user",
+    "--oauth2-bearer",
+    "-A",
+    "--user-agent",
+    "-e",
+    "--referer",
+    "-b",
+    "--cookie",
+    "--url",
+    "--max-redirs",
+    "-o",
+    "--output",
+];
+
+/// Options with a value that can't be used by an endpoint, their value is
+/// read so it isn't mistaken for the URL
+const UNSUPPORTED_VALUE_OPTIONS: [&str; 30] = [
+    "-x",
+    "--proxy",
+    "-U",
+    "--proxy-user",
+    "-m",
+    "--max-time",
+    "--connect-timeout",
+    "-c",
+    "--cookie-jar",
+    "-T",
+    "--upload-file",
+    "-w",
+    "--write-out",
+    "-r",
+    "--range",
+    "--retry",
+    "-E",
+    "--cert",
+    "--key",
+    "--cacert",
+    "--capath",
+    "--resolve",
+    "--connect-to",
+    "--limit-rate",
+    "-y",
+    "--speed-time",
+    "-Y",
+    "--speed-limit",
+    "--interface",
+    "-K",
+];
+
+/// An endpoint read from a curl command line
+#[derive(Debug)]
+pub struct CurlImport {
+    pub endpoint: PersistedEndpoint,
+
+    /// Options without an equivalent in an endpoint, as they were written
+    pub unsupported: Vec<String>,
+
+    /// `-k/--insecure` was used, certificate checks are a project setting
+    pub insecure: bool,
+}
+
+#[derive(Default)]
+struct CurlCommand {
+    method: Option<String>,
+    urls: Vec<String>,
+    headers: Vec<Header>,
+    data: Vec<String>,
+    form: Vec<FormPart>,
+    json: bool,
+    get: bool,
+    head: bool,
+    follow_redirects: bool,
+    max_redirects: Option<usize>,
+    insecure: bool,
+    unsupported: Vec<String>,
+}
+
+struct FormPart {
+    name: String,
+    value: String,
+    content_type: Option<String>,
+}
+
+/// Reads a curl command, as copied from browser devtools, into an endpoint
+pub fn parse_curl(command: &str) -> anyhow::Result<CurlImport> {
+    let arguments = split_arguments(command)?;
+    let mut arguments = arguments.into_iter();
+
+    let is_curl = arguments.next().is_some_and(|program| {
+        let program = program.rsplit(['/', '\\']).next().unwrap_or_default();
+        program == "curl" || program == "curl.exe"
+    });
+
+    if !is_curl {
+        return Err(anyhow::Error::msg("The command does not start with curl"));
+    }
+
+    let mut curl = CurlCommand::default();
+
+    while let Some(argument) = arguments.next() {
+        if argument.starts_with("--") && argument.len() > 2 {
+            let value = match takes_value(&argument) {
+                true => Some(option_value(&argument, arguments.next())?),
+                false => None,
+            };
+
+            curl.apply(&argument, value)?;
+            continue;
+        }
+
+        if argument.starts_with('-') && argument.len() > 1 {
+            // NOTE: Short options can be grouped, like -sSL, and the last one
+            // can have its value attached, like -XPOST
+            let options: Vec<char> = argument.chars().skip(1).collect();
+
+            for (index, option) in options.iter().enumerate() {
+                let option = format!("-{option}");
+                if !takes_value(&option) {
+                    curl.apply(&option, None)?;
+                    continue;
+                }
+
+                let attached: String = options[index + 1..].iter().collect();
+                let value = match attached.is_empty() {
+                    true => option_value(&option, arguments.next())?,
+                    false => attached,
+                };
+
+                curl.apply(&option, Some(value))?;
+                break;
+            }
+
+            continue;
+        }
+
+        curl.urls.push(argument);
+    }
+
+    curl.into_import()
+}
+
+fn takes_value(option: &str) -> bool {
+    VALUE_OPTIONS.contains(&option) || UNSUPPORTED_VALUE_OPTIONS.contains(&option)
+}
+
+fn option_value(option: &str, value: Option<String>) -> anyhow::Result<String> {
+    value.ok_or_else(|| anyhow::Error::msg(format!("{option} is missing its value")))
+}
+
+impl CurlCommand {
+    fn apply(&mut self, option: &str, value: Option<String>) -> anyhow::Result<()> {
+        let value = value.unwrap_or_default();
+
+        match option {
+            "-X" | "--request" => self.method = Some(value.to_uppercase()),
+
+            "-H" | "--header" => self.add_header(&value),
+
+            "-d" | "--data" | "--data-ascii" | "--data-binary" => match value.starts_with('@') {
+                true => self.unsupported.push(format!("{option} {value}")),
+                false => self.data.push(value),
+            },
+
+            "--data-raw" => self.data.push(value),
+
+            // NOTE: Like curl, --json data is added to the data before it as is,
+            // while the other data options are joined with `&`
+            "--json" => {
+                self.json = true;
+                match (value.starts_with('@'), self.data.last_mut()) {
+                    (true, _) => self.unsupported.push(format!("{option} {value}")),
+                    (false, Some(data)) => data.push_str(&value),
+                    (false, None) => self.data.push(value),
+                }
+            }
+
+            "--data-urlencode" => match url_encode_data(&value) {
+                Some(data) => self.data.push(data),
+                None => self.unsupported.push(format!("{option} {value}")),
+            },
+
+            "-F" | "--form" | "--form-string" => {
+                let Some((name, content)) = value.split_once('=') else {
+                    return Err(anyhow::Error::msg(format!(
+                        "{option} {value} is not a name=content pair"
+                    )));
+                };
+
+                // NOTE: Only --form reads files, --form-string sends the text as is
+                if option != "--form-string" && content.starts_with(['@', '<']) {
+                    self.unsupported.push(format!("{option} {value}"));
+                    return Ok(());
+                }
+
+                let (content, content_type) = match option {
+                    "--form-string" => (content, None),
+                    _ => match content.split_once(";type=") {
+                        Some((content, content_type)) => (content, Some(content_type.to_string())),
+                        None => (content, None),
+                    },
+                };
+
+                self.form.push(FormPart {
+                    name: name.to_string(),
+                    value: content.to_string(),
+                    content_type,
+                });
+            }
+
+            "-u" | "--user" => {
+                // NOTE: curl asks for the password when it is left out
+                let credentials = match value.contains(':') {
+                    true => value,
+                    false => format!("{value}:"),
+                };
+
+                let value = format!("Basic {}", STANDARD.encode(credentials));
+                set_header(&mut self.headers, "Authorization", &value);
+            }
+
+            "--oauth2-bearer" => set_header(
+                &mut self.headers,
+                "Authorization",
+                &format!("Bearer {value}"),
+            ),
+
+            "-A" | "--user-agent" => set_header(&mut self.headers, "User-Agent", &value),
+
+            "-e" | "--referer" => set_header(&mut self.headers, "Referer", &value),
+
+            "-b" | "--cookie" => match value.contains('=') {
+                true => {
+                    let cookie = self
+                        .headers
+                        .iter()
+                        .find(|header| header.name.eq_ignore_ascii_case("cookie"))
+                        .map(|header| format!("{}; {value}", header.value))
+                        .unwrap_or(value);
+
+                    set_header(&mut self.headers, "Cookie", &cookie);
+                }
+
+                // NOTE: Without a = the value is a cookie file
+                false => self.unsupported.push(format!("{option} {value}")),
+            },
+
+            "--url" => self.urls.push(value),
+
+            "-G" | "--get" => self.get = true,
+
+            "-I" | "--head" => self.head = true,
+
+            "-L" | "--location" => self.follow_redirects = true,
+
+            "--max-redirs" => {
+                let max_redirects = value.parse::<usize>().map_err(|_| {
+                    anyhow::Error::msg(format!("--max-redirs {value} is not a number"))
+                })?;
+
+                self.max_redirects = Some(max_redirects);
+            }
+
+            "-k" | "--insecure" => self.insecure = true,
+
+            // NOTE: The response is shown in Tome, there is nothing to write
+            "-o" | "--output" => {}
+
+            option if IGNORED_OPTIONS.contains(&option) => {}
+
+            option if UNSUPPORTED_VALUE_OPTIONS.contains(&option) => {
+                self.unsupported.push(format!("{option} {value}"))
+            }
+
+            option => self.unsupported.push(option.to_string()),
+        }
+
+        Ok(())
+    }
+
+    fn add_header(&mut self, header: &str) {
+        // NOTE: "Name;" sends an empty header, "Name:" removes one of curl's own
+        if let Some(name) = header.strip_suffix(';') {
+            if !name.contains(':') {
+                set_header(&mut self.headers, name.trim(), "");
+                return;
+            }
+        }
+
+        match header.split_once(':') {
+            Some((name, value)) if value.trim().is_empty() => self
+                .headers
+                .retain(|header| !header.name.eq_ignore_ascii_case(name.trim())),
+
+            Some((name, value)) => self.headers.push(Header {
+                name: name.trim().to_string(),
+                value: value.trim().to_string(),
+            }),
+
+            None => self.unsupported.push(format!("-H {header}")),
+        }
+    }
+
+    fn into_import(mut self) -> anyhow::Result<CurlImport> {
+        if !self.data.is_empty() && !self.form.is_empty() {
+            return Err(anyhow::Error::msg(
+                "Data (-d) and form (-F) options can't be used together",
+            ));
+        }
+
+        let mut urls = self.urls.drain(..);
+        let Some(url) = urls.next() else {
+            return Err(anyhow::Error::msg("The curl command has no URL"));
+        };
+
+        let extra_urls: Vec<String> = urls.collect();
+        self.unsupported.extend(extra_urls);
+
+        // NOTE: curl defaults to plain HTTP for URLs without a scheme
+        let mut url = match url.contains("://") {
+            true => url,
+            false => format!("http://{url}"),
+        };
+
+        let mut endpoint = PersistedEndpoint::default();
+        endpoint
+            .headers
+            .retain(|header| header.name == "user-agent");
+
+        for header in self.headers.drain(..) {
+            set_header(&mut endpoint.headers, &header.name, &header.value);
+        }
+
+        let data = self.data.join("&");
+        let mut method = match self.head {
+            true => "HEAD",
+            false => "GET",
+        };
+
+        if self.get && !data.is_empty() {
+            let separator = match url.contains('?') {
+                true => '&',
+                false => '?',
+            };
+
+            url = format!("{url}{separator}{data}");
+        } else if !data.is_empty() {
+            let content_type = match self.json {
+                true => "application/json",
+                false => "application/x-www-form-urlencoded",
+            };
+
+            set_default_header(&mut endpoint.headers, "Content-Type", content_type);
+            if self.json {
+                set_default_header(&mut endpoint.headers, "Accept", "application/json");
+            }
+
+            endpoint.body = data;
+            method = "POST";
+        } else if !self.form.is_empty() {
+            let content_type = format!("multipart/form-data; boundary={FORM_BOUNDARY}");
+            set_header(&mut endpoint.headers, "Content-Type", &content_type);
+
+            endpoint.body = multipart_body(&self.form);
+            method = "POST";
+        }
+
+        endpoint.method = self.method.unwrap_or_else(|| method.to_string());
+        endpoint.name = endpoint_name(&endpoint.method, &url);
+        endpoint.url = url;
+        endpoint.follow_redirects = self.follow_redirects;
+        if let Some(max_redirects) = self.max_redirects {
+            endpoint.max_redirects = max_redirects;
+        }
+
+        Ok(CurlImport {
+            endpoint,
+            unsupported: self.unsupported,
+            insecure: self.insecure,
+        })
+    }
+}
+
+/// Applies curl's --data-urlencode rules, file contents (@file) aren't read
+fn url_encode_data(value: &str) -> Option<String> {
+    let encode = |text: &str| byte_serialize(text.as_bytes()).collect::<String>();
+
+    if let Some((name, content)) = value.split_once('=') {
+        return match name.is_empty() {
+            true => Some(encode(content)),
+            false => Some(format!("{name}={}", encode(content))),
+        };
+    }
+
+    match value.contains('@') {
+        true => None,
+        false => Some(encode(value)),
+    }
+}
+
+fn multipart_body(parts: &[FormPart]) -> String {
+    let mut body = String::new();
+
+    for part in parts {
+        body.push_str(&format!("--{FORM_BOUNDARY}\r\n"));
+        body.push_str(&format!(
+            "Content-Disposition: form-data; name=\"{}\"\r\n",
+            part.name
+        ));
+
+        if let Some(content_type) = &part.content_type {
+            body.push_str(&format!("Content-Type: {content_type}\r\n"));
+        }
+
+        body.push_str(&format!("\r\n{}\r\n", part.value));
+    }
+
+    body.push_str(&format!("--{FORM_BOUNDARY}--\r\n"));
+    body
+}
+
+/// Names like "POST /api/users", which is how requests show up in devtools
+fn endpoint_name(method: &str, url: &str) -> String {
+    match Url::parse(url) {
+        Ok(url) => format!("{method} {}", url.path()),
+        Err(_) => format!("{method} {url}"),
+    }
+}
+
+fn set_header(headers: &mut Vec<Header>, name: &str, value: &str) {
+    headers.retain(|header| !header.name.eq_ignore_ascii_case(name));
+    headers.push(Header {
+        name: name.to_string(),
+        value: value.to_string(),
+    });
+}
+
+fn set_default_header(headers: &mut Vec<Header>, name: &str, value: &str) {
+    if !headers
+        .iter()
+        .any(|header| header.name.eq_ignore_ascii_case(name))
+    {
+        set_header(headers, name, value);
+    }
+}
+
+/// Splits a command line the way a POSIX shell would, with '', "", $'' quoting
+/// and backslash line continuations
+fn split_arguments(command: &str) -> anyhow::Result<Vec<String>> {
+    let mut arguments: Vec<String> = vec![];
+    let mut argument = String::new();
+    let mut in_argument = false;
+    let mut chars = command.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        match char {
+            ' ' | '\t' | '\r' | '\n' => {
+                if in_argument {
+                    arguments.push(std::mem::take(&mut argument));
+                    in_argument = false;
+                }
+            }
+
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(escaped) => {
+                    argument.push(escaped);
+                    in_argument = true;
+                }
+                None => {}
+            },
+
+            '\'' => {
+                in_argument = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(char) => argument.push(char),
+                        None => return Err(anyhow::Error::msg("Unclosed ' quote")),
+                    }
+                }
+            }
+
+            '"' => {
+                in_argument = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('\n') => {}
+                            Some(escaped @ ('$' | '`' | '"' | '\\')) => argument.push(escaped),
+                            Some(char) => {
+                                argument.push('\\');
+                                argument.push(char);
+                            }
+                            None => return Err(anyhow::Error::msg("Unclosed \" quote")),
+                        },
+                        Some(char) => argument.push(char),
+                        None => return Err(anyhow::Error::msg("Unclosed \" quote")),
+                    }
+                }
+            }
+
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_argument = true;
+                read_ansi_c_quoted(&mut chars, &mut argument)?;
+            }
+
+            char => {
+                argument.push(char);
+                in_argument = true;
+            }
+        }
+    }
+
+    if in_argument {
+        arguments.push(argument);
+    }
+
+    Ok(arguments)
+}
+
+/// Reads a $'...' string, which devtools use for bodies with quotes or newlines
+fn read_ansi_c_quoted(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    argument: &mut String,
+) -> anyhow::Result<()> {
+    loop {
+        let char = chars
+            .next()
+            .ok_or_else(|| anyhow::Error::msg("Unclosed $' quote"))?;
+
+        if char == '\'' {
+            return Ok(());
+        }
+
+        if char != '\\' {
+            argument.push(char);
+            continue;
+        }
+
+        let escaped = chars
+            .next()
+            .ok_or_else(|| anyhow::Error::msg("Unclosed $' quote"))?;
+
+        match escaped {
+            'n' => argument.push('\n'),
+            't' => argument.push('\t'),
+            'r' => argument.push('\r'),
+            '0' => argument.push('\0'),
+            'x' | 'u' | 'U' => {
+                let digits = match escaped {
+                    'x' => 2,
+                    'u' => 4,
+                    _ => 8,
+                };
+
+                let mut hex = String::new();
+                while hex.len() < digits
+                    && chars.peek().is_some_and(|char| char.is_ascii_hexdigit())
+                {
+                    hex.extend(chars.next());
+                }
+
+                let decoded = u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| {
+                        anyhow::Error::msg(format!("Invalid escape \\{escaped}{hex}"))
+                    })?;
+
+                argument.push(decoded);
+            }
+            escaped => argument.push(escaped),
+        }
+    }
+}
+
+#[test]
+fn test_parse_devtools_command() {
+    let command = r#"curl 'https://api.example.com/v1/users?page=2' \
+  -H 'accept: application/json' \
+  -H 'authorization: Bearer abc' \
+  -H "x-note: \"quoted\"" \
+  --data-raw $'{"name":"O\'Brien","bio":"line\\nbreak é"}' \
+  --compressed"#;
+
+    let import = parse_curl(command).unwrap();
+    let endpoint = import.endpoint;
+
+    assert_eq!(endpoint.name, "POST /v1/users");
+    assert_eq!(endpoint.method, "POST");
+    assert_eq!(endpoint.url, "https://api.example.com/v1/users?page=2");
+    assert_eq!(endpoint.body, r#"{"name":"O'Brien","bio":"line\nbreak é"}"#);
+    assert!(!endpoint.follow_redirects);
+
+    let headers: Vec<(&str, &str)> = endpoint
+        .headers
+        .iter()
+        .map(|header| (header.name.as_str(), header.value.as_str()))
+        .collect();
+    assert_eq!(
+        headers,
+        vec![
+            ("user-agent", "tome-tui"),
+            ("accept", "application/json"),
+            ("authorization", "Bearer abc"),
+            ("x-note", "\"quoted\""),
+            ("Content-Type", "application/x-www-form-urlencoded"),
+        ]
+    );
+    assert!(import.unsupported.is_empty());
+}
+
+#[test]
+fn test_parse_short_options() {
+    let import = parse_curl(
+        "curl -sSLk -XPUT -u admin:secret --data-urlencode 'q=a b&c' -d x=1 example.com/search",
+    )
+    .unwrap();
+    let endpoint = import.endpoint;
+
+    assert_eq!(endpoint.method, "PUT");
+    assert_eq!(endpoint.url, "http://example.com/search");
+    assert_eq!(endpoint.body, "q=a+b%26c&x=1");
+    assert!(endpoint.follow_redirects);
+    assert!(import.insecure);
+    assert!(endpoint
+        .headers
+        .iter()
+        .any(|header| header.name == "Authorization" && header.value == "Basic YWRtaW46c2VjcmV0"));
+}
+
+#[test]
+fn test_parse_json_parts() {
+    let import =
+        parse_curl(r#"curl example.com/users --json '{"name": "Ada",' --json ' "admin": true}'"#)
+            .unwrap();
+    assert_eq!(import.endpoint.body, r#"{"name": "Ada", "admin": true}"#);
+
+    let import = parse_curl("curl example.com/users -d a=1 --data b=2").unwrap();
+    assert_eq!(import.endpoint.body, "a=1&b=2");
+}
+
+#[test]
+fn test_parse_form_and_unsupported_options() {
+    let import = parse_curl(
+        "curl https://example.com/upload -F 'title=Hello;type=text/plain' -F file=@photo.jpg \
+         --max-time 5 --tcp-nodelay -b cookies.txt",
+    )
+    .unwrap();
+
+    assert_eq!(import.endpoint.method, "POST");
+    assert_eq!(
+        import.endpoint.body,
+        format!(
+            "--{FORM_BOUNDARY}\r\nContent-Disposition: form-data; name=\"title\"\r\n\
+             Content-Type: text/plain\r\n\r\nHello\r\n--{FORM_BOUNDARY}--\r\n"
+        )
+    );
+    assert_eq!(
+        import.unsupported,
+        vec![
+            "-F file=@photo.jpg",
+            "--max-time 5",
+            "--tcp-nodelay",
+            "-b cookies.txt"
+        ]
+    );
+
+    assert!(parse_curl("wget https://example.com").is_err());
+    assert!(parse_curl("curl -H 'Accept: */*").is_err());
+}
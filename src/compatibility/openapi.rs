@@ -0,0 +1,1089 @@
+use std::{fs, path::Path};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    output::OutputFile,
+    projects::{
+        unique_endpoint_name, BodyMode, EndpointKind, Header, PersistedEndpoint, PersistedProject,
+        Variable,
+    },
+    requests::last_response::{load_last_response, StoredResponse},
+};
+
+/// Project variable holding the URL of the document's first server
+pub const BASE_URL_VARIABLE: &str = "baseUrl";
+
+const OPERATION_METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+// NOTE: Recursive schemas would otherwise produce examples forever
+const MAX_SCHEMA_DEPTH: usize = 8;
+
+/// Reads an OpenAPI 3.x document, in YAML or JSON, into a project with one
+/// endpoint per operation
+pub fn import_openapi(path: &Path) -> anyhow::Result<PersistedProject> {
+    let text = fs::read_to_string(path)
+        .map_err(|error| anyhow::Error::msg(format!("Could not read {path:?}: {error}")))?;
+
+    let document = parse_document(&text)?;
+    let mut project = openapi_to_project(&document)?;
+
+    if project.name.is_empty() {
+        project.name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| String::from("OpenAPI Import"));
+    }
+
+    Ok(project)
+}
+
+fn parse_document(text: &str) -> anyhow::Result<Value> {
+    match text.trim_start().starts_with('{') {
+        true => Ok(serde_json::from_str(text)?),
+        false => Ok(serde_yaml::from_str(text)?),
+    }
+}
+
+pub fn openapi_to_project(document: &Value) -> anyhow::Result<PersistedProject> {
+    let version = document["openapi"].as_str().unwrap_or_default();
+    if !version.starts_with("3.") {
+        return Err(anyhow::Error::msg(
+            "Only OpenAPI 3.x documents can be imported",
+        ));
+    }
+
+    // NOTE: Project names are used as file names
+    let name = document["info"]["title"]
+        .as_str()
+        .unwrap_or_default()
+        .replace(['/', '\\'], "-")
+        .trim()
+        .to_string();
+
+    let mut endpoints: Vec<PersistedEndpoint> = vec![];
+    if let Some(paths) = document["paths"].as_object() {
+        for (path, path_item) in paths {
+            let path_item = resolve(document, path_item);
+
+            for method in OPERATION_METHODS {
+                let Some(operation) = path_item.get(method) else {
+                    continue;
+                };
+
+                let mut endpoint =
+                    operation_to_endpoint(document, path, method, path_item, operation);
+                endpoint.name = unique_endpoint_name(&endpoint.name, &endpoints);
+                endpoints.push(endpoint);
+            }
+        }
+    }
+
+    Ok(PersistedProject {
+        name,
+        endpoints,
+        tls: Default::default(),
+        proxy: None,
+        proto_files: vec![],
+        variables: vec![Variable {
+            name: BASE_URL_VARIABLE.to_string(),
+            value: server_url(document),
+        }],
+        folders: vec![],
+    })
+}
+
+/// URL of the first server with its variables set to their defaults
+fn server_url(document: &Value) -> String {
+    let server = &document["servers"][0];
+    let mut url = server["url"].as_str().unwrap_or_default().to_string();
+
+    if let Some(variables) = server["variables"].as_object() {
+        for (name, variable) in variables {
+            let default = value_to_string(&variable["default"]);
+            url = url.replace(&format!("{{{name}}}"), &default);
+        }
+    }
+
+    url.trim_end_matches('/').to_string()
+}
+
+fn operation_to_endpoint(
+    document: &Value,
+    path: &str,
+    method: &str,
+    path_item: &Value,
+    operation: &Value,
+) -> PersistedEndpoint {
+    let mut endpoint = PersistedEndpoint::default();
+
+    endpoint.method = method.to_uppercase();
+    endpoint.name = operation["operationId"]
+        .as_str()
+        .or(operation["summary"].as_str())
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("{} {path}", endpoint.method));
+
+    let mut url_path = path.to_string();
+    let mut query: Vec<String> = vec![];
+    let mut cookies: Vec<String> = vec![];
+
+    for parameter in operation_parameters(document, path_item, operation) {
+        let name = parameter["name"].as_str().unwrap_or_default();
+        let example = parameter_example(document, parameter);
+        let required = parameter["required"].as_bool().unwrap_or(false);
+
+        // NOTE: Optional parameters are only kept when there is a value to send
+        if !required && example.is_none() && parameter["in"] != "path" {
+            continue;
+        }
+
+        let value = example.unwrap_or_else(|| format!("{{{{{name}}}}}"));
+
+        match parameter["in"].as_str().unwrap_or_default() {
+            "path" => url_path = url_path.replace(&format!("{{{name}}}"), &value),
+            "query" => query.push(format!("{name}={value}")),
+            "header" => set_header(&mut endpoint.headers, name, &value),
+            "cookie" => cookies.push(format!("{name}={value}")),
+            _ => {}
+        }
+    }
+
+    apply_security(
+        document,
+        operation,
+        &mut endpoint.headers,
+        &mut query,
+        &mut cookies,
+    );
+
+    if !cookies.is_empty() {
+        set_header(&mut endpoint.headers, "cookie", &cookies.join("; "));
+    }
+
+    endpoint.url = format!("{{{{{BASE_URL_VARIABLE}}}}}{url_path}");
+    if !query.is_empty() {
+        endpoint.url = format!("{}?{}", endpoint.url, query.join("&"));
+    }
+
+    if let Some((media_type, body)) = request_body(document, operation) {
+        set_header(&mut endpoint.headers, "content-type", &media_type);
+        endpoint.body = body;
+    }
+
+    endpoint
+}
+
+/// Path level parameters with the operation's own ones taking their place
+fn operation_parameters<'a>(
+    document: &'a Value,
+    path_item: &'a Value,
+    operation: &'a Value,
+) -> Vec<&'a Value> {
+    let mut parameters: Vec<&Value> = vec![];
+
+    let declared = path_item["parameters"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .chain(operation["parameters"].as_array().into_iter().flatten());
+
+    for parameter in declared {
+        let parameter = resolve(document, parameter);
+        parameters.retain(|existing| {
+            existing["name"] != parameter["name"] || existing["in"] != parameter["in"]
+        });
+        parameters.push(parameter);
+    }
+
+    parameters
+}
+
+fn parameter_example(document: &Value, parameter: &Value) -> Option<String> {
+    if let Some(example) = parameter.get("example") {
+        return Some(value_to_string(example));
+    }
+
+    if let Some(example) = first_example(document, &parameter["examples"]) {
+        return Some(value_to_string(example));
+    }
+
+    let schema = resolve(document, &parameter["schema"]);
+    schema
+        .get("example")
+        .or(schema.get("default"))
+        .or(schema["enum"].get(0))
+        .map(value_to_string)
+}
+
+/// Operation security overrides the document's, only the first alternative is used
+fn apply_security(
+    document: &Value,
+    operation: &Value,
+    headers: &mut Vec<Header>,
+    query: &mut Vec<String>,
+    cookies: &mut Vec<String>,
+) {
+    let requirements = match operation.get("security") {
+        Some(security) => security,
+        None => &document["security"],
+    };
+
+    let Some(requirement) = requirements[0].as_object() else {
+        return;
+    };
+
+    for scheme_name in requirement.keys() {
+        let scheme = resolve(
+            document,
+            &document["components"]["securitySchemes"][scheme_name],
+        );
+        let placeholder = format!("{{{{{scheme_name}}}}}");
+
+        match scheme["type"].as_str().unwrap_or_default() {
+            "apiKey" => {
+                let name = scheme["name"].as_str().unwrap_or(scheme_name);
+
+                match scheme["in"].as_str().unwrap_or_default() {
+                    "query" => query.push(format!("{name}={placeholder}")),
+                    "cookie" => cookies.push(format!("{name}={placeholder}")),
+                    _ => set_header(headers, name, &placeholder),
+                }
+            }
+
+            "http" => {
+                let auth_scheme = match scheme["scheme"].as_str().unwrap_or("bearer") {
+                    scheme if scheme.eq_ignore_ascii_case("basic") => "Basic".to_string(),
+                    scheme if scheme.eq_ignore_ascii_case("bearer") => "Bearer".to_string(),
+                    scheme => scheme.to_string(),
+                };
+
+                set_header(
+                    headers,
+                    "authorization",
+                    &format!("{auth_scheme} {placeholder}"),
+                );
+            }
+
+            "oauth2" | "openIdConnect" => {
+                set_header(headers, "authorization", &format!("Bearer {placeholder}"));
+            }
+
+            _ => {}
+        }
+    }
+}
+
+/// Media type and example body, JSON content is preferred when there is a choice
+fn request_body(document: &Value, operation: &Value) -> Option<(String, String)> {
+    let request_body = resolve(document, &operation["requestBody"]);
+    let content = request_body["content"].as_object()?;
+
+    let (media_type, media) = content
+        .iter()
+        .find(|(media_type, _)| media_type.as_str() == "application/json")
+        .or_else(|| {
+            content
+                .iter()
+                .find(|(media_type, _)| media_type.ends_with("+json"))
+        })
+        .or_else(|| content.iter().next())?;
+
+    let example = media
+        .get("example")
+        .or_else(|| first_example(document, &media["examples"]))
+        .cloned()
+        .unwrap_or_else(|| schema_example(document, &media["schema"], 0));
+
+    let body = match (media_type.as_str(), &example) {
+        (_, Value::Null) => String::new(),
+        (_, Value::String(text)) => text.clone(),
+        ("application/x-www-form-urlencoded", Value::Object(fields)) => fields
+            .iter()
+            .map(|(name, value)| format!("{name}={}", value_to_string(value)))
+            .collect::<Vec<String>>()
+            .join("&"),
+        (_, example) => serde_json::to_string_pretty(example).unwrap_or_default(),
+    };
+
+    Some((media_type.clone(), body))
+}
+
+fn first_example<'a>(document: &'a Value, examples: &'a Value) -> Option<&'a Value> {
+    let (_, example) = examples.as_object()?.iter().next()?;
+
+    resolve(document, example).get("value")
+}
+
+/// Builds an example value out of a schema when the document doesn't have one
+pub fn schema_example(document: &Value, schema: &Value, depth: usize) -> Value {
+    if depth > MAX_SCHEMA_DEPTH {
+        return Value::Null;
+    }
+
+    let schema = resolve(document, schema);
+
+    if let Some(example) = schema.get("example").or(schema.get("default")) {
+        return example.clone();
+    }
+
+    if let Some(example) = schema["examples"].get(0).or(schema["enum"].get(0)) {
+        return example.clone();
+    }
+
+    if let Some(schemas) = schema["allOf"].as_array() {
+        let mut merged = Map::new();
+        for schema in schemas {
+            if let Value::Object(fields) = schema_example(document, schema, depth + 1) {
+                merged.extend(fields);
+            }
+        }
+
+        return Value::Object(merged);
+    }
+
+    if let Some(schema) = schema["oneOf"].get(0).or(schema["anyOf"].get(0)) {
+        return schema_example(document, schema, depth + 1);
+    }
+
+    match schema_type(schema).as_str() {
+        "object" => Value::Object(
+            schema["properties"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(name, property)| {
+                    (name.clone(), schema_example(document, property, depth + 1))
+                })
+                .collect(),
+        ),
+
+        "array" => Value::Array(vec![schema_example(document, &schema["items"], depth + 1)]),
+
+        "string" => Value::String(
+            match schema["format"].as_str().unwrap_or_default() {
+                "date-time" => "2024-01-01T00:00:00Z",
+                "date" => "2024-01-01",
+                "email" => "user@example.com",
+                "uuid" => "00000000-0000-0000-0000-000000000000",
+                "uri" | "url" => "https://example.com",
+                _ => "string",
+            }
+            .to_string(),
+        ),
+
+        "integer" | "number" => Value::from(0),
+        "boolean" => Value::Bool(false),
+        _ => Value::Null,
+    }
+}
+
+/// The schema's type, 3.1 type lists use their first non null entry
+fn schema_type(schema: &Value) -> String {
+    let schema_type = match &schema["type"] {
+        Value::Array(types) => types
+            .iter()
+            .filter_map(|schema_type| schema_type.as_str())
+            .find(|schema_type| *schema_type != "null"),
+        schema_type => schema_type.as_str(),
+    };
+
+    match schema_type {
+        Some(schema_type) => schema_type.to_string(),
+        None if schema.get("properties").is_some() => String::from("object"),
+        None => String::new(),
+    }
+}
+
+/// Follows local `$ref`s, references to other documents are left alone
+fn resolve<'a>(document: &'a Value, value: &'a Value) -> &'a Value {
+    let mut value = value;
+
+    for _ in 0..MAX_SCHEMA_DEPTH {
+        let Some(reference) = value["$ref"].as_str() else {
+            break;
+        };
+
+        match reference
+            .strip_prefix('#')
+            .and_then(|pointer| document.pointer(pointer))
+        {
+            Some(target) => value = target,
+            None => break,
+        }
+    }
+
+    value
+}
+
+fn set_header(headers: &mut Vec<Header>, name: &str, value: &str) {
+    headers.retain(|header| !header.name.eq_ignore_ascii_case(name));
+    headers.push(Header {
+        name: name.to_string(),
+        value: value.to_string(),
+    });
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+/// Describes the project's HTTP endpoints as an OpenAPI 3.1 document, the
+/// last stored response of each endpoint is used for its response schema
+pub fn project_to_openapi(
+    project: &PersistedProject,
+    last_response: impl Fn(&PersistedEndpoint) -> Option<StoredResponse>,
+) -> Value {
+    let mut servers: Vec<String> = vec![];
+    let mut paths = Map::new();
+    let mut security_schemes = Map::new();
+    let mut operation_ids: Vec<String> = vec![];
+
+    let endpoints = project
+        .endpoints
+        .iter()
+        .filter(|endpoint| endpoint.kind == EndpointKind::Http);
+
+    for endpoint in endpoints {
+        let (server, path, query) = split_url(&endpoint.url, &project.variables);
+        let (path, path_parameters) = template_path(&path);
+
+        if !servers.contains(&server) {
+            servers.push(server.clone());
+        }
+
+        let method = endpoint.method.to_lowercase();
+        let path_item = paths
+            .entry(path)
+            .or_insert_with(|| Value::Object(Map::new()));
+
+        // NOTE: Only the first endpoint for a path and method can be described
+        if path_item.get(&method).is_some() {
+            continue;
+        }
+
+        let mut operation = Map::new();
+        operation.insert("summary".to_string(), Value::from(endpoint.name.clone()));
+
+        let operation_id = unique_operation_id(&endpoint.name, &operation_ids);
+        operation_ids.push(operation_id.clone());
+        operation.insert("operationId".to_string(), Value::from(operation_id));
+
+        if server != servers[0] {
+            operation.insert(
+                "servers".to_string(),
+                serde_json::json!([{ "url": server }]),
+            );
+        }
+
+        let mut parameters: Vec<Value> = path_parameters
+            .iter()
+            .map(|name| {
+                serde_json::json!({
+                    "name": name,
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "string" }
+                })
+            })
+            .collect();
+
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            parameters.push(parameter("query", name, value));
+        }
+
+        let mut content_type = String::from("application/json");
+        for header in endpoint.headers.iter() {
+            match header.name.to_lowercase().as_str() {
+                "content-type" => content_type = header.value.clone(),
+
+                "authorization" => {
+                    let (name, scheme) = security_scheme(&header.value);
+                    security_schemes.insert(name.to_string(), scheme);
+                    operation.insert("security".to_string(), serde_json::json!([{ name: [] }]));
+                }
+
+                // NOTE: OpenAPI ignores Accept as a parameter, and the user agent
+                // is always Tome's own
+                "accept" | "user-agent" => {}
+
+                _ => parameters.push(parameter("header", &header.name, &header.value)),
+            }
+        }
+
+        if !parameters.is_empty() {
+            operation.insert("parameters".to_string(), Value::Array(parameters));
+        }
+
+        if let Some(request_body) = request_body_object(endpoint, &content_type) {
+            operation.insert("requestBody".to_string(), request_body);
+        }
+
+        if let Some(response) = last_response(endpoint) {
+            let mut response_object = Map::new();
+            response_object.insert(
+                "description".to_string(),
+                Value::from(status_description(response.status)),
+            );
+
+            if let Some(content) = media_content(response.content_type.as_deref(), &response.body) {
+                response_object.insert("content".to_string(), content);
+            }
+
+            operation.insert(
+                "responses".to_string(),
+                serde_json::json!({ response.status.to_string(): response_object }),
+            );
+        }
+
+        if let Some(path_item) = path_item.as_object_mut() {
+            path_item.insert(method, Value::Object(operation));
+        }
+    }
+
+    let mut document = serde_json::json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": project.name,
+            "version": "1.0.0",
+            "description": "Exported from Tome"
+        },
+        "servers": servers
+            .iter()
+            .map(|server| serde_json::json!({ "url": server }))
+            .collect::<Vec<Value>>(),
+        "paths": paths
+    });
+
+    if !security_schemes.is_empty() {
+        document["components"] = serde_json::json!({ "securitySchemes": security_schemes });
+    }
+
+    document
+}
+
+/// The project's OpenAPI document, named so it sits beside the Postman export
+pub fn export_openapi(project: &PersistedProject) -> anyhow::Result<OutputFile> {
+    let document = project_to_openapi(project, |endpoint| {
+        load_last_response(&project.name, &endpoint.name)
+    });
+
+    Ok(OutputFile::new(
+        format!("{}.openapi.json", project.name),
+        serde_json::to_string_pretty(&document)?,
+    ))
+}
+
+/// Splits a URL into its server, path and query string. URLs starting with a
+/// project variable, like `{{baseUrl}}/pets`, use the variable as the server.
+fn split_url(url: &str, variables: &[Variable]) -> (String, String, String) {
+    let url = url.trim();
+    let url = url.split_once('#').map_or(url, |(url, _)| url);
+
+    let variable_server = variables.iter().find_map(|variable| {
+        url.strip_prefix(&format!("{{{{{}}}}}", variable.name))
+            .map(|rest| (variable.value.trim_end_matches('/').to_string(), rest))
+    });
+
+    let (server, rest) = match variable_server {
+        Some((server, rest)) => (server, rest.to_string()),
+        None => {
+            let authority_start = url.find("://").map_or(0, |index| index + 3);
+            match url[authority_start..].find(['/', '?']) {
+                Some(index) => (
+                    url[..authority_start + index].to_string(),
+                    url[authority_start + index..].to_string(),
+                ),
+                None => (url.to_string(), String::new()),
+            }
+        }
+    };
+
+    let (path, query) = rest.split_once('?').unwrap_or((&rest, ""));
+    let path = match path.is_empty() {
+        true => "/",
+        false => path,
+    };
+
+    (server, path.to_string(), query.to_string())
+}
+
+/// Turns `{{name}}` placeholders in a path into OpenAPI path parameters
+fn template_path(path: &str) -> (String, Vec<String>) {
+    let mut names: Vec<String> = vec![];
+    let mut templated = String::with_capacity(path.len());
+    let mut rest = path;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            break;
+        };
+
+        let name = rest[start + 2..start + 2 + length].trim().to_string();
+        templated.push_str(&rest[..start]);
+        templated.push_str(&format!("{{{name}}}"));
+
+        if !names.contains(&name) {
+            names.push(name);
+        }
+
+        rest = &rest[start + 2 + length + 2..];
+    }
+
+    templated.push_str(rest);
+    (templated, names)
+}
+
+/// A query or header parameter, placeholder values aren't used as examples
+fn parameter(location: &str, name: &str, value: &str) -> Value {
+    let mut parameter = serde_json::json!({
+        "name": name,
+        "in": location,
+        "schema": { "type": "string" }
+    });
+
+    if !value.is_empty() && !value.contains("{{") {
+        parameter["example"] = Value::from(value);
+    }
+
+    parameter
+}
+
+fn security_scheme(authorization: &str) -> (&'static str, Value) {
+    let scheme = authorization
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+
+    match scheme.as_str() {
+        "bearer" => (
+            "bearerAuth",
+            serde_json::json!({ "type": "http", "scheme": "bearer" }),
+        ),
+        "basic" => (
+            "basicAuth",
+            serde_json::json!({ "type": "http", "scheme": "basic" }),
+        ),
+        _ => (
+            "authorizationHeader",
+            serde_json::json!({ "type": "apiKey", "in": "header", "name": "Authorization" }),
+        ),
+    }
+}
+
+fn request_body_object(endpoint: &PersistedEndpoint, content_type: &str) -> Option<Value> {
+    let body = match endpoint.body_mode {
+        BodyMode::GraphQL => {
+            let variables = serde_json::from_str::<Value>(&endpoint.graphql.variables)
+                .unwrap_or(Value::Object(Map::new()));
+
+            serde_json::json!({ "query": endpoint.graphql.query, "variables": variables })
+                .to_string()
+        }
+        BodyMode::Raw => endpoint.body.clone(),
+    };
+
+    let content_type = match endpoint.body_mode {
+        BodyMode::GraphQL => "application/json",
+        BodyMode::Raw => content_type,
+    };
+
+    let content = media_content(Some(content_type), &body)?;
+
+    Some(serde_json::json!({ "content": content }))
+}
+
+/// Media type object for a body, JSON bodies get a schema inferred from them
+fn media_content(content_type: Option<&str>, body: &str) -> Option<Value> {
+    if body.trim().is_empty() {
+        return None;
+    }
+
+    let media_type = content_type
+        .and_then(|content_type| content_type.split(';').next())
+        .map(|media_type| media_type.trim().to_lowercase())
+        .filter(|media_type| !media_type.is_empty())
+        .unwrap_or_else(|| String::from("application/octet-stream"));
+
+    let media = match serde_json::from_str::<Value>(body) {
+        Ok(example) => serde_json::json!({ "schema": infer_schema(&example), "example": example }),
+        Err(_) => serde_json::json!({ "schema": { "type": "string" } }),
+    };
+
+    Some(serde_json::json!({ media_type: media }))
+}
+
+/// JSON Schema describing the shape of a value, arrays are described by
+/// their first item
+pub fn infer_schema(value: &Value) -> Value {
+    match value {
+        Value::Null => serde_json::json!({ "type": "null" }),
+        Value::Bool(_) => serde_json::json!({ "type": "boolean" }),
+        Value::Number(number) if number.is_f64() => serde_json::json!({ "type": "number" }),
+        Value::Number(_) => serde_json::json!({ "type": "integer" }),
+        Value::String(_) => serde_json::json!({ "type": "string" }),
+        Value::Array(items) => match items.first() {
+            Some(item) => serde_json::json!({ "type": "array", "items": infer_schema(item) }),
+            None => serde_json::json!({ "type": "array" }),
+        },
+        Value::Object(fields) => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|(name, value)| (name.clone(), infer_schema(value)))
+                .collect();
+
+            serde_json::json!({ "type": "object", "properties": properties })
+        }
+    }
+}
+
+fn status_description(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        301 | 302 | 303 | 307 | 308 => "Redirect",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        409 => "Conflict",
+        422 => "Unprocessable Content",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        _ => "Response",
+    }
+}
+
+/// Endpoint names turned into identifiers, numbered when they repeat
+fn unique_operation_id(name: &str, taken: &[String]) -> String {
+    let mut operation_id: String = name
+        .trim()
+        .chars()
+        .map(|char| match char.is_ascii_alphanumeric() {
+            true => char,
+            false => '_',
+        })
+        .collect();
+
+    if operation_id.is_empty() {
+        operation_id = String::from("operation");
+    }
+
+    if !taken.contains(&operation_id) {
+        return operation_id;
+    }
+
+    (2..)
+        .map(|count| format!("{operation_id}_{count}"))
+        .find(|candidate| !taken.contains(candidate))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+const PETSTORE: &str = r##"
+openapi: 3.0.3
+info:
+  title: Pet Store
+  version: 1.0.0
+servers:
+  - url: https://{region}.example.com/v1/
+    variables:
+      region:
+        default: eu
+security:
+  - apiKey: []
+paths:
+  /pets/{petId}:
+    parameters:
+      - $ref: '#/components/parameters/PetId'
+    get:
+      operationId: getPet
+      parameters:
+        - name: fields
+          in: query
+          required: true
+          schema:
+            type: string
+        - name: verbose
+          in: query
+          schema:
+            type: boolean
+        - name: X-Request-Id
+          in: header
+          required: true
+          example: abc-123
+      responses:
+        200:
+          description: A pet
+    put:
+      summary: Update a pet
+      security:
+        - bearer: []
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Pet'
+      responses:
+        200:
+          description: Updated
+components:
+  parameters:
+    PetId:
+      name: petId
+      in: path
+      required: true
+      schema:
+        type: integer
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name:
+          type: string
+          example: Rex
+        tags:
+          type: array
+          items:
+            type: string
+        born:
+          type: string
+          format: date
+  securitySchemes:
+    apiKey:
+      type: apiKey
+      in: header
+      name: X-API-Key
+    bearer:
+      type: http
+      scheme: bearer
+"##;
+
+#[cfg(test)]
+fn header_value<'a>(endpoint: &'a PersistedEndpoint, name: &str) -> Option<&'a str> {
+    endpoint
+        .headers
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case(name))
+        .map(|header| header.value.as_str())
+}
+
+#[test]
+fn test_import_operations() {
+    let project = openapi_to_project(&parse_document(PETSTORE).unwrap()).unwrap();
+
+    assert_eq!(project.name, "Pet Store");
+    assert_eq!(
+        project.variables,
+        vec![Variable {
+            name: "baseUrl".to_string(),
+            value: "https://eu.example.com/v1".to_string(),
+        }]
+    );
+
+    let get_pet = &project.endpoints[0];
+    assert_eq!(get_pet.name, "getPet");
+    assert_eq!(get_pet.method, "GET");
+    assert_eq!(get_pet.url, "{{baseUrl}}/pets/{{petId}}?fields={{fields}}");
+    assert_eq!(header_value(get_pet, "X-Request-Id"), Some("abc-123"));
+    assert_eq!(header_value(get_pet, "X-API-Key"), Some("{{apiKey}}"));
+
+    let update_pet = &project.endpoints[1];
+    assert_eq!(update_pet.name, "Update a pet");
+    assert_eq!(update_pet.method, "PUT");
+    assert_eq!(header_value(update_pet, "X-API-Key"), None);
+    assert_eq!(
+        header_value(update_pet, "authorization"),
+        Some("Bearer {{bearer}}")
+    );
+    assert_eq!(
+        header_value(update_pet, "content-type"),
+        Some("application/json")
+    );
+
+    let body: Value = serde_json::from_str(&update_pet.body).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({ "name": "Rex", "tags": ["string"], "born": "2024-01-01" })
+    );
+}
+
+#[test]
+fn test_import_rejects_swagger_2() {
+    let document = parse_document(r#"{"swagger": "2.0", "paths": {}}"#).unwrap();
+
+    assert!(openapi_to_project(&document).is_err());
+}
+
+#[test]
+fn test_unique_names() {
+    let document = parse_document(
+        r#"{
+            "openapi": "3.1.0",
+            "info": { "title": "Dupes" },
+            "paths": {
+                "/a": { "get": { "summary": "List" } },
+                "/b": { "get": { "summary": "List" } }
+            }
+        }"#,
+    )
+    .unwrap();
+
+    let project = openapi_to_project(&document).unwrap();
+    let names: Vec<&str> = project
+        .endpoints
+        .iter()
+        .map(|endpoint| endpoint.name.as_str())
+        .collect();
+
+    assert_eq!(names, vec!["List", "List (2)"]);
+    assert_eq!(project.variables[0].value, "");
+}
+
+#[cfg(test)]
+fn export_fixture() -> PersistedProject {
+    let mut create_pet = PersistedEndpoint {
+        name: "Create pet".to_string(),
+        url: "{{baseUrl}}/pets".to_string(),
+        method: "POST".to_string(),
+        body: r#"{"name": "Rex", "age": 3, "weight": 4.5, "tags": ["good"]}"#.to_string(),
+        ..PersistedEndpoint::default()
+    };
+    create_pet.headers.push(Header {
+        name: "Authorization".to_string(),
+        value: "Bearer abc".to_string(),
+    });
+
+    let get_pet = PersistedEndpoint {
+        name: "Get pet".to_string(),
+        url: "{{baseUrl}}/pets/{{petId}}?fields=name&token={{token}}".to_string(),
+        ..PersistedEndpoint::default()
+    };
+
+    let health = PersistedEndpoint {
+        name: "Health".to_string(),
+        url: "http://localhost:8080".to_string(),
+        ..PersistedEndpoint::default()
+    };
+
+    PersistedProject {
+        name: "Pets".to_string(),
+        endpoints: vec![create_pet, get_pet, health],
+        tls: Default::default(),
+        proxy: None,
+        proto_files: vec![],
+        variables: vec![Variable {
+            name: BASE_URL_VARIABLE.to_string(),
+            value: "https://api.example.com/v1/".to_string(),
+        }],
+        folders: vec![],
+    }
+}
+
+#[test]
+fn test_export_operations() {
+    let document = project_to_openapi(&export_fixture(), |endpoint| {
+        (endpoint.name == "Get pet").then(|| {
+            StoredResponse::new(200, Some("application/json; charset=utf-8"), r#"{"id": 1}"#)
+        })
+    });
+
+    assert_eq!(document["openapi"], "3.1.0");
+    assert_eq!(
+        document["servers"],
+        serde_json::json!([
+            { "url": "https://api.example.com/v1" },
+            { "url": "http://localhost:8080" }
+        ])
+    );
+
+    let create_pet = &document["paths"]["/pets"]["post"];
+    assert_eq!(create_pet["operationId"], "Create_pet");
+    assert_eq!(
+        create_pet["security"],
+        serde_json::json!([{ "bearerAuth": [] }])
+    );
+    assert_eq!(
+        create_pet["requestBody"]["content"]["application/json"]["schema"],
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "age": { "type": "integer" },
+                "weight": { "type": "number" },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            }
+        })
+    );
+    assert!(create_pet.get("responses").is_none());
+
+    let get_pet = &document["paths"]["/pets/{petId}"]["get"];
+    let parameters: Vec<(&str, &str, Option<&str>)> = get_pet["parameters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|parameter| {
+            (
+                parameter["in"].as_str().unwrap(),
+                parameter["name"].as_str().unwrap(),
+                parameter["example"].as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        parameters,
+        vec![
+            ("path", "petId", None),
+            ("query", "fields", Some("name")),
+            ("query", "token", None),
+        ]
+    );
+    assert_eq!(
+        get_pet["responses"]["200"]["content"]["application/json"]["schema"],
+        serde_json::json!({ "type": "object", "properties": { "id": { "type": "integer" } } })
+    );
+
+    let health = &document["paths"]["/"]["get"];
+    assert_eq!(
+        health["servers"],
+        serde_json::json!([{ "url": "http://localhost:8080" }])
+    );
+    assert_eq!(
+        document["components"]["securitySchemes"]["bearerAuth"]["scheme"],
+        "bearer"
+    );
+}
+
+#[test]
+fn test_export_imports_back() {
+    let document = project_to_openapi(&export_fixture(), |_| None);
+    let project = openapi_to_project(&document).unwrap();
+
+    let urls: Vec<(&str, &str)> = project
+        .endpoints
+        .iter()
+        .map(|endpoint| (endpoint.method.as_str(), endpoint.url.as_str()))
+        .collect();
+
+    assert_eq!(project.variables[0].value, "https://api.example.com/v1");
+    assert_eq!(
+        urls,
+        vec![
+            ("GET", "{{baseUrl}}/"),
+            ("POST", "{{baseUrl}}/pets"),
+            ("GET", "{{baseUrl}}/pets/{{petId}}?fields=name"),
+        ]
+    );
+}
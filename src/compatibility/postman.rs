@@ -1,9 +1,19 @@
-use std::{fs, time::SystemTime};
+use std::{fs, path::Path, time::SystemTime};
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{fs::get_documents_dir, projects::PersistedProject};
+use crate::{
+    output::OutputFile,
+    projects::{
+        folders::{
+            all_folders, folder_name, folder_segments, normalize_folder, with_folder_headers,
+            Folder, FOLDER_SEPARATOR,
+        },
+        unique_endpoint_name, BodyMode, EndpointKind, GraphQLBody, Header, PersistedEndpoint,
+        PersistedProject,
+    },
+};
 
 const POSTMAN_JSON_SCHEMA: &str =
     "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
@@ -11,36 +21,80 @@ const POSTMAN_JSON_SCHEMA: &str =
 #[derive(Default, Debug, Deserialize, Serialize)]
 struct PostmanJson {
     info: PostmanInformation,
+
+    #[serde(default)]
     item: Vec<PostmanItem>,
 }
 
 #[derive(Default, Debug, Deserialize, Serialize)]
 struct PostmanInformation {
     name: String,
-    description: String,
+
+    #[serde(default)]
+    description: serde_json::Value,
+
+    #[serde(default)]
     schema: String,
 }
 
+/// A request, or an item group (folder) when it has items of its own
 #[derive(Default, Debug, Deserialize, Serialize)]
 struct PostmanItem {
+    #[serde(default)]
     id: String,
     name: String,
-    request: PostmanRequest,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request: Option<PostmanRequest>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    item: Option<Vec<PostmanItem>>,
 }
 
 #[derive(Default, Debug, Deserialize, Serialize)]
 struct PostmanRequest {
-    description: String,
-    url: String,
+    #[serde(default)]
+    description: serde_json::Value,
+    url: PostmanUrl,
+
+    #[serde(default = "default_method")]
     method: String,
+
+    #[serde(default)]
     header: Vec<PostmanKV>,
     body: Option<PostmanBody>,
 }
 
+/// Exports write the URL as text, Postman itself also writes it split into parts
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+enum PostmanUrl {
+    Raw(String),
+    Object {
+        #[serde(default)]
+        raw: String,
+    },
+}
+
+impl Default for PostmanUrl {
+    fn default() -> Self {
+        PostmanUrl::Raw(String::new())
+    }
+}
+
+fn default_method() -> String {
+    String::from("GET")
+}
+
 #[derive(Default, Debug, Deserialize, Serialize)]
 struct PostmanKV {
     key: String,
+
+    #[serde(default)]
     value: String,
+
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    disabled: bool,
 }
 
 #[derive(Default, Debug, Deserialize, Serialize)]
@@ -64,10 +118,16 @@ enum PostmanBodyMode {
 }
 
 #[derive(Default, Debug, Deserialize, Serialize)]
-struct GraphQL;
+struct GraphQL {
+    query: String,
+
+    #[serde(default)]
+    variables: String,
+}
 
 #[derive(Default, Debug, Deserialize, Serialize)]
 struct PostmanBody {
+    #[serde(default)]
     mode: PostmanBodyMode,
 
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -90,88 +150,385 @@ fn create_uuid(seed: &str) -> String {
 
 impl From<PersistedProject> for PostmanJson {
     fn from(project: PersistedProject) -> Self {
+        let item = folder_items(&project, "");
+
         let info = PostmanInformation {
             name: project.name,
             description: format!(
                 "Postman collection exported from Tome on: {:?}",
                 SystemTime::now()
-            ),
+            )
+            .into(),
             schema: POSTMAN_JSON_SCHEMA.to_string(),
         };
 
-        let item: Vec<PostmanItem> = project
-            .endpoints
-            .iter()
-            .map(|endpoint| {
-                let id = create_uuid(&endpoint.name);
+        PostmanJson { info, item }
+    }
+}
 
-                let mut content_type = String::from("text/plain");
+/// The items in a folder, subfolders become item groups. Postman folders can't
+/// have headers, so requests get their folders' default headers.
+fn folder_items(project: &PersistedProject, parent: &str) -> Vec<PostmanItem> {
+    let depth = folder_segments(parent).len();
+
+    let groups = all_folders(&project.endpoints, &project.folders)
+        .into_iter()
+        .filter(|path| folder_segments(path).len() == depth + 1)
+        .filter(|path| {
+            parent.is_empty() || path.starts_with(&format!("{parent}{FOLDER_SEPARATOR}"))
+        })
+        .map(|path| PostmanItem {
+            id: create_uuid(&path),
+            name: folder_name(&path).to_string(),
+            request: None,
+            item: Some(folder_items(project, &path)),
+        });
+
+    let requests = project
+        .endpoints
+        .iter()
+        .filter(|endpoint| normalize_folder(&endpoint.folder) == parent)
+        .map(|endpoint| endpoint_item(&with_folder_headers(&project.folders, endpoint)));
+
+    groups.chain(requests).collect()
+}
 
-                let header: Vec<PostmanKV> = endpoint
-                    .headers
-                    .iter()
-                    .map(|header| {
-                        if header.name.to_lowercase() == "content-type" {
-                            content_type = header.value.clone();
-                        }
+fn endpoint_item(endpoint: &PersistedEndpoint) -> PostmanItem {
+    let id = create_uuid(&endpoint.name);
+
+    let mut content_type = String::from("text/plain");
+
+    let header: Vec<PostmanKV> = endpoint
+        .headers
+        .iter()
+        .map(|header| {
+            if header.name.to_lowercase() == "content-type" {
+                content_type = header.value.clone();
+            }
+
+            PostmanKV {
+                key: header.name.clone(),
+                value: header.value.clone(),
+                disabled: false,
+            }
+        })
+        .collect();
+
+    let body = match endpoint.body_mode {
+        BodyMode::GraphQL => Some(PostmanBody {
+            mode: PostmanBodyMode::GraphQL,
+            urlencoded: None,
+            raw: None,
+            graphql: Some(GraphQL {
+                query: endpoint.graphql.query.clone(),
+                variables: endpoint.graphql.variables.clone(),
+            }),
+        }),
+
+        BodyMode::Raw if endpoint.body.is_empty() => None,
+
+        BodyMode::Raw if is_url_encoded(&content_type) => Some(PostmanBody {
+            mode: PostmanBodyMode::UrlEncoded,
+            urlencoded: Some(
+                endpoint
+                    .body
+                    .split('&')
+                    .filter(|pair| !pair.is_empty())
+                    .map(|pair| {
+                        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
 
                         PostmanKV {
-                            key: header.name.clone(),
-                            value: header.value.clone(),
+                            key: key.to_string(),
+                            value: value.to_string(),
+                            disabled: false,
                         }
                     })
-                    .collect();
-
-                let body = match content_type.as_str() {
-                    "multipart/x-form-data" => {
-                        todo!()
-                    }
-
-                    "urlencoded" => {
-                        todo!()
-                    }
-
-                    endpoint_body => match endpoint_body {
-                        "" => None,
-                        _ => Some(PostmanBody {
-                            mode: PostmanBodyMode::Raw,
-                            urlencoded: None,
-                            raw: Some(endpoint.body.clone()),
-                            graphql: None,
-                        }),
-                    },
-                };
+                    .collect(),
+            ),
+            raw: None,
+            graphql: None,
+        }),
+
+        // NOTE: Multipart bodies are exported as written, with the boundary in
+        // their content type header, Postman's formdata mode can't hold the
+        // headers of each part
+        BodyMode::Raw => Some(PostmanBody {
+            mode: PostmanBodyMode::Raw,
+            urlencoded: None,
+            raw: Some(endpoint.body.clone()),
+            graphql: None,
+        }),
+    };
+
+    let request = PostmanRequest {
+        url: PostmanUrl::Raw(endpoint.url.clone()),
+        // TODO: Add descriptiong field/input in endpoint creation
+        description: "".into(),
+        method: endpoint.method.clone(),
+        header,
+        body,
+    };
+
+    PostmanItem {
+        id,
+        request: Some(request),
+        name: endpoint.name.clone(),
+        item: None,
+    }
+}
+
+fn is_url_encoded(content_type: &str) -> bool {
+    content_type.split(';').next().is_some_and(|media_type| {
+        media_type
+            .trim()
+            .eq_ignore_ascii_case("application/x-www-form-urlencoded")
+    })
+}
+
+/// Reads a Postman v2 collection into a project, item groups become folders
+pub fn import_postman(path: &Path) -> anyhow::Result<PersistedProject> {
+    let text = fs::read_to_string(path)
+        .map_err(|error| anyhow::Error::msg(format!("Could not read {path:?}: {error}")))?;
+
+    postman_to_project(&text)
+}
+
+/// Postman collections name their schema, which tells them apart from OpenAPI documents
+pub fn is_postman_collection(text: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(text).is_ok_and(|json| {
+        json["info"]["schema"]
+            .as_str()
+            .is_some_and(|schema| schema.contains("schema.getpostman.com"))
+    })
+}
 
-                let request = PostmanRequest {
-                    url: endpoint.url.clone(),
-                    // TODO: Add descriptiong field/input in endpoint creation
-                    description: "".to_string(),
-                    method: endpoint.method.clone(),
-                    header,
-                    body,
+pub fn postman_to_project(text: &str) -> anyhow::Result<PersistedProject> {
+    let postman_json: PostmanJson = serde_json::from_str(text)?;
+
+    let mut project = PersistedProject {
+        // NOTE: Project names are used as file names
+        name: postman_json
+            .info
+            .name
+            .replace(['/', '\\'], "-")
+            .trim()
+            .to_string(),
+        endpoints: vec![],
+        tls: Default::default(),
+        proxy: None,
+        proto_files: vec![],
+        variables: vec![],
+        folders: vec![],
+    };
+
+    add_items(&postman_json.item, "", &mut project);
+
+    Ok(project)
+}
+
+fn add_items(items: &[PostmanItem], folder: &str, project: &mut PersistedProject) {
+    for item in items {
+        if let Some(children) = &item.item {
+            // NOTE: A slash in the name would read as another level of folders
+            let name = item.name.replace(FOLDER_SEPARATOR, "-");
+            let path = normalize_folder(&format!("{folder}{FOLDER_SEPARATOR}{name}"));
+
+            // NOTE: Empty folders are kept by saving them without settings
+            if children.is_empty() {
+                project.folders.push(Folder {
+                    path: path.clone(),
+                    headers: vec![],
+                });
+            }
+
+            add_items(children, &path, project);
+            continue;
+        }
+
+        let Some(request) = &item.request else {
+            continue;
+        };
+
+        let url = match &request.url {
+            PostmanUrl::Raw(url) => url.clone(),
+            PostmanUrl::Object { raw } => raw.clone(),
+        };
+
+        let mut endpoint = PersistedEndpoint {
+            name: unique_endpoint_name(&item.name, &project.endpoints),
+            method: request.method.to_uppercase(),
+            headers: request
+                .header
+                .iter()
+                .filter(|header| !header.disabled)
+                .map(|header| Header {
+                    name: header.key.clone(),
+                    value: header.value.clone(),
+                })
+                .collect(),
+            kind: EndpointKind::for_url(&url).unwrap_or_default(),
+            url,
+            folder: folder.to_string(),
+            ..PersistedEndpoint::default()
+        };
+
+        match &request.body {
+            Some(PostmanBody {
+                mode: PostmanBodyMode::GraphQL,
+                graphql: Some(graphql),
+                ..
+            }) => {
+                endpoint.body_mode = BodyMode::GraphQL;
+                endpoint.graphql = GraphQLBody {
+                    query: graphql.query.clone(),
+                    variables: graphql.variables.clone(),
                 };
+            }
+
+            Some(PostmanBody {
+                mode: PostmanBodyMode::UrlEncoded,
+                urlencoded: Some(pairs),
+                ..
+            }) => {
+                endpoint.body = pairs
+                    .iter()
+                    .filter(|pair| !pair.disabled)
+                    .map(|pair| format!("{}={}", pair.key, pair.value))
+                    .collect::<Vec<String>>()
+                    .join("&");
+            }
 
-                PostmanItem {
-                    id,
-                    request,
-                    name: endpoint.name.clone(),
-                }
-            })
-            .collect();
+            Some(PostmanBody { raw: Some(raw), .. }) => endpoint.body = raw.clone(),
 
-        PostmanJson { info, item }
+            _ => {}
+        }
+
+        project.endpoints.push(endpoint);
     }
 }
 
-pub fn export_postman(project: PersistedProject) -> anyhow::Result<()> {
+pub fn export_postman(project: PersistedProject) -> anyhow::Result<OutputFile> {
     let postman_json: PostmanJson = project.into();
 
-    let mut docs_dir = get_documents_dir()?;
-    docs_dir.push(format!("{}.json", postman_json.info.name));
-
     let json = serde_json::to_string_pretty(&postman_json)?;
 
-    fs::write(docs_dir, json)?;
+    Ok(OutputFile::new(
+        format!("{}.json", postman_json.info.name),
+        json,
+    ))
+}
+
+#[test]
+fn test_postman_folders() {
+    let endpoint = |name: &str, folder: &str| PersistedEndpoint {
+        name: name.to_string(),
+        url: format!("https://api.example.com/{}", name.to_lowercase()),
+        folder: folder.to_string(),
+        ..PersistedEndpoint::default()
+    };
+
+    let project = PersistedProject {
+        name: String::from("Example"),
+        endpoints: vec![
+            endpoint("Health", ""),
+            endpoint("Users", "Accounts"),
+            endpoint("Admins", "Accounts/Admin"),
+        ],
+        tls: Default::default(),
+        proxy: None,
+        proto_files: vec![],
+        variables: vec![],
+        folders: vec![Folder {
+            path: String::from("Accounts"),
+            headers: vec![Header {
+                name: String::from("authorization"),
+                value: String::from("Bearer token"),
+            }],
+        }],
+    };
 
-    Ok(())
+    let postman_json: PostmanJson = project.into();
+    let names: Vec<&str> = postman_json
+        .item
+        .iter()
+        .map(|item| item.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["Accounts", "Health"]);
+
+    let json = serde_json::to_string(&postman_json).unwrap();
+    assert!(is_postman_collection(&json));
+
+    let imported = postman_to_project(&json).unwrap();
+    let folders: Vec<(&str, &str)> = imported
+        .endpoints
+        .iter()
+        .map(|endpoint| (endpoint.name.as_str(), endpoint.folder.as_str()))
+        .collect();
+    assert_eq!(
+        folders,
+        vec![
+            ("Admins", "Accounts/Admin"),
+            ("Users", "Accounts"),
+            ("Health", "")
+        ]
+    );
+
+    // NOTE: Folder headers are exported on each request, as Postman folders can't have them
+    assert!(imported.endpoints[0]
+        .headers
+        .iter()
+        .any(|header| header.value == "Bearer token"));
+}
+
+#[test]
+fn test_postman_form_bodies() {
+    let endpoint = |name: &str, content_type: &str, body: &str| PersistedEndpoint {
+        name: name.to_string(),
+        method: String::from("POST"),
+        url: String::from("https://api.example.com/forms"),
+        headers: vec![Header {
+            name: String::from("Content-Type"),
+            value: content_type.to_string(),
+        }],
+        body: body.to_string(),
+        ..PersistedEndpoint::default()
+    };
+
+    let multipart =
+        "--boundary\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nb\r\n--boundary--\r\n";
+    let project = PersistedProject {
+        name: String::from("Forms"),
+        endpoints: vec![
+            endpoint(
+                "Login",
+                "application/x-www-form-urlencoded",
+                "user=me&pass=a%20b",
+            ),
+            endpoint(
+                "Upload",
+                "multipart/form-data; boundary=boundary",
+                multipart,
+            ),
+        ],
+        tls: Default::default(),
+        proxy: None,
+        proto_files: vec![],
+        variables: vec![],
+        folders: vec![],
+    };
+
+    let postman_json: PostmanJson = project.into();
+    let login = postman_json.item[0].request.as_ref().unwrap();
+    assert!(matches!(
+        login.body,
+        Some(PostmanBody {
+            mode: PostmanBodyMode::UrlEncoded,
+            ..
+        })
+    ));
+
+    let json = serde_json::to_string(&postman_json).unwrap();
+    let imported = postman_to_project(&json).unwrap();
+    assert_eq!(imported.endpoints[0].body, "user=me&pass=a%20b");
+    assert_eq!(imported.endpoints[1].body, multipart);
 }
@@ -17,6 +17,7 @@ pub mod focusable_section;
 pub mod header_name_textinput;
 pub mod header_value_textinput;
 pub mod inputs;
+pub mod list_row;
 pub mod menu_item;
 pub mod method_selector;
 pub mod options;
@@ -28,6 +29,7 @@ pub mod row;
 pub mod syntax_highlighter;
 pub mod textarea;
 pub mod textinput;
+pub mod websocket_console;
 
 pub fn send_message(
     target: &str,
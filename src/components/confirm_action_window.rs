@@ -1,13 +1,31 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    cell::{Ref, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
 
 use anathema::{
-    component::{Component, ComponentId},
-    prelude::TuiBackend,
+    component::{Component, ComponentId, KeyCode},
+    prelude::{Context, TuiBackend},
     runtime::RuntimeBuilder,
-    state::{State, Value},
+    state::{CommonVal, State, Value},
+    widgets::Elements,
 };
 
-use crate::messages::confirm_delete_project::{ComponentMessage, ConfirmDeleteProject};
+use crate::{
+    components::{
+        dashboard::{DashboardMessageHandler, DashboardState, FloatingWindow},
+        floating_windows::output_path::save_output,
+    },
+    keymap::{get_keymap, KeyBinding, KeyContext, Keymap},
+    messages::{
+        confirm_delete_endpoint::ConfirmDeleteEndpoint,
+        confirm_delete_project::{ComponentMessage, ConfirmDeleteProject},
+        confirm_overwrite::ConfirmOverwrite,
+        confirm_unsaved_changes::ConfirmUnsavedChanges,
+    },
+    palette::Action,
+};
 
 pub const CONFIRM_ACTION_WINDOW_TEMPLATE: &str =
     "./src/components/templates/confirm_action_window.aml";
@@ -16,22 +34,32 @@ pub const CONFIRM_ACTION_WINDOW_TEMPLATE: &str =
 pub struct ConfirmActionWindow {
     #[allow(dead_code)]
     component_ids: Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+    keymap: Keymap,
 }
 
 impl ConfirmActionWindow {
-    pub fn new(component_ids: Rc<RefCell<HashMap<String, ComponentId<String>>>>) -> Self {
-        ConfirmActionWindow { component_ids }
+    pub fn new(
+        component_ids: Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+        keymap: Keymap,
+    ) -> Self {
+        ConfirmActionWindow {
+            component_ids,
+            keymap,
+        }
     }
 
     pub fn register(
         ids: &Rc<RefCell<HashMap<String, ComponentId<String>>>>,
         builder: &mut RuntimeBuilder<TuiBackend, ()>,
     ) -> anyhow::Result<()> {
+        let keymap = get_keymap();
+        let state = ConfirmActionWindowState::new(&keymap);
+
         let id = builder.register_component(
             "confirm_action_window",
             CONFIRM_ACTION_WINDOW_TEMPLATE,
-            ConfirmActionWindow::new(ids.clone()),
-            ConfirmActionWindowState::new(),
+            ConfirmActionWindow::new(ids.clone(), keymap),
+            state,
         )?;
 
         let mut ids_ref = ids.borrow_mut();
@@ -45,13 +73,78 @@ impl ConfirmActionWindow {
 pub struct ConfirmActionWindowState {
     title: Value<String>,
     message: Value<String>,
+
+    // NOTE: The confirm message as it was received, published with the answer
+    action: Value<String>,
+
+    /// Unsaved changes are saved or discarded instead of answering yes or no
+    unsaved: Value<bool>,
+
+    yes_label: Value<String>,
+    no_label: Value<String>,
+    save_label: Value<String>,
+    discard_label: Value<String>,
+    cancel_label: Value<String>,
 }
 
 impl ConfirmActionWindowState {
-    pub fn new() -> Self {
+    pub fn new(keymap: &Keymap) -> Self {
+        let label = |action, title| keymap.label(KeyContext::Confirm, action, title);
+
         ConfirmActionWindowState {
             title: "".to_string().into(),
             message: "".to_string().into(),
+            action: "".to_string().into(),
+            unsaved: false.into(),
+            yes_label: label(Action::Confirm, "Yes").into(),
+            no_label: label(Action::Deny, "No").into(),
+            save_label: label(Action::SaveChanges, "Save").into(),
+            discard_label: label(Action::DiscardChanges, "Discard").into(),
+            cancel_label: label(Action::Cancel, "Cancel").into(),
+        }
+    }
+}
+
+impl DashboardMessageHandler for ConfirmActionWindow {
+    fn handle_message(
+        value: CommonVal<'_>,
+        ident: impl Into<String>,
+        state: &mut DashboardState,
+        mut context: Context<'_, DashboardState>,
+        _: Elements<'_, '_>,
+        component_ids: Ref<'_, HashMap<String, ComponentId<String>>>,
+    ) {
+        let event: String = ident.into();
+        let action = &*value.to_common_str();
+
+        match event.as_str() {
+            "confirm_action__yes" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+
+                if let Ok(confirm_overwrite) = serde_json::from_str::<ConfirmOverwrite>(action) {
+                    save_output(
+                        &confirm_overwrite.path,
+                        &confirm_overwrite.output,
+                        &context,
+                        &component_ids,
+                    );
+                }
+            }
+
+            "confirm_action__no" => {
+                // NOTE: Declining an overwrite goes back to the path prompt to pick another name
+                if serde_json::from_str::<ConfirmOverwrite>(action).is_ok() {
+                    state.floating_window.set(FloatingWindow::OutputPath);
+                    context.set_focus("id", "output_path_window");
+                    return;
+                }
+
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+            }
+
+            _ => {}
         }
     }
 }
@@ -61,7 +154,7 @@ impl Component for ConfirmActionWindow {
     type Message = String;
 
     fn accept_focus(&self) -> bool {
-        false
+        true
     }
 
     fn message(
@@ -71,14 +164,72 @@ impl Component for ConfirmActionWindow {
         _: anathema::widgets::Elements<'_, '_>,
         _: anathema::prelude::Context<'_, Self::State>,
     ) {
-        if let Ok(component_message) =
+        let title_and_message = if let Ok(component_message) =
             serde_json::from_str::<ConfirmDeleteProject>(message.as_str())
         {
             let message_type = component_message.get_message_type();
-            if message_type.as_str() == "confirm_delete_project" {
-                state.title.set(component_message.title);
-                state.message.set(component_message.message);
+            (message_type.as_str() == "confirm_delete_project")
+                .then_some((component_message.title, component_message.message))
+        } else if let Ok(component_message) =
+            serde_json::from_str::<ConfirmDeleteEndpoint>(message.as_str())
+        {
+            Some((component_message.title, component_message.message))
+        } else if let Ok(component_message) =
+            serde_json::from_str::<ConfirmOverwrite>(message.as_str())
+        {
+            Some((component_message.title, component_message.message))
+        } else if let Ok(component_message) =
+            serde_json::from_str::<ConfirmUnsavedChanges>(message.as_str())
+        {
+            Some((component_message.title, component_message.message))
+        } else {
+            None
+        };
+
+        if let Some((title, confirm_message)) = title_and_message {
+            let unsaved = serde_json::from_str::<ConfirmUnsavedChanges>(&message).is_ok();
+            state.unsaved.set(unsaved);
+            state.title.set(title);
+            state.message.set(confirm_message);
+            state.action.set(message);
+        }
+    }
+
+    fn on_key(
+        &mut self,
+        key: anathema::component::KeyEvent,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: Context<'_, Self::State>,
+    ) {
+        let binding = match key.code {
+            KeyCode::Char(char) => KeyBinding {
+                key: char,
+                ctrl: key.ctrl,
+            },
+            KeyCode::Esc => return context.publish("confirm_action__no", |state| &state.action),
+
+            _ => return,
+        };
+
+        // NOTE: Unsaved changes are saved, discarded or cancelled instead of
+        // answering yes or no
+        let unsaved = *state.unsaved.to_ref();
+        match (unsaved, self.keymap.action(KeyContext::Confirm, binding)) {
+            (true, Some(Action::SaveChanges)) => {
+                context.publish("confirm_action__save", |state| &state.action)
             }
+            (true, Some(Action::DiscardChanges)) => {
+                context.publish("confirm_action__discard", |state| &state.action)
+            }
+            (true, Some(Action::Cancel)) | (false, Some(Action::Deny)) => {
+                context.publish("confirm_action__no", |state| &state.action)
+            }
+            (false, Some(Action::Confirm)) => {
+                context.publish("confirm_action__yes", |state| &state.action)
+            }
+
+            _ => {}
         }
     }
 }
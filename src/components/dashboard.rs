@@ -5,55 +5,90 @@ use anathema::{
     state::{CommonVal, List, State, Value},
     widgets::Elements,
 };
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::ops::Deref;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use std::{
     cell::{Ref, RefCell},
     collections::HashMap,
     rc::Rc,
 };
-use std::{fs, ops::Deref};
 
 use arboard::Clipboard;
 use serde::{Deserialize, Serialize};
 
-use crate::{fs::get_documents_dir, theme::get_app_theme};
+use crate::{
+    code_gen::{shell_snippet, ShellType},
+    keymap::{get_keymap, keymap_error, KeyBinding, KeyContext, Keymap},
+    messages::confirm_unsaved_changes::{ConfirmUnsavedChanges, UnsavedChangesTransition},
+    options::get_options,
+    output::{OutputFile, PendingOutput},
+    palette::{Action, ActionScope},
+    theme::get_app_theme,
+};
 use crate::{
     projects::{
-        save_project, Endpoint, HeaderState, PersistedEndpoint, PersistedProject, Project,
-        DEFAULT_ENDPOINT_NAME, DEFAULT_PROJECT_NAME,
+        load_projects, save_project,
+        tabs::{EndpointTab, ResponseBody, TabResponse, Tabs},
+        unsaved_changes, BodyMode, Endpoint, EndpointKind, Header, HeaderState, PersistedEndpoint,
+        PersistedProject, Project, DEFAULT_ENDPOINT_NAME, DEFAULT_PROJECT_NAME,
     },
     theme::AppTheme,
 };
-use crate::{requests::do_request, theme::get_app_theme_persisted};
+use crate::{
+    requests::{
+        do_grpc_request, do_request,
+        graphql::introspect,
+        grpc::{list_methods, load_descriptor_pool},
+        restore_response, show_in_response_view,
+        variables::resolve_endpoint,
+        RedirectHopState,
+    },
+    theme::get_app_theme_persisted,
+};
 
-use super::floating_windows::endpoints_selector::{EndpointsSelector, EndpointsSelectorMessages};
+use super::floating_windows::endpoints_selector::{send_endpoints_list, EndpointsSelector};
 use super::{
     add_header_window::AddHeaderWindow,
     app_layout::AppLayoutMessages,
+    confirm_action_window::ConfirmActionWindow,
     edit_header_selector::EditHeaderSelector,
     edit_header_window::EditHeaderWindow,
     floating_windows::{
         code_gen::CodeGen,
         commands::Commands,
+        curl_import::CurlImport,
         edit_endpoint_name::{EditEndpointName, EditEndpointNameMessages},
         edit_project_name::{EditProjectName, EditProjectNameMessages},
+        endpoint_settings::EndpointSettingsWindow,
+        grpc_method_selector::{GrpcMethodSelector, GrpcMethodSelectorMessages},
+        openapi_import::OpenApiImport,
+        output_path::{prompt_output_path, OutputPathWindow},
+        project_recovery::ProjectRecoveryWindow,
+        project_settings::ProjectSettingsWindow,
+        proxy_settings::ProxySettingsWindow,
     },
     method_selector::MethodSelector,
-    project_window::ProjectWindow,
+    project_window::{open_endpoint, ProjectWindow},
+    response_renderer::ResponseRendererMessages,
     send_message,
     syntax_highlighter::get_highlight_theme,
     textarea::TextAreaMessages,
     textinput::TextInputMessages,
+    websocket_console::{WebSocketConsole, WebSocketConsoleMessages},
 };
 
 pub const DASHBOARD_TEMPLATE: &str = "./src/components/templates/dashboard.aml";
 
+/// How long editing has to pause before autosave writes the changes
+const AUTOSAVE_DELAY: Duration = Duration::from_secs(2);
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum DashboardDisplay {
     RequestBody,
     RequestHeadersEditor,
     ResponseBody,
     ResponseHeaders,
+    WebSocketConsole,
 }
 
 impl anathema::state::State for DashboardDisplay {
@@ -65,14 +100,51 @@ impl anathema::state::State for DashboardDisplay {
             }
             DashboardDisplay::ResponseBody => Some(CommonVal::Str("response_body")),
             DashboardDisplay::ResponseHeaders => Some(CommonVal::Str("response_headers")),
+            DashboardDisplay::WebSocketConsole => Some(CommonVal::Str("websocket_console")),
+        }
+    }
+}
+
+/// Labels of the top and bottom bars, with the keys from the keymap
+#[derive(anathema::state::State)]
+pub struct MenuLabels {
+    new_project: Value<String>,
+    new_endpoint: Value<String>,
+    commands: Value<String>,
+    palette: Value<String>,
+    save_project: Value<String>,
+    save_endpoint: Value<String>,
+    switch_project: Value<String>,
+    switch_endpoint: Value<String>,
+    options: Value<String>,
+}
+
+impl MenuLabels {
+    fn new(keymap: &Keymap) -> Self {
+        let label = |action: Action, title: &str| -> Value<String> {
+            keymap.label(KeyContext::Dashboard, action, title).into()
+        };
+
+        MenuLabels {
+            new_project: label(Action::NewProject, "New Project"),
+            new_endpoint: label(Action::NewEndpoint, "New Endpoint"),
+            commands: label(Action::OpenCommands, "Commands"),
+            palette: label(Action::OpenCommandPalette, "Palette"),
+            save_project: label(Action::SaveProject, "Save Project"),
+            save_endpoint: label(Action::SaveEndpoint, "Save Endpoint"),
+            switch_project: label(Action::SwitchProject, "Swap Project"),
+            switch_endpoint: label(Action::SwitchEndpoint, "Swap Endpoint"),
+            options: label(Action::OpenOptions, "Options"),
         }
     }
 }
 
+/// An open endpoint in the tab bar
 #[derive(anathema::state::State)]
-pub struct MenuItem {
-    label: Value<String>,
-    color: Value<String>,
+pub struct TabLabel {
+    title: Value<String>,
+    active: Value<bool>,
+    dirty: Value<bool>,
 }
 
 #[derive(PartialEq, Eq)]
@@ -84,13 +156,22 @@ pub enum FloatingWindow {
     Error,
     EditHeaderSelector,
     Project,
-    ConfirmProject,
+    ConfirmAction,
     Message,
     ChangeEndpointName,
     ChangeProjectName,
     EndpointsSelector,
     Commands,
     CodeGen,
+    EndpointSettings,
+    ProjectSettings,
+    ProxySettings,
+    GrpcMethodSelector,
+    OpenApiImport,
+    CurlImport,
+    OutputPath,
+    CommandPalette,
+    ProjectRecovery,
 }
 
 impl State for FloatingWindow {
@@ -103,13 +184,22 @@ impl State for FloatingWindow {
             FloatingWindow::Error => Some(CommonVal::Str("Error")),
             FloatingWindow::EditHeaderSelector => Some(CommonVal::Str("EditHeaderSelector")),
             FloatingWindow::Project => Some(CommonVal::Str("Project")),
-            FloatingWindow::ConfirmProject => Some(CommonVal::Str("ConfirmProject")),
+            FloatingWindow::ConfirmAction => Some(CommonVal::Str("ConfirmAction")),
             FloatingWindow::Message => Some(CommonVal::Str("Message")),
             FloatingWindow::ChangeEndpointName => Some(CommonVal::Str("ChangeEndpointName")),
             FloatingWindow::ChangeProjectName => Some(CommonVal::Str("ChangeProjectName")),
             FloatingWindow::EndpointsSelector => Some(CommonVal::Str("EndpointsSelector")),
             FloatingWindow::Commands => Some(CommonVal::Str("Commands")),
             FloatingWindow::CodeGen => Some(CommonVal::Str("CodeGen")),
+            FloatingWindow::EndpointSettings => Some(CommonVal::Str("EndpointSettings")),
+            FloatingWindow::ProjectSettings => Some(CommonVal::Str("ProjectSettings")),
+            FloatingWindow::ProxySettings => Some(CommonVal::Str("ProxySettings")),
+            FloatingWindow::GrpcMethodSelector => Some(CommonVal::Str("GrpcMethodSelector")),
+            FloatingWindow::OpenApiImport => Some(CommonVal::Str("OpenApiImport")),
+            FloatingWindow::CurlImport => Some(CommonVal::Str("CurlImport")),
+            FloatingWindow::OutputPath => Some(CommonVal::Str("OutputPath")),
+            FloatingWindow::CommandPalette => Some(CommonVal::Str("CommandPalette")),
+            FloatingWindow::ProjectRecovery => Some(CommonVal::Str("ProjectRecovery")),
         }
     }
 }
@@ -121,14 +211,16 @@ pub struct DashboardState {
 
     pub endpoint: Value<Endpoint>,
     pub response_headers: Value<List<HeaderState>>,
+    pub redirect_chain: Value<List<RedirectHopState>>,
     pub response: Value<String>,
     pub response_body_window_label: Value<String>,
 
     pub error_message: Value<String>,
     pub message: Value<String>,
     pub message_label: Value<String>,
-    pub menu_items: Value<List<MenuItem>>,
-    pub top_menu_items: Value<List<MenuItem>>,
+    pub menu: Value<MenuLabels>,
+    pub tabs: Value<List<TabLabel>>,
+    pub modified: Value<bool>,
     pub logs: Value<String>,
 
     pub new_header_name: Value<String>,
@@ -152,17 +244,9 @@ pub struct DashboardState {
 }
 
 impl DashboardState {
-    pub fn new(app_theme: AppTheme) -> Self {
+    pub fn new(app_theme: AppTheme, keymap: &Keymap) -> Self {
         let project = Project::new();
 
-        // TODO: Re-do this in a way that doesn't suck
-        let color: Value<String> = app_theme.menu_color_1.to_ref().to_string().into();
-        let color1: Value<String> = app_theme.menu_color_1.to_ref().to_string().into();
-        let color2: Value<String> = app_theme.menu_color_2.to_ref().to_string().into();
-        let color3: Value<String> = app_theme.menu_color_3.to_ref().to_string().into();
-        let color4: Value<String> = app_theme.menu_color_4.to_ref().to_string().into();
-        let color5: Value<String> = app_theme.menu_color_5.to_ref().to_string().into();
-
         DashboardState {
             // project_count: 0.into(),
             project: project.into(),
@@ -182,33 +266,11 @@ impl DashboardState {
             // main_display: Value::<DashboardDisplay>::new(DashboardDisplay::RequestBody),
             main_display: DashboardDisplay::RequestBody.into(),
             logs: "".to_string().into(),
-            menu_items: List::from_iter([
-                MenuItem {
-                    color: color1,
-                    label: "(S)ave Project".to_string().into(),
-                },
-                MenuItem {
-                    color: color2,
-                    label: "Save Endpo(i)nt".to_string().into(),
-                },
-                MenuItem {
-                    color: color3,
-                    label: "Swap (P)roject".to_string().into(),
-                },
-                MenuItem {
-                    color: color4,
-                    label: "Swap (E)ndpoint".to_string().into(),
-                },
-                MenuItem {
-                    color: color5,
-                    label: "(O)ptions".to_string().into(),
-                },
-            ]),
-            top_menu_items: List::from_iter([MenuItem {
-                color,
-                label: "(P)rojects".to_string().into(),
-            }]),
+            menu: MenuLabels::new(keymap).into(),
+            tabs: List::empty(),
+            modified: false.into(),
             response_headers: List::from_iter(vec![]),
+            redirect_chain: List::empty(),
             header_being_edited: None.into(),
             filter_indexes: List::empty(),
             filter_total: 0.into(),
@@ -221,7 +283,12 @@ impl DashboardState {
 
 pub struct DashboardComponent {
     pub component_ids: Rc<RefCell<HashMap<String, ComponentId<String>>>>,
-    test: bool,
+    keymap: Keymap,
+    tabs: Tabs,
+    response_body: Option<ResponseBody>,
+    autosave: bool,
+    edited_at: Option<Instant>,
+    autosave_failed: bool,
 }
 
 impl DashboardComponent {
@@ -233,19 +300,45 @@ impl DashboardComponent {
 
         let app_theme = get_app_theme();
 
-        let mut state = DashboardState::new(app_theme);
+        let keymap = get_keymap();
+        let mut state = DashboardState::new(app_theme, &keymap);
         let color = theme.settings.background.unwrap();
 
         state
             .app_bg
             .set(format!("#{:02X}{:02X}{:02X}", color.r, color.g, color.b));
 
+        if let Some(error) = keymap_error() {
+            state
+                .error_message
+                .set(format!("Keymap not loaded: {error}"));
+            state.floating_window.set(FloatingWindow::Error);
+        }
+
+        // NOTE: Project files that fail to parse are listed at startup instead of hidden
+        let unreadable_projects = load_projects().map(|(_, errors)| errors.len());
+        let no_window = *state.floating_window.to_ref() == FloatingWindow::None;
+        if no_window && unreadable_projects.unwrap_or_default() > 0 {
+            state.floating_window.set(FloatingWindow::ProjectRecovery);
+        }
+
+        let tabs = Tabs::new(EndpointTab::new(
+            state.project.to_ref().deref().into(),
+            state.endpoint.to_ref().deref().into(),
+        ));
+        show_tab_labels(&tabs, &mut state);
+
         let id = builder.register_component(
             "dashboard",
             DASHBOARD_TEMPLATE,
             DashboardComponent {
                 component_ids: ids.clone(),
-                test: false,
+                keymap,
+                tabs,
+                response_body: None,
+                autosave: get_options().autosave,
+                edited_at: None,
+                autosave_failed: false,
             },
             state,
         )?;
@@ -277,6 +370,11 @@ impl DashboardComponent {
         // state.floating_window.set(FloatingWindow::None);
     }
 
+    /// Remembers how the response on the dashboard was received, for its tab
+    pub fn set_response_body(&mut self, body: Option<ResponseBody>) {
+        self.response_body = body;
+    }
+
     fn save_project(&self, state: &mut DashboardState, show_message: bool) {
         let project: PersistedProject = state.project.to_ref().deref().into();
 
@@ -314,8 +412,6 @@ impl DashboardComponent {
         context: Context<'_, DashboardState>,
         _: anathema::widgets::Elements<'_, '_>,
     ) {
-        self.save_project(state, false);
-
         state.project.set(Project::new());
         state.endpoint.set(Endpoint::new());
 
@@ -323,8 +419,6 @@ impl DashboardComponent {
     }
 
     fn new_endpoint(&self, state: &mut DashboardState, context: Context<'_, DashboardState>) {
-        self.save_endpoint(state, &context, false);
-
         state.endpoint = Endpoint::new().into();
         self.clear_url_and_request_body(&context);
     }
@@ -334,15 +428,15 @@ impl DashboardComponent {
             let url = String::from("");
             let _ = send_message("url_text_input", url, &component_ids, context.emitter);
 
-            let body = String::from("");
-            let textarea_msg = TextAreaMessages::SetInput(body);
+            let textarea_msg = TextAreaMessages::SetInput(String::from(""));
             if let Ok(message) = serde_json::to_string(&textarea_msg) {
-                let _ = send_message(
+                for input in [
                     "request_body_input",
-                    message,
-                    &component_ids,
-                    context.emitter,
-                );
+                    "graphql_query_input",
+                    "graphql_variables_input",
+                ] {
+                    let _ = send_message(input, message.clone(), &component_ids, context.emitter);
+                }
             }
         };
     }
@@ -393,37 +487,30 @@ impl DashboardComponent {
         }
     }
 
-    fn save_response_body(&self, state: &mut DashboardState, _: Context<'_, DashboardState>) {
-        let dir = get_documents_dir();
-
-        match dir {
-            Ok(mut docs_dir) => {
-                let response = state.response.to_ref().to_string();
-
-                let endpoint_name = state.endpoint.to_ref().name.to_ref().to_string();
-                let endpoint_name = endpoint_name.replace("/", "_");
-
-                let timestamp = SystemTime::now();
-                let duration = timestamp
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap_or(Duration::from_secs(1));
-                let name = format!("{endpoint_name}_{}.txt", duration.as_secs());
+    fn save_response_body(
+        &self,
+        state: &mut DashboardState,
+        mut context: Context<'_, DashboardState>,
+    ) {
+        let response = state.response.to_ref().to_string();
 
-                docs_dir.push(name);
-                let save_path = docs_dir.clone();
+        let endpoint_name = state.endpoint.to_ref().name.to_ref().to_string();
+        let endpoint_name = endpoint_name.replace("/", "_");
+
+        let timestamp = SystemTime::now();
+        let duration = timestamp
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::from_secs(1));
+        let name = format!("{endpoint_name}_{}.txt", duration.as_secs());
+
+        let output = PendingOutput {
+            project_name: state.project.to_ref().name.to_ref().to_string(),
+            title: String::from("Response Saved"),
+            file: OutputFile::new(name, response),
+        };
 
-                match fs::write(docs_dir, response) {
-                    Ok(_) => {
-                        self.show_message(
-                            "Response Saved",
-                            format!("Saved to {save_path:?}").as_str(),
-                            state,
-                        );
-                    }
-                    Err(err) => self.show_error(&err.to_string(), state),
-                }
-            }
-            Err(error) => self.show_error(&error.to_string(), state),
+        if let Ok(component_ids) = self.component_ids.try_borrow() {
+            prompt_output_path(output, state, &mut context, &component_ids);
         }
     }
 
@@ -457,31 +544,14 @@ impl DashboardComponent {
         state.floating_window.set(FloatingWindow::EndpointsSelector);
         context.set_focus("id", "endpoints_selector_window");
 
-        let persisted_endpoints: Vec<PersistedEndpoint> = state
-            .project
-            .to_ref()
-            .endpoints
-            .to_ref()
-            .iter()
-            .map(|endpoint| {
-                let e = endpoint.to_ref();
-
-                (&*e).into()
-            })
-            .collect();
+        let project: PersistedProject = state.project.to_ref().deref().into();
 
-        let msg = EndpointsSelectorMessages::EndpointsList(persisted_endpoints);
-        #[allow(clippy::single_match)]
         match self.component_ids.try_borrow() {
-            #[allow(clippy::single_match)]
-            Ok(ids) => match ids.get("endpoints_selector_window") {
-                Some(id) => {
-                    let _ = serde_json::to_string(&msg).map(|payload| {
-                        context.emit(*id, payload);
-                    });
+            Ok(ids) => {
+                if send_endpoints_list(&project, false, &ids, context.emitter).is_err() {
+                    self.show_error("Unable to find endpoints window id", state);
                 }
-                None => self.show_error("Unable to find endpoints window id", state),
-            },
+            }
             Err(_) => self.show_error("Unable to find components id map", state),
         };
     }
@@ -524,6 +594,657 @@ impl DashboardComponent {
         }
     }
 
+    fn yank_curl(&self, state: &mut DashboardState) {
+        let Ok(mut clipboard) = Clipboard::new() else {
+            self.show_error("Error accessing your clipboard", state);
+
+            return;
+        };
+
+        let project: PersistedProject = (&*state.project.to_ref()).into();
+        let endpoint: PersistedEndpoint = (&*state.endpoint.to_ref()).into();
+        let endpoint = resolve_endpoint(&project, &endpoint);
+
+        let snippet = match shell_snippet(&endpoint, &ShellType::Curl) {
+            Ok(snippet) => snippet,
+            Err(error) => {
+                self.show_error(&error.to_string(), state);
+
+                return;
+            }
+        };
+
+        match clipboard.set().text(snippet) {
+            Ok(_) => self.show_message("Clipboard", "Request copied to clipboard as cURL", state),
+            Err(error) => self.show_error(&error.to_string(), state),
+        }
+    }
+
+    fn toggle_body_mode(&self, state: &mut DashboardState, context: Context<'_, DashboardState>) {
+        let Ok(component_ids) = self.component_ids.try_borrow() else {
+            return;
+        };
+
+        let mut endpoint = state.endpoint.to_mut();
+        let body_mode = match *endpoint.body_mode.to_ref() {
+            BodyMode::Raw => BodyMode::GraphQL,
+            BodyMode::GraphQL => BodyMode::Raw,
+        };
+        endpoint.body_mode.set(body_mode);
+
+        // NOTE: The editors keep their own text, so load the endpoint's values
+        // into the ones that are about to be shown
+        let inputs = match body_mode {
+            BodyMode::Raw => vec![("request_body_input", endpoint.body.to_ref().to_string())],
+            BodyMode::GraphQL => vec![
+                (
+                    "graphql_query_input",
+                    endpoint.graphql_query.to_ref().to_string(),
+                ),
+                (
+                    "graphql_variables_input",
+                    endpoint.graphql_variables.to_ref().to_string(),
+                ),
+            ],
+        };
+
+        for (input, value) in inputs {
+            if let Ok(message) = serde_json::to_string(&TextAreaMessages::SetInput(value)) {
+                let _ = send_message(input, message, &component_ids, context.emitter);
+            }
+        }
+    }
+
+    fn load_graphql_schema(
+        &mut self,
+        state: &mut DashboardState,
+        context: Context<'_, DashboardState>,
+    ) {
+        let endpoint: PersistedEndpoint = (&*state.endpoint.to_ref()).into();
+        if endpoint.body_mode != BodyMode::GraphQL {
+            return;
+        }
+
+        let project: PersistedProject = (&*state.project.to_ref()).into();
+
+        match introspect(&project, &resolve_endpoint(&project, &endpoint)) {
+            Ok(schema) => {
+                let label = String::from("GraphQL Schema");
+                let result = show_in_response_view(
+                    schema,
+                    label,
+                    "graphql".to_string(),
+                    state,
+                    context,
+                    self,
+                );
+
+                if let Err(error) = result {
+                    self.show_error(&error.to_string(), state);
+                }
+            }
+
+            Err(error) => self.show_error(&error.to_string(), state),
+        }
+    }
+
+    fn preview_graphql_query(
+        &mut self,
+        state: &mut DashboardState,
+        context: Context<'_, DashboardState>,
+    ) {
+        if *state.endpoint.to_ref().body_mode.to_ref() != BodyMode::GraphQL {
+            return;
+        }
+
+        let query = state.endpoint.to_ref().graphql_query.to_ref().to_string();
+        let label = String::from("GraphQL Query");
+
+        let result =
+            show_in_response_view(query, label, "graphql".to_string(), state, context, self);
+        if let Err(error) = result {
+            self.show_error(&error.to_string(), state);
+        }
+    }
+
+    fn connect_websocket(
+        &self,
+        state: &mut DashboardState,
+        mut context: Context<'_, DashboardState>,
+    ) {
+        let Ok(component_ids) = self.component_ids.try_borrow() else {
+            return;
+        };
+
+        let endpoint: PersistedEndpoint = (&*state.endpoint.to_ref()).into();
+        let project: PersistedProject = (&*state.project.to_ref()).into();
+        let endpoint = resolve_endpoint(&project, &endpoint);
+
+        state.main_display.set(DashboardDisplay::WebSocketConsole);
+
+        let message = WebSocketConsoleMessages::Connect(project, endpoint);
+        if let Ok(message) = serde_json::to_string(&message) {
+            let _ = send_message(
+                "websocket_console",
+                message,
+                &component_ids,
+                context.emitter,
+            );
+        }
+
+        context.set_focus("id", "websocket_console");
+    }
+
+    fn open_grpc_method_selector(
+        &self,
+        state: &mut DashboardState,
+        mut context: Context<'_, DashboardState>,
+    ) {
+        let Ok(component_ids) = self.component_ids.try_borrow() else {
+            return;
+        };
+
+        let project: PersistedProject = (&*state.project.to_ref()).into();
+        let methods = match load_descriptor_pool(&project.proto_files) {
+            Ok(pool) => list_methods(&pool),
+            Err(error) => {
+                self.show_error(&error.to_string(), state);
+                return;
+            }
+        };
+
+        if methods.is_empty() {
+            self.show_error("The project's .proto files have no unary methods", state);
+            return;
+        }
+
+        state
+            .floating_window
+            .set(FloatingWindow::GrpcMethodSelector);
+        context.set_focus("id", "grpc_method_selector_window");
+
+        let message = GrpcMethodSelectorMessages::MethodsList(methods);
+        if let Ok(message) = serde_json::to_string(&message) {
+            let _ = send_message(
+                "grpc_method_selector_window",
+                message,
+                &component_ids,
+                context.emitter,
+            );
+        }
+    }
+
+    fn toggle_raw_response(&self, context: Context<'_, DashboardState>) {
+        let Ok(component_ids) = self.component_ids.try_borrow() else {
+            return;
+        };
+
+        if let Ok(msg) = serde_json::to_string(&ResponseRendererMessages::ToggleRawView) {
+            let _ = send_message("response_renderer", msg, &component_ids, context.emitter);
+        }
+    }
+
+    /// Runs a dashboard action, shared by key presses and the command palette
+    fn handle_action(
+        &mut self,
+        action: Action,
+        state: &mut DashboardState,
+        elements: Elements<'_, '_>,
+        mut context: Context<'_, DashboardState>,
+    ) {
+        let main_display = *state.main_display.to_ref();
+
+        match action {
+            Action::OpenCommandPalette => {
+                state.floating_window.set(FloatingWindow::CommandPalette);
+                context.set_focus("id", "command_palette_window");
+            }
+            Action::OpenCommands => self.open_commands_window(state, context),
+            Action::SaveProject => self.save_project(state, true),
+            Action::RenameEndpoint => self.open_edit_endpoint_name_window(state, context),
+            Action::RenameProject => self.open_edit_project_name_window(state, context),
+            Action::SaveEndpoint => self.save_endpoint(state, &context, true),
+            Action::OpenOptions => self.send_options_open(state, context),
+            Action::RecoverProjects => {
+                state.floating_window.set(FloatingWindow::ProjectRecovery);
+                context.set_focus("id", "project_recovery_window");
+            }
+            Action::NewEndpoint => self.confirm_unsaved(
+                UnsavedChangesTransition::NewEndpoint,
+                state,
+                elements,
+                context,
+            ),
+            Action::NewProject => self.confirm_unsaved(
+                UnsavedChangesTransition::NewProject,
+                state,
+                elements,
+                context,
+            ),
+
+            Action::SaveResponseBody => match main_display {
+                DashboardDisplay::RequestBody => self.preview_graphql_query(state, context),
+                DashboardDisplay::RequestHeadersEditor => {}
+                DashboardDisplay::ResponseBody => self.save_response_body(state, context),
+                DashboardDisplay::ResponseHeaders => {}
+                DashboardDisplay::WebSocketConsole => {}
+            },
+
+            // Set focus to the request url text input
+            Action::EditUrl => context.set_focus("id", "url_input"),
+
+            // Quit app
+            Action::Quit => {
+                self.confirm_unsaved(UnsavedChangesTransition::Quit, state, elements, context)
+            }
+
+            // Make the request
+            Action::SendRequest => {
+                let url_kind = EndpointKind::for_url(&state.endpoint.to_ref().url.to_ref());
+                if let Some(kind) = url_kind {
+                    state.endpoint.to_mut().kind.set(kind);
+                }
+
+                let kind = *state.endpoint.to_ref().kind.to_ref();
+                match kind {
+                    EndpointKind::WebSocket => self.connect_websocket(state, context),
+                    EndpointKind::Grpc => {
+                        if let Err(error) = do_grpc_request(state, context, self) {
+                            self.show_error(&error.to_string(), state);
+                        }
+                    }
+
+                    // TODO: Handle the error for making a request and let the user know if
+                    // there was an error
+                    EndpointKind::Http => {
+                        let _ = do_request(state, context, elements, self);
+                    }
+                }
+            }
+
+            // Cycle the endpoint between an HTTP request, a WebSocket and a gRPC call
+            Action::CycleEndpointKind => {
+                let mut endpoint = state.endpoint.to_mut();
+                let kind = match *endpoint.kind.to_ref() {
+                    EndpointKind::Http => EndpointKind::WebSocket,
+                    EndpointKind::WebSocket => EndpointKind::Grpc,
+                    EndpointKind::Grpc => EndpointKind::Http,
+                };
+                endpoint.kind.set(kind);
+            }
+
+            // Show request body editor window
+            Action::FocusBody => match main_display {
+                DashboardDisplay::RequestBody => {
+                    match *state.endpoint.to_ref().body_mode.to_ref() {
+                        BodyMode::Raw => context.set_focus("id", "textarea"),
+                        BodyMode::GraphQL => context.set_focus("id", "graphql_query"),
+                    }
+                }
+                DashboardDisplay::RequestHeadersEditor => {
+                    state.main_display.set(DashboardDisplay::RequestBody);
+                }
+                DashboardDisplay::ResponseBody => {
+                    // NOTE: Maybe revert this, needs testing to check focus UX
+                    // state.main_display.set(DashboardDisplay::RequestBody);
+                    context.set_focus("id", "response_renderer");
+                }
+                DashboardDisplay::ResponseHeaders => {
+                    state.main_display.set(DashboardDisplay::ResponseBody)
+                }
+                DashboardDisplay::WebSocketConsole => {
+                    context.set_focus("id", "websocket_console");
+                }
+            },
+
+            // Show request headers editor window
+            Action::EditRequestHeaders => {
+                state
+                    .main_display
+                    .set(DashboardDisplay::RequestHeadersEditor);
+            }
+
+            // Open Endpoints selector
+            Action::SwitchEndpoint => {
+                self.open_endpoints_selector(state, context);
+            }
+
+            // Show projects window
+            Action::SwitchProject => {
+                if let Ok(component_ids) = self.component_ids.try_borrow() {
+                    state.floating_window.set(FloatingWindow::Project);
+                    context.set_focus("id", "project_selector");
+
+                    let _ = component_ids.get("project_selector").map(|id| {
+                        context.emit(*id, "projects".to_string());
+                    });
+                }
+            }
+
+            // Show response headers display
+            Action::ShowHeaders => match main_display {
+                DashboardDisplay::RequestBody => {}
+                DashboardDisplay::RequestHeadersEditor => {
+                    state
+                        .floating_window
+                        .set(FloatingWindow::EditHeaderSelector);
+                    context.set_focus("id", "edit_header_selector");
+                }
+                DashboardDisplay::ResponseBody => {
+                    state.main_display.set(DashboardDisplay::ResponseHeaders)
+                }
+                DashboardDisplay::ResponseHeaders => {}
+                DashboardDisplay::WebSocketConsole => {}
+            },
+
+            // Open Request Method selection window, gRPC endpoints pick
+            // a service method from the project's .proto files instead
+            Action::ChangeMethod => {
+                let kind = *state.endpoint.to_ref().kind.to_ref();
+                match kind {
+                    EndpointKind::Grpc => self.open_grpc_method_selector(state, context),
+                    _ => {
+                        state.floating_window.set(FloatingWindow::Method);
+                        context.set_focus("id", "method_selector");
+                    }
+                }
+            }
+
+            Action::AddHeader => match main_display {
+                DashboardDisplay::RequestBody => {
+                    if *state.endpoint.to_ref().body_mode.to_ref() == BodyMode::GraphQL {
+                        context.set_focus("id", "graphql_variables");
+                    }
+                }
+                DashboardDisplay::RequestHeadersEditor => {
+                    // Open header window
+                    state.floating_window.set(FloatingWindow::AddHeader);
+                    context.set_focus("id", "add_header_window");
+                }
+                DashboardDisplay::ResponseBody => {}
+                DashboardDisplay::ResponseHeaders => {}
+                DashboardDisplay::WebSocketConsole => {}
+            },
+
+            // Switch the request body between raw and GraphQL
+            Action::ToggleBodyMode if main_display == DashboardDisplay::RequestBody => {
+                self.toggle_body_mode(state, context)
+            }
+
+            // Run GraphQL introspection to list the schema's types and fields
+            Action::LoadGraphQLSchema if main_display == DashboardDisplay::RequestBody => {
+                self.load_graphql_schema(state, context)
+            }
+
+            // Toggle the hex dump of the response bytes as received
+            Action::ToggleHexDump => match main_display {
+                DashboardDisplay::RequestBody => {}
+                DashboardDisplay::RequestHeadersEditor => {}
+                DashboardDisplay::ResponseBody => self.toggle_raw_response(context),
+                DashboardDisplay::ResponseHeaders => {}
+                DashboardDisplay::WebSocketConsole => {}
+            },
+
+            Action::NextTab => self.switch_tab(state, context, Tabs::next),
+            Action::PreviousTab => self.switch_tab(state, context, Tabs::previous),
+            Action::CloseTab => {
+                self.confirm_unsaved(UnsavedChangesTransition::CloseTab, state, elements, context)
+            }
+
+            Action::Yank => match main_display {
+                // Copy the request as a curl command to the clipboard
+                DashboardDisplay::RequestBody => self.yank_curl(state),
+                DashboardDisplay::RequestHeadersEditor => self.yank_curl(state),
+                DashboardDisplay::ResponseBody => {
+                    // Copy response body to clipboard
+                    self.yank_response(state)
+                }
+                DashboardDisplay::ResponseHeaders => {}
+                DashboardDisplay::WebSocketConsole => {}
+            },
+
+            _ => {}
+        }
+    }
+
+    /// Runs a command chosen in the palette like its keys do
+    fn run_action(
+        &mut self,
+        action: Action,
+        state: &mut DashboardState,
+        elements: Elements<'_, '_>,
+        mut context: Context<'_, DashboardState>,
+    ) {
+        state.floating_window.set(FloatingWindow::None);
+        context.set_focus("id", "app");
+
+        match action.scope() {
+            ActionScope::Dashboard => self.handle_action(action, state, elements, context),
+            ActionScope::Commands => {
+                let Ok(action) = serde_json::to_string(&action) else {
+                    return;
+                };
+
+                if let Ok(component_ids) = self.component_ids.try_borrow() {
+                    Commands::handle_message(
+                        CommonVal::Str(&action),
+                        "commands__selection",
+                        state,
+                        context,
+                        elements,
+                        component_ids,
+                    );
+                }
+            }
+            ActionScope::Editor | ActionScope::Window => {}
+        }
+    }
+
+    /// Keeps the dashboard's edits and response in the active tab, then shows
+    /// the tab `change` picks
+    fn switch_tab(
+        &mut self,
+        state: &mut DashboardState,
+        context: Context<'_, DashboardState>,
+        change: impl FnOnce(&mut Tabs),
+    ) {
+        self.tabs.update_active(
+            state.project.to_ref().deref().into(),
+            state.endpoint.to_ref().deref().into(),
+        );
+        self.tabs
+            .store_response(current_response(state, self.response_body.take()));
+
+        change(&mut self.tabs);
+
+        let tab = self.tabs.active().clone();
+        if let Ok(component_ids) = self.component_ids.try_borrow() {
+            state.project.set((&tab.project).into());
+            state.endpoint_count.set(tab.project.endpoints.len() as u8);
+            open_endpoint(&tab.endpoint, state, &context, &component_ids);
+
+            let response = tab.response.as_ref();
+            self.response_body = response.map(|response| response.body.clone());
+            if let Err(error) = restore_response(response, state, &context, &component_ids) {
+                self.show_error(&error.to_string(), state);
+            }
+        }
+
+        show_tab_labels(&self.tabs, state);
+    }
+
+    /// Opens the endpoint in a tab, or shows its tab when it's already open
+    fn open_tab(
+        &mut self,
+        project: PersistedProject,
+        endpoint: PersistedEndpoint,
+        state: &mut DashboardState,
+        context: Context<'_, DashboardState>,
+    ) {
+        let tab = EndpointTab::new(project, endpoint);
+        self.switch_tab(state, context, |tabs| tabs.open(tab));
+    }
+
+    /// Asks to save or discard the changes the transition would lose, or runs it
+    /// right away when there are none
+    fn confirm_unsaved(
+        &mut self,
+        transition: UnsavedChangesTransition,
+        state: &mut DashboardState,
+        elements: Elements<'_, '_>,
+        mut context: Context<'_, DashboardState>,
+    ) {
+        self.tabs.update_active(
+            state.project.to_ref().deref().into(),
+            state.endpoint.to_ref().deref().into(),
+        );
+
+        let active = self.tabs.active();
+        let changes = unsaved_changes(&active.project, &active.endpoint);
+        let (loses_changes, message) = match transition {
+            UnsavedChangesTransition::NewEndpoint => (
+                changes.endpoint,
+                format!("Save the changes to {} first?", active.endpoint.name),
+            ),
+            UnsavedChangesTransition::NewProject => (
+                changes.any(),
+                format!("Save the changes to {} first?", active.project.name),
+            ),
+            UnsavedChangesTransition::CloseTab => (
+                changes.any() && self.tabs.tabs().len() > 1,
+                format!(
+                    "Save the changes to {} before closing it?",
+                    active.endpoint.name
+                ),
+            ),
+            UnsavedChangesTransition::Quit => (
+                self.tabs
+                    .tabs()
+                    .iter()
+                    .any(|tab| unsaved_changes(&tab.project, &tab.endpoint).any()),
+                String::from("Save the changes in the open tabs before quitting?"),
+            ),
+        };
+
+        if !loses_changes {
+            self.continue_transition(transition, state, elements, context);
+            return;
+        }
+
+        state.floating_window.set(FloatingWindow::ConfirmAction);
+        context.set_focus("id", "confirm_action_window");
+
+        let confirm_message = ConfirmUnsavedChanges {
+            transition,
+            title: String::from("Unsaved Changes"),
+            message,
+        };
+
+        if let (Ok(component_ids), Ok(message)) = (
+            self.component_ids.try_borrow(),
+            serde_json::to_string(&confirm_message),
+        ) {
+            let _ = send_message(
+                "confirm_action_window",
+                message,
+                &component_ids,
+                context.emitter,
+            );
+        }
+    }
+
+    fn continue_transition(
+        &mut self,
+        transition: UnsavedChangesTransition,
+        state: &mut DashboardState,
+        elements: Elements<'_, '_>,
+        context: Context<'_, DashboardState>,
+    ) {
+        match transition {
+            UnsavedChangesTransition::NewEndpoint => self.new_endpoint(state, context),
+            UnsavedChangesTransition::NewProject => self.new_project(state, context, elements),
+            UnsavedChangesTransition::CloseTab => self.switch_tab(state, context, |tabs| {
+                tabs.close();
+            }),
+            UnsavedChangesTransition::Quit => quit::with_code(0),
+        }
+    }
+
+    /// Saves the changes of the active tab, or of every tab before quitting
+    fn save_tab_changes(
+        &mut self,
+        all_tabs: bool,
+        state: &mut DashboardState,
+    ) -> anyhow::Result<()> {
+        let indexes: Vec<usize> = match all_tabs {
+            true => (0..self.tabs.tabs().len()).collect(),
+            false => vec![self.tabs.active_index()],
+        };
+
+        for index in indexes {
+            let tab = &self.tabs.tabs()[index];
+            if !unsaved_changes(&tab.project, &tab.endpoint).any() {
+                continue;
+            }
+
+            let project = save_changes(&tab.project, &tab.endpoint)?;
+            self.tabs.update_project(&project);
+        }
+
+        let project = &self.tabs.active().project;
+        state.project.set(project.into());
+        state.endpoint_count.set(project.endpoints.len() as u8);
+
+        Ok(())
+    }
+
+    /// Starts the autosave delay over, the changes are saved once editing pauses
+    fn schedule_autosave(&mut self) {
+        if self.autosave {
+            self.edited_at = Some(Instant::now());
+        }
+    }
+
+    /// Saves the active tab's changes when autosave is on, once they can be saved
+    fn autosave(&mut self, state: &mut DashboardState) {
+        if !self.autosave {
+            return;
+        }
+
+        self.tabs.update_active(
+            state.project.to_ref().deref().into(),
+            state.endpoint.to_ref().deref().into(),
+        );
+
+        let active = self.tabs.active();
+        let changes = unsaved_changes(&active.project, &active.endpoint);
+        let unnamed = active.project.name == DEFAULT_PROJECT_NAME
+            || (changes.endpoint && active.endpoint.name == DEFAULT_ENDPOINT_NAME);
+        if !changes.any() || unnamed {
+            return;
+        }
+
+        // NOTE: A save that keeps failing is only reported until one succeeds again
+        match self.save_tab_changes(false, state) {
+            Ok(_) => self.autosave_failed = false,
+            Err(error) if !self.autosave_failed => {
+                self.autosave_failed = true;
+                self.show_error(&error.to_string(), state);
+            }
+            Err(_) => {}
+        }
+    }
+
+    /// Keeps the active tab in step with the dashboard and redraws the tab bar
+    fn refresh_tabs(&mut self, state: &mut DashboardState) {
+        self.tabs.update_active(
+            state.project.to_ref().deref().into(),
+            state.endpoint.to_ref().deref().into(),
+        );
+
+        show_tab_labels(&self.tabs, state);
+    }
+
     fn open_commands_window(
         &self,
         state: &mut DashboardState,
@@ -558,6 +1279,24 @@ impl anathema::component::Component for DashboardComponent {
     type State = DashboardState;
     type Message = String;
 
+    fn tick(
+        &mut self,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        _: Context<'_, Self::State>,
+        _: Duration,
+    ) {
+        let idle = self
+            .edited_at
+            .is_some_and(|edited_at| edited_at.elapsed() >= AUTOSAVE_DELAY);
+
+        if idle {
+            self.edited_at = None;
+            self.autosave(state);
+            self.refresh_tabs(state);
+        }
+    }
+
     fn message(
         &mut self,
         message: Self::Message,
@@ -626,18 +1365,26 @@ impl anathema::component::Component for DashboardComponent {
                     },
                 },
 
-                // TODO: Refactor this message to not be 100% coupled to only editing the
-                // endpoint body
                 DashboardMessages::TextArea(text_area_message) => match text_area_message {
-                    TextAreaMessages::InputChange(value) => {
-                        state.endpoint.to_mut().body.set(value);
+                    TextAreaMessages::InputUpdate(update) => {
+                        let mut endpoint = state.endpoint.to_mut();
+
+                        match update.id.as_str() {
+                            "endpoint_graphql_query" => endpoint.graphql_query.set(update.value),
+                            "endpoint_graphql_variables" => {
+                                endpoint.graphql_variables.set(update.value)
+                            }
+                            _ => endpoint.body.set(update.value),
+                        }
                     }
 
                     // NOTE: SetInput is only used for sending the TextArea a new value
-                    TextAreaMessages::SetInput(_) => todo!(),
+                    TextAreaMessages::SetInput(_) => {}
                 },
             }
         }
+
+        self.refresh_tabs(state);
     }
 
     fn receive(
@@ -648,13 +1395,77 @@ impl anathema::component::Component for DashboardComponent {
         elements: Elements<'_, '_>,
         mut context: Context<'_, Self::State>,
     ) {
-        #[allow(clippy::single_match)]
         match ident {
             // Unfocus the url input and set back to dashboard
             "url_input_focus" => {
                 context.set_focus("id", "app");
             }
 
+            // NOTE: Running a command needs the dashboard itself, not only its state
+            "command_palette__run" => {
+                let value = &*value.to_common_str();
+                if let Ok(action) = serde_json::from_str::<Action>(value) {
+                    self.run_action(action, state, elements, context);
+                }
+                self.schedule_autosave();
+                self.refresh_tabs(state);
+                return;
+            }
+
+            // NOTE: Continuing after unsaved changes were saved or discarded needs the dashboard
+            "confirm_action__save" | "confirm_action__discard" => {
+                let value = &*value.to_common_str();
+                if let Ok(confirm) = serde_json::from_str::<ConfirmUnsavedChanges>(value) {
+                    state.floating_window.set(FloatingWindow::None);
+                    context.set_focus("id", "app");
+
+                    let transition = confirm.transition;
+                    let all_tabs = transition == UnsavedChangesTransition::Quit;
+                    let saved = match ident {
+                        "confirm_action__save" => self.save_tab_changes(all_tabs, state),
+                        _ => Ok(()),
+                    };
+
+                    match saved {
+                        Ok(_) => self.continue_transition(transition, state, elements, context),
+                        Err(error) => self.show_error(&error.to_string(), state),
+                    }
+                }
+                self.refresh_tabs(state);
+                return;
+            }
+
+            "command_palette__cancel" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+                return;
+            }
+
+            // NOTE: Switching endpoints or projects opens them in a tab, which needs the dashboard
+            "endpoints_selector__selection" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+
+                let value = &*value.to_common_str();
+                if let Ok(endpoint) = serde_json::from_str::<PersistedEndpoint>(value) {
+                    let project = state.project.to_ref().deref().into();
+                    self.open_tab(project, endpoint, state, context);
+                }
+                return;
+            }
+
+            "project_window__selection" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+
+                let value = &*value.to_common_str();
+                if let Ok(project) = serde_json::from_str::<PersistedProject>(value) {
+                    let endpoint = project.endpoints.first().cloned().unwrap_or_default();
+                    self.open_tab(project, endpoint, state, context);
+                }
+                return;
+            }
+
             _ => {}
         }
 
@@ -670,6 +1481,116 @@ impl anathema::component::Component for DashboardComponent {
                     CodeGen::handle_message(value, ident, state, context, elements, component_ids);
                 }
 
+                "endpoint_settings" => {
+                    EndpointSettingsWindow::handle_message(
+                        value,
+                        ident,
+                        state,
+                        context,
+                        elements,
+                        component_ids,
+                    );
+                }
+
+                "project_settings" => {
+                    ProjectSettingsWindow::handle_message(
+                        value,
+                        ident,
+                        state,
+                        context,
+                        elements,
+                        component_ids,
+                    );
+                }
+
+                "websocket_console" => {
+                    WebSocketConsole::handle_message(
+                        value,
+                        ident,
+                        state,
+                        context,
+                        elements,
+                        component_ids,
+                    );
+                }
+
+                "grpc_method_selector" => {
+                    GrpcMethodSelector::handle_message(
+                        value,
+                        ident,
+                        state,
+                        context,
+                        elements,
+                        component_ids,
+                    );
+                }
+
+                "openapi_import" => {
+                    OpenApiImport::handle_message(
+                        value,
+                        ident,
+                        state,
+                        context,
+                        elements,
+                        component_ids,
+                    );
+                }
+
+                "output_path" => {
+                    OutputPathWindow::handle_message(
+                        value,
+                        ident,
+                        state,
+                        context,
+                        elements,
+                        component_ids,
+                    );
+                }
+
+                "project_recovery" => {
+                    ProjectRecoveryWindow::handle_message(
+                        value,
+                        ident,
+                        state,
+                        context,
+                        elements,
+                        component_ids,
+                    );
+                }
+
+                "confirm_action" => {
+                    ConfirmActionWindow::handle_message(
+                        value,
+                        ident,
+                        state,
+                        context,
+                        elements,
+                        component_ids,
+                    );
+                }
+
+                "curl_import" => {
+                    CurlImport::handle_message(
+                        value,
+                        ident,
+                        state,
+                        context,
+                        elements,
+                        component_ids,
+                    );
+                }
+
+                "proxy_settings" => {
+                    ProxySettingsWindow::handle_message(
+                        value,
+                        ident,
+                        state,
+                        context,
+                        elements,
+                        component_ids,
+                    );
+                }
+
                 "add_header" => {
                     AddHeaderWindow::handle_message(
                         value,
@@ -760,9 +1681,10 @@ impl anathema::component::Component for DashboardComponent {
 
                 _ => {}
             }
-        } else {
-            println!("Could not find id for {ident}");
         }
+
+        self.schedule_autosave();
+        self.refresh_tabs(state);
     }
 
     fn on_key(
@@ -774,128 +1696,13 @@ impl anathema::component::Component for DashboardComponent {
     ) {
         match event.code {
             KeyCode::Char(char) => {
-                let main_display = *state.main_display.to_ref();
-
-                match char {
-                    'c' => self.open_commands_window(state, context),
-                    's' => self.save_project(state, true),
-                    'n' => self.open_edit_endpoint_name_window(state, context),
-                    'j' => self.open_edit_project_name_window(state, context),
-                    'i' => self.save_endpoint(state, &context, true),
-                    // 'f' => context.set_focus("id", "response_body_input"),
-                    'o' => self.send_options_open(state, context),
-                    't' => self.new_endpoint(state, context),
-                    'w' => self.new_project(state, context, elements),
-
-                    'v' => match main_display {
-                        DashboardDisplay::RequestBody => {}
-                        DashboardDisplay::RequestHeadersEditor => {}
-                        DashboardDisplay::ResponseBody => self.save_response_body(state, context),
-                        DashboardDisplay::ResponseHeaders => {}
-                    },
+                let binding = KeyBinding {
+                    key: char,
+                    ctrl: event.ctrl,
+                };
 
-                    // Set focus to the request url text input
-                    'u' => {
-                        if !event.ctrl {
-                            context.set_focus("id", "url_input");
-                        }
-                    }
-
-                    // Quit app
-                    'q' => quit::with_code(0),
-
-                    // Make the request
-                    'r' => {
-                        // TODO: Handle the error for making a request and let the user know if
-                        // there was an error
-                        let _ = do_request(state, context, elements, self);
-                    }
-
-                    // Show request body editor window
-                    'b' => match main_display {
-                        DashboardDisplay::RequestBody => context.set_focus("id", "textarea"),
-                        DashboardDisplay::RequestHeadersEditor => {
-                            state.main_display.set(DashboardDisplay::RequestBody);
-                        }
-                        DashboardDisplay::ResponseBody => {
-                            // NOTE: Maybe revert this, needs testing to check focus UX
-                            // state.main_display.set(DashboardDisplay::RequestBody);
-                            context.set_focus("id", "response_renderer");
-                        }
-                        DashboardDisplay::ResponseHeaders => {
-                            state.main_display.set(DashboardDisplay::ResponseBody)
-                        }
-                    },
-
-                    // Show request headers editor window
-                    'd' => {
-                        if !event.ctrl {
-                            state
-                                .main_display
-                                .set(DashboardDisplay::RequestHeadersEditor);
-                        }
-                    }
-
-                    // Open Endpoints selector
-                    'e' => {
-                        self.open_endpoints_selector(state, context);
-                    }
-
-                    // Show projects window
-                    'p' => {
-                        if let Ok(component_ids) = self.component_ids.try_borrow() {
-                            state.floating_window.set(FloatingWindow::Project);
-                            context.set_focus("id", "project_selector");
-
-                            let _ = component_ids.get("project_selector").map(|id| {
-                                context.emit(*id, "projects".to_string());
-                            });
-                        }
-                    }
-
-                    // Show response headers display
-                    'h' => match main_display {
-                        DashboardDisplay::RequestBody => {}
-                        DashboardDisplay::RequestHeadersEditor => {
-                            state
-                                .floating_window
-                                .set(FloatingWindow::EditHeaderSelector);
-                            context.set_focus("id", "edit_header_selector");
-                        }
-                        DashboardDisplay::ResponseBody => {
-                            state.main_display.set(DashboardDisplay::ResponseHeaders)
-                        }
-                        DashboardDisplay::ResponseHeaders => {}
-                    },
-
-                    // Open Request Method selection window
-                    'm' => {
-                        state.floating_window.set(FloatingWindow::Method);
-                        context.set_focus("id", "method_selector");
-                    }
-
-                    'a' => match main_display {
-                        DashboardDisplay::RequestBody => {}
-                        DashboardDisplay::RequestHeadersEditor => {
-                            // Open header window
-                            state.floating_window.set(FloatingWindow::AddHeader);
-                            context.set_focus("id", "add_header_window");
-                        }
-                        DashboardDisplay::ResponseBody => {}
-                        DashboardDisplay::ResponseHeaders => {}
-                    },
-
-                    'y' => match main_display {
-                        DashboardDisplay::RequestBody => {}
-                        DashboardDisplay::RequestHeadersEditor => {}
-                        DashboardDisplay::ResponseBody => {
-                            // Copy response body to clipboard
-                            self.yank_response(state)
-                        }
-                        DashboardDisplay::ResponseHeaders => {}
-                    },
-
-                    _ => {}
+                if let Some(action) = self.keymap.action(KeyContext::Dashboard, binding) {
+                    self.handle_action(action, state, elements, context);
                 }
             }
 
@@ -916,30 +1723,24 @@ impl anathema::component::Component for DashboardComponent {
 
             _ => {}
         }
+
+        self.schedule_autosave();
+        self.refresh_tabs(state);
     }
 
     fn on_focus(
         &mut self,
         state: &mut Self::State,
         _: Elements<'_, '_>,
-        context: Context<'_, Self::State>,
+        mut context: Context<'_, Self::State>,
     ) {
         update_theme(state);
 
-        if self.test {
-            return;
-        }
-
-        // TODO: REMOVE THIS - ONLY FOR TESTING
-        if let Ok(ids) = self.component_ids.try_borrow() {
-            let _ = send_message(
-                "url_text_input",
-                "https://jsonplaceholder.typicode.com/todos".to_string(),
-                &ids,
-                context.emitter,
-            );
+        // NOTE: Autosave can be turned on in the options view, which hands focus back here
+        self.autosave = get_options().autosave;
 
-            self.test = true;
+        if *state.floating_window.to_ref() == FloatingWindow::ProjectRecovery {
+            context.set_focus("id", "project_recovery_window");
         }
     }
 
@@ -948,6 +1749,79 @@ impl anathema::component::Component for DashboardComponent {
     }
 }
 
+/// The response shown on the dashboard, kept by the tab when another one is shown
+fn current_response(state: &DashboardState, body: Option<ResponseBody>) -> Option<TabResponse> {
+    let label = state.response_body_window_label.to_ref().to_string();
+    if label.is_empty() {
+        return None;
+    }
+
+    let headers: Vec<Header> = state
+        .response_headers
+        .to_ref()
+        .iter()
+        .map(|header| Header::from(&*header.to_ref()))
+        .collect();
+
+    // NOTE: Event streams have no body to keep, their tab keeps the text shown
+    let body = body.unwrap_or_else(|| ResponseBody {
+        raw: state.response.to_ref().as_bytes().to_vec(),
+        ext: String::from("txt"),
+        ..Default::default()
+    });
+
+    Some(TabResponse {
+        body,
+        label,
+        headers,
+    })
+}
+
+fn show_tab_labels(tabs: &Tabs, state: &mut DashboardState) {
+    let labels = tabs.tabs().iter().enumerate().map(|(index, tab)| TabLabel {
+        title: tabs.title(index).into(),
+        active: (index == tabs.active_index()).into(),
+        dirty: unsaved_changes(&tab.project, &tab.endpoint).any().into(),
+    });
+
+    state.tabs = List::from_iter(labels);
+
+    let active = tabs.active();
+    let modified = unsaved_changes(&active.project, &active.endpoint).any();
+    state.modified.set(modified);
+}
+
+/// Puts the endpoint into its project and saves it, both need a name first
+fn save_changes(
+    project: &PersistedProject,
+    endpoint: &PersistedEndpoint,
+) -> anyhow::Result<PersistedProject> {
+    let mut project = project.clone();
+
+    if unsaved_changes(&project, endpoint).endpoint {
+        if endpoint.name == DEFAULT_ENDPOINT_NAME {
+            anyhow::bail!("Please give your endpoint a name to save");
+        }
+
+        match project
+            .endpoints
+            .iter_mut()
+            .find(|saved| saved.name == endpoint.name)
+        {
+            Some(saved) => *saved = endpoint.clone(),
+            None => project.endpoints.push(endpoint.clone()),
+        }
+    }
+
+    if project.name == DEFAULT_PROJECT_NAME {
+        anyhow::bail!("Please give your project a name to save");
+    }
+
+    save_project(project.clone())?;
+
+    Ok(project)
+}
+
 fn update_theme(state: &mut DashboardState) {
     let app_theme = get_app_theme_persisted();
 
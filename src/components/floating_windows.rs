@@ -1,7 +1,16 @@
 pub mod app_theme_selector;
 pub mod code_gen;
+pub mod command_palette;
 pub mod commands;
+pub mod curl_import;
 pub mod edit_endpoint_name;
 pub mod edit_project_name;
+pub mod endpoint_settings;
 pub mod endpoints_selector;
+pub mod grpc_method_selector;
+pub mod openapi_import;
+pub mod output_path;
+pub mod project_recovery;
+pub mod project_settings;
+pub mod proxy_settings;
 pub mod syntax_theme_selector;
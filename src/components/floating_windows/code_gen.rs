@@ -1,6 +1,8 @@
 use std::{
     cell::{Ref, RefCell},
     collections::HashMap,
+    fmt::Display,
+    path::PathBuf,
     rc::Rc,
 };
 
@@ -8,24 +10,56 @@ use anathema::{
     component::{self, Component, ComponentId, Emitter},
     prelude::TuiBackend,
     runtime::RuntimeBuilder,
-    state::{State, Value},
+    state::{List, State, Value},
     widgets::Elements,
 };
 
 use crate::{
-    code_gen::{generate_rust, generate_web, WebType},
+    code_gen::{
+        generate_from_template, generate_go, generate_php, generate_python, generate_reqwest,
+        generate_rust, generate_shell, generate_web, module_templates, ModuleTemplate, PythonType,
+        ShellType, WebType,
+    },
     components::{
         dashboard::{DashboardMessageHandler, DashboardMessages, DashboardState, FloatingWindow},
+        floating_windows::output_path::prompt_output_path,
         send_message,
     },
+    keymap::{get_keymap, KeyBinding, KeyContext, Keymap},
+    output::PendingOutput,
+    palette::Action,
+    projects::PersistedProject,
     theme::{get_app_theme, AppTheme},
 };
 
 const TEMPLATE: &str = "./src/components/floating_windows/templates/code_gen.aml";
 
+// NOTE: Module templates are chosen with the keys 1 to 9
+const MAX_MODULE_TEMPLATES: usize = 9;
+
+/// The generators in the order they are listed, with the titles their keys are marked in
+const LANGUAGES: [(Action, &str, &str); 11] = [
+    (Action::GenerateRust, "rust", "Rust"),
+    (Action::GenerateReqwest, "reqwest", "Rust Reqwest"),
+    (Action::GenerateTypeScript, "typescript", "TypeScript"),
+    (Action::GenerateJavaScript, "javascript", "JavaScript"),
+    (
+        Action::GeneratePythonRequests,
+        "python_requests",
+        "Python Requests",
+    ),
+    (Action::GeneratePythonHttpx, "python_httpx", "Python Httpx"),
+    (Action::GenerateGo, "go", "Go"),
+    (Action::GeneratePhp, "php", "PHP Guzzle"),
+    (Action::GenerateCurl, "curl", "CURL"),
+    (Action::GenerateHttpie, "httpie", "HTTPie"),
+    (Action::GenerateWget, "wget", "Wget"),
+];
+
 #[derive(Default)]
 pub struct CodeGen {
-    languages: HashMap<char, String>,
+    keymap: Keymap,
+    module_templates: Vec<ModuleTemplate>,
 }
 
 impl CodeGen {
@@ -33,16 +67,17 @@ impl CodeGen {
         ids: &Rc<RefCell<HashMap<String, ComponentId<String>>>>,
         builder: &mut RuntimeBuilder<TuiBackend, ()>,
     ) -> anyhow::Result<()> {
-        let mut languages = HashMap::<char, String>::new();
-        languages.insert('r', String::from("rust"));
-        languages.insert('t', String::from("typescript"));
-        languages.insert('j', String::from("javascript"));
+        let keymap = get_keymap();
+        let state = CodeGenState::new(&keymap);
 
         let app_id = builder.register_component(
             "codegen_window",
             TEMPLATE,
-            CodeGen { languages },
-            CodeGenState::new(),
+            CodeGen {
+                keymap,
+                module_templates: vec![],
+            },
+            state,
         )?;
 
         let mut ids_ref = ids.borrow_mut();
@@ -56,28 +91,38 @@ impl CodeGen {
         let app_theme = get_app_theme();
         state.app_theme.set(app_theme);
     }
-}
-
-fn show_successful_code_gen_msg(
-    language_name: &str,
-    component_ids: Ref<'_, HashMap<String, ComponentId<String>>>,
-    emitter: Emitter,
-) {
-    let title = format!("{language_name} Code Gen");
-    let msg = String::from("Code generated successfully");
-    let message = DashboardMessages::ShowSucces((title, msg));
 
-    let _ = serde_json::to_string(&message).map(|message| {
-        let _ = send_message("dashboard", message, &component_ids, &emitter);
-    });
+    /// Picks up templates added to the code_templates dir since the window was last opened
+    fn update_module_templates(&mut self, state: &mut CodeGenState) {
+        let mut module_templates = module_templates().unwrap_or_default();
+        module_templates.truncate(MAX_MODULE_TEMPLATES);
+
+        let mut labels = List::<String>::empty();
+        module_templates
+            .iter()
+            .enumerate()
+            .for_each(|(index, module_template)| {
+                labels.push(format!(
+                    "• ({}) {} (.{})",
+                    index + 1,
+                    module_template.name,
+                    module_template.extension
+                ));
+            });
+
+        state.module_templates = labels;
+        state.has_module_templates.set(!module_templates.is_empty());
+        self.module_templates = module_templates;
+    }
 }
 
 fn show_error_code_gen_msg(
     language_name: &str,
+    error: impl Display,
     component_ids: Ref<'_, HashMap<String, ComponentId<String>>>,
     emitter: Emitter,
 ) {
-    let msg = format!("Error generating {language_name} code");
+    let msg = format!("Error generating {language_name} code: {error}");
     let message = DashboardMessages::ShowError(msg);
 
     let _ = serde_json::to_string(&message).map(|message| {
@@ -89,15 +134,27 @@ fn show_error_code_gen_msg(
 pub struct CodeGenState {
     app_theme: Value<AppTheme>,
     language: Value<String>,
+    languages: Value<List<String>>,
+    module_templates: Value<List<String>>,
+    has_module_templates: Value<bool>,
 }
 
 impl CodeGenState {
-    pub fn new() -> Self {
+    pub fn new(keymap: &Keymap) -> Self {
         let app_theme = get_app_theme();
 
+        let mut languages = List::<String>::empty();
+        LANGUAGES.iter().for_each(|(action, _, title)| {
+            let label = keymap.label(KeyContext::CodeGen, *action, title);
+            languages.push(format!("• {label}"));
+        });
+
         CodeGenState {
             app_theme: app_theme.into(),
             language: "".to_string().into(),
+            languages,
+            module_templates: List::empty(),
+            has_module_templates: false.into(),
         }
     }
 }
@@ -117,6 +174,7 @@ impl Component for CodeGen {
         _: anathema::prelude::Context<'_, Self::State>,
     ) {
         self.update_app_theme(state);
+        self.update_module_templates(state);
     }
 
     fn on_key(
@@ -127,12 +185,32 @@ impl Component for CodeGen {
         mut context: anathema::prelude::Context<'_, Self::State>,
     ) {
         match key.code {
-            anathema::component::KeyCode::Char(char) => {
-                let default_language = "rust".to_string();
-                let language = self.languages.get(&char).unwrap_or(&default_language);
-                state.language.set(language.clone());
+            anathema::component::KeyCode::Char(char @ '1'..='9') => {
+                let index = char as usize - '1' as usize;
 
-                context.publish("codegen__selection", |state| &state.language);
+                if let Some(module_template) = self.module_templates.get(index) {
+                    let path = module_template.path.to_string_lossy();
+                    state.language.set(format!("template:{path}"));
+
+                    context.publish("codegen__selection", |state| &state.language);
+                }
+            }
+
+            anathema::component::KeyCode::Char(char) => {
+                let binding = KeyBinding {
+                    key: char,
+                    ctrl: key.ctrl,
+                };
+
+                let action = self.keymap.action(KeyContext::CodeGen, binding);
+                let language = LANGUAGES
+                    .iter()
+                    .find(|(language_action, _, _)| Some(*language_action) == action);
+
+                if let Some((_, language, _)) = language {
+                    state.language.set(language.to_string());
+                    context.publish("codegen__selection", |state| &state.language);
+                }
             }
 
             anathema::component::KeyCode::Esc => {
@@ -155,67 +233,93 @@ impl DashboardMessageHandler for CodeGen {
     ) {
         let event: String = ident.into();
         match event.as_str() {
-            #[allow(clippy::single_match)]
-            "codegen__selection" => match value.to_string().as_str() {
-                "rust" => {
-                    state.floating_window.set(FloatingWindow::None);
-                    context.set_focus("id", "app");
-
-                    let project = state.project.to_ref();
-                    match generate_rust((&*project).into()) {
-                        Ok(_) => {
-                            show_successful_code_gen_msg(
-                                "Rust",
+            "codegen__selection" => {
+                let project: PersistedProject = (&*state.project.to_ref()).into();
+                let project_name = project.name.clone();
+
+                let (result, language_name) = match value.to_string().as_str() {
+                    "rust" => (generate_rust(project), String::from("Rust")),
+                    "typescript" => (
+                        generate_web(project, WebType::TypeScript),
+                        String::from("TypeScript"),
+                    ),
+                    "javascript" => (
+                        generate_web(project, WebType::JavaScript),
+                        String::from("JavaScript"),
+                    ),
+                    "curl" => (
+                        generate_shell(project, ShellType::Curl),
+                        String::from("cURL"),
+                    ),
+                    "httpie" => (
+                        generate_shell(project, ShellType::HTTPie),
+                        String::from("HTTPie"),
+                    ),
+                    "wget" => (
+                        generate_shell(project, ShellType::Wget),
+                        String::from("wget"),
+                    ),
+                    "python_requests" => (
+                        generate_python(project, PythonType::Requests),
+                        String::from("Python (requests)"),
+                    ),
+                    "python_httpx" => (
+                        generate_python(project, PythonType::Httpx),
+                        String::from("Python (httpx)"),
+                    ),
+                    "go" => (generate_go(project), String::from("Go")),
+                    "php" => (generate_php(project), String::from("PHP")),
+                    "reqwest" => (generate_reqwest(project), String::from("Rust (reqwest)")),
+
+                    code_type if code_type.starts_with("template:") => {
+                        let path = PathBuf::from(&code_type["template:".len()..]);
+                        let Some(module_template) = ModuleTemplate::from_path(path) else {
+                            state.floating_window.set(FloatingWindow::None);
+                            context.set_focus("id", "app");
+
+                            show_error_code_gen_msg(
+                                "template",
+                                "the template file was not found",
                                 component_ids,
                                 context.emitter.clone(),
                             );
-                        }
+                            return;
+                        };
 
-                        Err(_) => {
-                            show_error_code_gen_msg("Rust", component_ids, context.emitter.clone());
-                        }
+                        (
+                            generate_from_template(project, &module_template),
+                            module_template.name,
+                        )
                     }
-                }
 
-                code_type @ ("typescript" | "javascript") => {
-                    let web_type = match code_type {
-                        "typescript" => WebType::TypeScript,
-                        "javascript" => WebType::JavaScript,
-                        _ => WebType::TypeScript,
-                    };
+                    _ => return,
+                };
 
-                    let language_name = match code_type {
-                        "typescript" => "TypeScript",
-                        "javascript" => "JavaScript",
-                        _ => "",
-                    };
+                match result {
+                    Ok(file) => {
+                        let output = PendingOutput {
+                            project_name,
+                            title: format!("{language_name} Code Gen"),
+                            file,
+                        };
 
-                    state.floating_window.set(FloatingWindow::None);
-                    context.set_focus("id", "app");
-
-                    let project = state.project.to_ref();
-
-                    match generate_web((&*project).into(), web_type) {
-                        Ok(_) => {
-                            show_successful_code_gen_msg(
-                                language_name,
-                                component_ids,
-                                context.emitter.clone(),
-                            );
-                        }
+                        prompt_output_path(output, state, &mut context, &component_ids);
+                    }
 
-                        Err(_) => {
-                            show_error_code_gen_msg(
-                                language_name,
-                                component_ids,
-                                context.emitter.clone(),
-                            );
-                        }
+                    Err(error) => {
+                        state.floating_window.set(FloatingWindow::None);
+                        context.set_focus("id", "app");
+
+                        // NOTE: Template mistakes are the user's to fix, so errors have the details
+                        show_error_code_gen_msg(
+                            &language_name,
+                            error,
+                            component_ids,
+                            context.emitter.clone(),
+                        );
                     }
                 }
-
-                _ => {}
-            },
+            }
 
             "codegen__cancel" => {
                 state.floating_window.set(FloatingWindow::None);
@@ -0,0 +1,174 @@
+use std::{cell::RefCell, cmp::min, collections::HashMap, rc::Rc};
+
+use anathema::{
+    component::{Component, ComponentId, KeyCode},
+    prelude::{Context, TuiBackend},
+    runtime::RuntimeBuilder,
+    state::{List, State, Value},
+    widgets::Elements,
+};
+
+use crate::{
+    components::list_row::ListRow,
+    keymap::{get_keymap, Keymap},
+    palette::{Action, CommandRegistry},
+    theme::{get_app_theme, AppTheme},
+};
+
+const TEMPLATE: &str = "./src/components/floating_windows/templates/command_palette.aml";
+
+// TODO: Fix the default project row color to the correct gray
+const DEFAULT_ROW_COLOR: &str = "#333333";
+const SELECTED_ROW_COLOR: &str = "#FFFFFF";
+
+const VISIBLE_ROWS: usize = 8;
+
+/// Lists every registered command, filtered by what's typed, and runs the chosen one
+#[derive(Default)]
+pub struct CommandPalette {
+    registry: CommandRegistry,
+    keymap: Keymap,
+    matches: Vec<Action>,
+    first_index: usize,
+}
+
+impl CommandPalette {
+    pub fn register(
+        ids: &Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+        builder: &mut RuntimeBuilder<TuiBackend, ()>,
+    ) -> anyhow::Result<()> {
+        let id = builder.register_component(
+            "command_palette_window",
+            TEMPLATE,
+            CommandPalette {
+                keymap: get_keymap(),
+                ..CommandPalette::default()
+            },
+            CommandPaletteState::new(),
+        )?;
+
+        let mut ids_ref = ids.borrow_mut();
+        ids_ref.insert(String::from("command_palette_window"), id);
+
+        Ok(())
+    }
+
+    /// Searches the registry and scrolls the window so the cursor is visible
+    fn show_commands(&mut self, cursor: usize, state: &mut CommandPaletteState) {
+        let matches = self.registry.search(&state.query.to_ref());
+        let cursor = min(cursor, matches.len().saturating_sub(1));
+
+        if cursor < self.first_index {
+            self.first_index = cursor;
+        }
+
+        if cursor >= self.first_index + VISIBLE_ROWS {
+            self.first_index = cursor + 1 - VISIBLE_ROWS;
+        }
+
+        let mut window_list = List::<ListRow>::empty();
+        matches
+            .iter()
+            .enumerate()
+            .skip(self.first_index)
+            .take(VISIBLE_ROWS)
+            .for_each(|(index, (command, found))| {
+                let row_color = match index == cursor {
+                    true => SELECTED_ROW_COLOR,
+                    false => DEFAULT_ROW_COLOR,
+                };
+
+                let row = ListRow::new(&command.title, &found.positions, row_color)
+                    .with_detail(self.keymap.keybinding(command.action));
+                window_list.push(row);
+            });
+
+        self.matches = matches.iter().map(|(command, _)| command.action).collect();
+
+        state.cursor.set(cursor as u8);
+        state.window_list = window_list;
+    }
+}
+
+#[derive(State)]
+pub struct CommandPaletteState {
+    app_theme: Value<AppTheme>,
+    query: Value<String>,
+    cursor: Value<u8>,
+    window_list: Value<List<ListRow>>,
+    action: Value<String>,
+}
+
+impl CommandPaletteState {
+    pub fn new() -> Self {
+        CommandPaletteState {
+            app_theme: get_app_theme().into(),
+            query: String::from("").into(),
+            cursor: 0.into(),
+            window_list: List::empty(),
+            action: String::from("").into(),
+        }
+    }
+}
+
+impl Component for CommandPalette {
+    type State = CommandPaletteState;
+    type Message = String;
+
+    fn accept_focus(&self) -> bool {
+        true
+    }
+
+    fn on_focus(
+        &mut self,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        _: Context<'_, Self::State>,
+    ) {
+        state.app_theme.set(get_app_theme());
+        state.query.set(String::new());
+
+        self.first_index = 0;
+        self.show_commands(0, state);
+    }
+
+    fn on_key(
+        &mut self,
+        key: anathema::component::KeyEvent,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: Context<'_, Self::State>,
+    ) {
+        let cursor = *state.cursor.to_ref() as usize;
+
+        match key.code {
+            KeyCode::Char(char) => {
+                state.query.to_mut().push(char);
+                self.show_commands(0, state);
+            }
+
+            KeyCode::Backspace => {
+                state.query.to_mut().pop();
+                self.show_commands(0, state);
+            }
+
+            KeyCode::Down => self.show_commands(cursor + 1, state),
+            KeyCode::Up => self.show_commands(cursor.saturating_sub(1), state),
+
+            KeyCode::Enter => {
+                let Some(action) = self.matches.get(cursor) else {
+                    return;
+                };
+
+                if let Ok(action) = serde_json::to_string(action) {
+                    state.action.set(action);
+                    context.publish("command_palette__run", |state| &state.action);
+                }
+            }
+
+            KeyCode::Esc => context.publish("command_palette__cancel", |state| &state.query),
+
+            _ => {}
+        }
+    }
+}
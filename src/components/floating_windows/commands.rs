@@ -6,38 +6,60 @@ use std::{
 
 use anathema::{
     component::{self, Component, ComponentId},
-    prelude::TuiBackend,
+    prelude::{Context, TuiBackend},
     runtime::RuntimeBuilder,
-    state::{State, Value},
+    state::{List, State, Value},
     widgets::Elements,
 };
 
 use crate::{
-    compatibility::postman::export_postman,
+    compatibility::{openapi::export_openapi, postman::export_postman},
     components::{
         dashboard::{DashboardMessageHandler, DashboardMessages, DashboardState, FloatingWindow},
+        floating_windows::{
+            endpoint_settings::{EndpointSettings, EndpointSettingsMessages},
+            output_path::prompt_output_path,
+            project_settings::{ProjectSettings, ProjectSettingsMessages},
+            proxy_settings::ProxySettingsMessages,
+        },
         send_message,
     },
+    keymap::{get_keymap, KeyBinding, KeyContext, Keymap},
+    output::{OutputFile, PendingOutput},
+    palette::Action,
     projects::PersistedProject,
     theme::{get_app_theme, AppTheme},
 };
 
 const TEMPLATE: &str = "./src/components/floating_windows/templates/commands.aml";
 
+/// The commands in the order they are listed, with the titles their keys are marked in
+const COMMANDS: [(Action, &str); 8] = [
+    (Action::GenerateCode, "Generate Code"),
+    (Action::ExportPostman, "Export to Postman"),
+    (Action::ImportOpenApi, "Import OpenAPI / Postman"),
+    (Action::ExportOpenApi, "Export OpenAPI"),
+    (Action::ImportCurl, "Import Curl"),
+    (Action::EndpointSettings, "Endpoint Settings"),
+    (Action::ProjectSettings, "Project Settings"),
+    (Action::ProxySettings, "Proxy Settings"),
+];
+
 #[derive(Default)]
-pub struct Commands;
+pub struct Commands {
+    keymap: Keymap,
+}
 
 impl Commands {
     pub fn register(
         ids: &Rc<RefCell<HashMap<String, ComponentId<String>>>>,
         builder: &mut RuntimeBuilder<TuiBackend, ()>,
     ) -> anyhow::Result<()> {
-        let app_id = builder.register_component(
-            "commands_window",
-            TEMPLATE,
-            Commands {},
-            CommandsState::new(),
-        )?;
+        let keymap = get_keymap();
+        let state = CommandsState::new(&keymap);
+
+        let app_id =
+            builder.register_component("commands_window", TEMPLATE, Commands { keymap }, state)?;
 
         let mut ids_ref = ids.borrow_mut();
         ids_ref.insert("commands_window".to_string(), app_id);
@@ -52,19 +74,59 @@ impl Commands {
     }
 }
 
+/// Asks where an export goes, or reports why it couldn't be made
+fn prompt_export(
+    title: &str,
+    project_name: String,
+    result: Result<OutputFile, String>,
+    state: &mut DashboardState,
+    mut context: Context<'_, DashboardState>,
+    component_ids: &Ref<'_, HashMap<String, ComponentId<String>>>,
+) {
+    match result {
+        Ok(file) => {
+            let output = PendingOutput {
+                project_name,
+                title: title.to_string(),
+                file,
+            };
+
+            prompt_output_path(output, state, &mut context, component_ids);
+        }
+
+        Err(message) => {
+            state.floating_window.set(FloatingWindow::None);
+            context.set_focus("id", "app");
+
+            let dashboard_message = DashboardMessages::ShowError(message);
+            if let Ok(msg) = serde_json::to_string(&dashboard_message) {
+                let _ = send_message("dashboard", msg, component_ids, context.emitter);
+            }
+        }
+    }
+}
+
 #[derive(Default, State)]
 pub struct CommandsState {
     app_theme: Value<AppTheme>,
-    command: Value<char>,
+    command: Value<String>,
+    labels: Value<List<String>>,
 }
 
 impl CommandsState {
-    pub fn new() -> Self {
+    pub fn new(keymap: &Keymap) -> Self {
         let app_theme = get_app_theme();
 
+        let mut labels = List::<String>::empty();
+        COMMANDS.iter().for_each(|(action, title)| {
+            let label = keymap.label(KeyContext::Commands, *action, title);
+            labels.push(format!("• {label}"));
+        });
+
         CommandsState {
             app_theme: app_theme.into(),
-            command: ' '.into(),
+            command: String::new().into(),
+            labels,
         }
     }
 }
@@ -95,8 +157,16 @@ impl Component for Commands {
     ) {
         match key.code {
             anathema::component::KeyCode::Char(char) => {
-                state.command.set(char);
-                context.publish("commands__selection", |state| &state.command);
+                let binding = KeyBinding {
+                    key: char,
+                    ctrl: key.ctrl,
+                };
+
+                let action = self.keymap.action(KeyContext::Commands, binding);
+                if let Some(Ok(action)) = action.map(|action| serde_json::to_string(&action)) {
+                    state.command.set(action);
+                    context.publish("commands__selection", |state| &state.command);
+                }
             }
 
             anathema::component::KeyCode::Esc => {
@@ -120,42 +190,118 @@ impl DashboardMessageHandler for Commands {
         let event: String = ident.into();
 
         match event.as_str() {
-            #[allow(clippy::single_match)]
-            "commands__selection" => match value.to_string().as_str() {
-                "g" => {
+            "commands__selection" => match serde_json::from_str::<Action>(&value.to_string()) {
+                Ok(Action::GenerateCode) => {
                     state.floating_window.set(FloatingWindow::CodeGen);
                     context.set_focus("id", "codegen_window");
                 }
 
-                "e" => {
-                    state.floating_window.set(FloatingWindow::CodeGen);
-                    context.set_focus("id", "codegen_window");
+                Ok(Action::EndpointSettings) => {
+                    state.floating_window.set(FloatingWindow::EndpointSettings);
+                    context.set_focus("id", "endpoint_settings_window");
+
+                    let endpoint = state.endpoint.to_ref();
+                    let settings = EndpointSettings {
+                        follow_redirects: *endpoint.follow_redirects.to_ref(),
+                        max_redirects: *endpoint.max_redirects.to_ref(),
+                    };
+
+                    let message = EndpointSettingsMessages::Settings(settings);
+                    if let Ok(msg) = serde_json::to_string(&message) {
+                        let _ = send_message(
+                            "endpoint_settings_window",
+                            msg,
+                            &component_ids,
+                            context.emitter,
+                        );
+                    }
+                }
+
+                Ok(Action::ProjectSettings) => {
+                    state.floating_window.set(FloatingWindow::ProjectSettings);
+                    context.set_focus("id", "project_settings_window");
 
                     let project: PersistedProject = (&*state.project.to_ref()).into();
+                    let settings = ProjectSettings {
+                        tls: project.tls,
+                        proto_files: project.proto_files,
+                        variables: project.variables,
+                    };
 
-                    match export_postman(project) {
-                        Ok(_) => {
-                            let title = "Postman Export".to_string();
-                            let message = "Postman exported successfully".to_string();
-                            let dashboard_message = DashboardMessages::ShowSucces((title, message));
-                            let msg = serde_json::to_string(&dashboard_message);
-                            if let Ok(msg) = msg {
-                                let _ =
-                                    send_message("dashboard", msg, &component_ids, context.emitter);
-                            }
-                        }
-                        Err(_) => {
-                            let message = "Postman export failed".to_string();
-                            let dashboard_message = DashboardMessages::ShowError(message);
-                            let msg = serde_json::to_string(&dashboard_message);
-                            if let Ok(msg) = msg {
-                                let _ =
-                                    send_message("dashboard", msg, &component_ids, context.emitter);
-                            }
-                        }
+                    let message = ProjectSettingsMessages::Settings(settings);
+                    if let Ok(msg) = serde_json::to_string(&message) {
+                        let _ = send_message(
+                            "project_settings_window",
+                            msg,
+                            &component_ids,
+                            context.emitter,
+                        );
                     }
                 }
 
+                Ok(Action::ImportOpenApi) => {
+                    state.floating_window.set(FloatingWindow::OpenApiImport);
+                    context.set_focus("id", "openapi_import");
+                }
+
+                Ok(Action::ImportCurl) => {
+                    state.floating_window.set(FloatingWindow::CurlImport);
+                    context.set_focus("id", "curl_import");
+                }
+
+                Ok(Action::ExportOpenApi) => {
+                    let project: PersistedProject = (&*state.project.to_ref()).into();
+                    let project_name = project.name.clone();
+
+                    let result = export_openapi(&project)
+                        .map_err(|error| format!("OpenAPI export failed: {error}"));
+
+                    prompt_export(
+                        "OpenAPI Export",
+                        project_name,
+                        result,
+                        state,
+                        context,
+                        &component_ids,
+                    );
+                }
+
+                Ok(Action::ProxySettings) => {
+                    state.floating_window.set(FloatingWindow::ProxySettings);
+                    context.set_focus("id", "proxy_settings_window");
+
+                    let project: PersistedProject = (&*state.project.to_ref()).into();
+                    let settings = project.proxy.unwrap_or_default();
+
+                    let message =
+                        ProxySettingsMessages::Settings(String::from("Project Override"), settings);
+                    if let Ok(msg) = serde_json::to_string(&message) {
+                        let _ = send_message(
+                            "proxy_settings_window",
+                            msg,
+                            &component_ids,
+                            context.emitter,
+                        );
+                    }
+                }
+
+                Ok(Action::ExportPostman) => {
+                    let project: PersistedProject = (&*state.project.to_ref()).into();
+                    let project_name = project.name.clone();
+
+                    let result = export_postman(project)
+                        .map_err(|error| format!("Postman export failed: {error}"));
+
+                    prompt_export(
+                        "Postman Export",
+                        project_name,
+                        result,
+                        state,
+                        context,
+                        &component_ids,
+                    );
+                }
+
                 _ => {}
             },
 
@@ -0,0 +1,284 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use anathema::{
+    component::{Component, ComponentId, KeyCode},
+    prelude::{Context, TuiBackend},
+    runtime::RuntimeBuilder,
+    state::{State, Value},
+    widgets::Elements,
+};
+use arboard::Clipboard;
+
+use crate::{
+    compatibility::curl::parse_curl,
+    components::{
+        dashboard::{DashboardMessageHandler, DashboardMessages, DashboardState, FloatingWindow},
+        project_window::open_endpoint,
+        send_message,
+        textarea::TextAreaMessages,
+    },
+    keymap::{get_keymap, KeyBinding, KeyContext, Keymap},
+    palette::Action,
+    projects::{save_project, unique_endpoint_name, PersistedProject, DEFAULT_PROJECT_NAME},
+    theme::{get_app_theme, AppTheme},
+};
+
+const TEMPLATE: &str = "./src/components/floating_windows/templates/curl_import.aml";
+
+pub struct CurlImport {
+    component_ids: Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+    keymap: Keymap,
+}
+
+impl CurlImport {
+    pub fn register(
+        ids: &Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+        builder: &mut RuntimeBuilder<TuiBackend, ()>,
+    ) -> anyhow::Result<()> {
+        let keymap = get_keymap();
+        let state = CurlImportState::new(&keymap);
+
+        let id = builder.register_component(
+            "curl_import",
+            TEMPLATE,
+            CurlImport {
+                component_ids: ids.clone(),
+                keymap,
+            },
+            state,
+        )?;
+
+        let mut ids_ref = ids.borrow_mut();
+        ids_ref.insert(String::from("curl_import"), id);
+
+        Ok(())
+    }
+
+    fn set_command(
+        &self,
+        command: String,
+        state: &mut CurlImportState,
+        context: &Context<'_, CurlImportState>,
+    ) {
+        if let Ok(component_ids) = self.component_ids.try_borrow() {
+            let message = TextAreaMessages::SetInput(command.clone());
+            if let Ok(message) = serde_json::to_string(&message) {
+                let _ = send_message(
+                    "curl_command_input",
+                    message,
+                    &component_ids,
+                    context.emitter,
+                );
+            }
+        }
+
+        state.command.set(command);
+    }
+
+    fn paste(&self, state: &mut CurlImportState, context: &Context<'_, CurlImportState>) {
+        let text = Clipboard::new().and_then(|mut clipboard| clipboard.get_text());
+
+        if let Ok(text) = text {
+            self.set_command(text, state, context);
+        }
+    }
+}
+
+#[derive(State)]
+pub struct CurlImportState {
+    app_theme: Value<AppTheme>,
+    command: Value<String>,
+    hint: Value<String>,
+    submit_label: Value<String>,
+    cancel_label: Value<String>,
+}
+
+impl CurlImportState {
+    pub fn new(keymap: &Keymap) -> Self {
+        let label = |action, title| keymap.label(KeyContext::CurlImport, action, title);
+        let hint = format!(
+            "{} the command or {} it from the clipboard",
+            label(Action::EditCommand, "Edit"),
+            label(Action::PasteCommand, "Paste")
+        );
+
+        CurlImportState {
+            app_theme: get_app_theme().into(),
+            command: String::from("").into(),
+            hint: hint.into(),
+            submit_label: label(Action::Submit, "Submit").into(),
+            cancel_label: label(Action::Cancel, "Cancel").into(),
+        }
+    }
+}
+
+impl DashboardMessageHandler for CurlImport {
+    fn handle_message(
+        value: anathema::state::CommonVal<'_>,
+        ident: impl Into<String>,
+        state: &mut DashboardState,
+        mut context: anathema::prelude::Context<'_, DashboardState>,
+        _: Elements<'_, '_>,
+        component_ids: std::cell::Ref<'_, HashMap<String, ComponentId<String>>>,
+    ) {
+        let event: String = ident.into();
+
+        match event.as_str() {
+            "curl_import__submit" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+
+                let dashboard_message = match parse_curl(&value.to_string()) {
+                    Ok(import) => {
+                        let mut project: PersistedProject = (&*state.project.to_ref()).into();
+                        let mut endpoint = import.endpoint;
+                        endpoint.name = unique_endpoint_name(&endpoint.name, &project.endpoints);
+                        project.endpoints.push(endpoint.clone());
+
+                        // NOTE: Unnamed projects are saved once they are given a name
+                        let saved = match project.name == DEFAULT_PROJECT_NAME {
+                            true => Ok(()),
+                            false => save_project(project.clone()),
+                        };
+
+                        state.project.set((&project).into());
+                        state.endpoint_count.set(project.endpoints.len() as u8);
+                        open_endpoint(&endpoint, state, &context, &component_ids);
+
+                        let mut message = format!("Imported {}", endpoint.name);
+                        if !import.unsupported.is_empty() {
+                            message.push_str(&format!(
+                                ". Ignored unsupported options: {}",
+                                import.unsupported.join(", ")
+                            ));
+                        }
+
+                        if import.insecure {
+                            message.push_str(
+                                ". Certificate checks (-k) can be turned off in Project Settings",
+                            );
+                        }
+
+                        match saved {
+                            Ok(_) => {
+                                DashboardMessages::ShowSucces(("cURL Import".to_string(), message))
+                            }
+                            Err(error) => DashboardMessages::ShowError(format!(
+                                "{message}, but the project could not be saved: {error}"
+                            )),
+                        }
+                    }
+
+                    Err(error) => {
+                        DashboardMessages::ShowError(format!("cURL import failed: {error}"))
+                    }
+                };
+
+                if let Ok(message) = serde_json::to_string(&dashboard_message) {
+                    let _ = send_message("dashboard", message, &component_ids, context.emitter);
+                }
+
+                // NOTE: The window starts empty the next time it is opened
+                if let Ok(message) =
+                    serde_json::to_string(&TextAreaMessages::SetInput(String::from("")))
+                {
+                    let _ = send_message("curl_import", message, &component_ids, context.emitter);
+                }
+            }
+
+            "curl_import__cancel" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+            }
+
+            _ => {}
+        }
+    }
+}
+
+impl Component for CurlImport {
+    type State = CurlImportState;
+    type Message = String;
+
+    fn accept_focus(&self) -> bool {
+        true
+    }
+
+    fn on_focus(
+        &mut self,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        _: Context<'_, Self::State>,
+    ) {
+        state.app_theme.set(get_app_theme());
+    }
+
+    fn receive(
+        &mut self,
+        ident: &str,
+        value: anathema::state::CommonVal<'_>,
+        _: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: Context<'_, Self::State>,
+    ) {
+        // NOTE: Leaving the command input comes back to the window instead of the dashboard
+        if ident == "command_input_focus" && !value.to_bool() {
+            context.set_focus("id", "curl_import");
+        }
+    }
+
+    fn on_key(
+        &mut self,
+        key: anathema::component::KeyEvent,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: Context<'_, Self::State>,
+    ) {
+        match key.code {
+            KeyCode::Char(char) => {
+                let binding = KeyBinding {
+                    key: char,
+                    ctrl: key.ctrl,
+                };
+
+                match self.keymap.action(KeyContext::CurlImport, binding) {
+                    Some(Action::EditCommand) => context.set_focus("id", "curl_command"),
+                    Some(Action::PasteCommand) => self.paste(state, &context),
+                    Some(Action::Submit) => {
+                        context.publish("curl_import__submit", |state| &state.command)
+                    }
+                    Some(Action::Cancel) => {
+                        context.publish("curl_import__cancel", |state| &state.command)
+                    }
+
+                    _ => {}
+                }
+            }
+
+            KeyCode::Esc => context.publish("curl_import__cancel", |state| &state.command),
+
+            _ => {}
+        }
+    }
+
+    fn message(
+        &mut self,
+        message: Self::Message,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        context: Context<'_, Self::State>,
+    ) {
+        // NOTE: Typed text comes from the textarea wrapped for its listeners, a new
+        // command comes as a bare SetInput
+        if let Some(update) = TextAreaMessages::input_update(&message) {
+            state.command.set(update.value);
+            return;
+        }
+
+        if let Ok(TextAreaMessages::SetInput(command)) =
+            serde_json::from_str::<TextAreaMessages>(&message)
+        {
+            self.set_command(command, state, &context);
+        }
+    }
+}
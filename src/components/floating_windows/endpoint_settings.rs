@@ -0,0 +1,217 @@
+use std::{
+    cell::{Ref, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+
+use anathema::{
+    component::{self, Component, ComponentId, KeyCode},
+    prelude::TuiBackend,
+    runtime::RuntimeBuilder,
+    state::{State, Value},
+    widgets::Elements,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::dashboard::{DashboardMessageHandler, DashboardState, FloatingWindow},
+    projects::DEFAULT_MAX_REDIRECTS,
+    theme::{get_app_theme, AppTheme},
+};
+
+const TEMPLATE: &str = "./src/components/floating_windows/templates/endpoint_settings.aml";
+
+// NOTE: Upper bound for the redirect hops setting
+const MAX_REDIRECTS_LIMIT: usize = 30;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointSettings {
+    pub follow_redirects: bool,
+    pub max_redirects: usize,
+}
+
+impl Default for EndpointSettings {
+    fn default() -> Self {
+        EndpointSettings {
+            follow_redirects: true,
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum EndpointSettingsMessages {
+    Settings(EndpointSettings),
+}
+
+#[derive(Default)]
+pub struct EndpointSettingsWindow {
+    settings: EndpointSettings,
+}
+
+impl EndpointSettingsWindow {
+    pub fn register(
+        ids: &Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+        builder: &mut RuntimeBuilder<TuiBackend, ()>,
+    ) -> anyhow::Result<()> {
+        let id = builder.register_component(
+            "endpoint_settings_window",
+            TEMPLATE,
+            EndpointSettingsWindow::default(),
+            EndpointSettingsState::new(),
+        )?;
+
+        let mut ids_ref = ids.borrow_mut();
+        ids_ref.insert(String::from("endpoint_settings_window"), id);
+
+        Ok(())
+    }
+
+    fn update_app_theme(&self, state: &mut EndpointSettingsState) {
+        let app_theme = get_app_theme();
+        state.app_theme.set(app_theme);
+    }
+
+    fn update_labels(&self, state: &mut EndpointSettingsState) {
+        let follow_redirects = match self.settings.follow_redirects {
+            true => "on",
+            false => "off",
+        };
+
+        state.follow_redirects.set(follow_redirects.to_string());
+        state.max_redirects.set(self.settings.max_redirects);
+    }
+}
+
+#[derive(Default, State)]
+pub struct EndpointSettingsState {
+    follow_redirects: Value<String>,
+    max_redirects: Value<usize>,
+    settings: Value<String>,
+    app_theme: Value<AppTheme>,
+}
+
+impl EndpointSettingsState {
+    pub fn new() -> Self {
+        let app_theme = get_app_theme();
+
+        EndpointSettingsState {
+            follow_redirects: "on".to_string().into(),
+            max_redirects: DEFAULT_MAX_REDIRECTS.into(),
+            settings: "".to_string().into(),
+            app_theme: app_theme.into(),
+        }
+    }
+}
+
+impl Component for EndpointSettingsWindow {
+    type State = EndpointSettingsState;
+    type Message = String;
+
+    fn accept_focus(&self) -> bool {
+        true
+    }
+
+    fn on_focus(
+        &mut self,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        _: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        self.update_app_theme(state);
+    }
+
+    fn on_key(
+        &mut self,
+        key: component::KeyEvent,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        match key.code {
+            KeyCode::Char(char) => match char {
+                'f' => {
+                    self.settings.follow_redirects = !self.settings.follow_redirects;
+                    self.update_labels(state);
+                }
+
+                '+' | 'k' => {
+                    let max_redirects = self.settings.max_redirects + 1;
+                    self.settings.max_redirects = max_redirects.min(MAX_REDIRECTS_LIMIT);
+                    self.update_labels(state);
+                }
+
+                '-' | 'j' => {
+                    self.settings.max_redirects = self.settings.max_redirects.saturating_sub(1);
+                    self.update_labels(state);
+                }
+
+                's' => {
+                    if let Ok(settings) = serde_json::to_string(&self.settings) {
+                        state.settings.set(settings);
+                        context.publish("endpoint_settings__submit", |state| &state.settings);
+                    }
+                }
+
+                'c' => context.publish("endpoint_settings__cancel", |state| &state.settings),
+
+                _ => {}
+            },
+
+            KeyCode::Esc => context.publish("endpoint_settings__cancel", |state| &state.settings),
+
+            _ => {}
+        }
+    }
+
+    fn message(
+        &mut self,
+        message: Self::Message,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        _: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        if let Ok(settings_message) = serde_json::from_str::<EndpointSettingsMessages>(&message) {
+            match settings_message {
+                EndpointSettingsMessages::Settings(settings) => {
+                    self.settings = settings;
+                    self.update_labels(state);
+                }
+            }
+        }
+    }
+}
+
+impl DashboardMessageHandler for EndpointSettingsWindow {
+    fn handle_message(
+        value: component::CommonVal<'_>,
+        ident: impl Into<String>,
+        state: &mut DashboardState,
+        mut context: anathema::prelude::Context<'_, DashboardState>,
+        _: Elements<'_, '_>,
+        _: Ref<'_, HashMap<String, ComponentId<String>>>,
+    ) {
+        let event: String = ident.into();
+
+        match event.as_str() {
+            "endpoint_settings__submit" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+
+                let value = &*value.to_common_str();
+                if let Ok(settings) = serde_json::from_str::<EndpointSettings>(value) {
+                    let mut endpoint = state.endpoint.to_mut();
+                    endpoint.follow_redirects.set(settings.follow_redirects);
+                    endpoint.max_redirects.set(settings.max_redirects);
+                }
+            }
+
+            "endpoint_settings__cancel" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+            }
+
+            _ => {}
+        }
+    }
+}
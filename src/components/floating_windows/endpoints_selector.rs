@@ -1,12 +1,13 @@
 use std::{
-    cell::RefCell,
-    cmp::{max, min},
-    collections::HashMap,
+    cell::{Ref, RefCell},
+    cmp::min,
+    collections::{HashMap, HashSet},
+    ops::Deref,
     rc::Rc,
 };
 
 use anathema::{
-    component::{Component, ComponentId},
+    component::{Component, ComponentId, Emitter, KeyCode},
     prelude::TuiBackend,
     runtime::RuntimeBuilder,
     state::{List, State, Value},
@@ -15,15 +16,48 @@ use anathema::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    components::dashboard::{DashboardMessageHandler, DashboardState, FloatingWindow},
+    components::{
+        dashboard::{DashboardMessageHandler, DashboardMessages, DashboardState, FloatingWindow},
+        list_row::ListRow,
+        send_message,
+        textarea::TextAreaMessages,
+    },
+    fuzzy::fuzzy_search,
+    keymap::{get_keymap, KeyBinding, KeyContext, Keymap},
     messages::confirm_delete_endpoint::ConfirmDeleteEndpoint,
-    projects::{Endpoint, PersistedEndpoint},
+    palette::Action,
+    projects::{
+        folders::{
+            folder_ancestors, folder_name, format_headers, normalize_folder, parse_headers,
+            set_folder_headers, tree_rows, Folder, TreeRow,
+        },
+        save_project, PersistedEndpoint, PersistedProject,
+    },
     theme::{get_app_theme, AppTheme},
 };
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum EndpointsSelectorMessages {
-    EndpointsList(Vec<PersistedEndpoint>),
+    EndpointsList(Vec<PersistedEndpoint>, Vec<Folder>),
+
+    /// The project changed while the selector is open, keeps the cursor in place
+    EndpointsUpdate(Vec<PersistedEndpoint>, Vec<Folder>),
+}
+
+/// A change made from the endpoints tree
+#[derive(Debug, Serialize, Deserialize)]
+pub enum EndpointsTreeEdit {
+    Move { endpoint: String, folder: String },
+    FolderHeaders { path: String, headers: String },
+}
+
+/// What the inline input of the selector is editing
+#[derive(Debug, Default, Clone)]
+enum TreeInput {
+    #[default]
+    None,
+    Move(String),
+    FolderHeaders(String),
 }
 
 pub const ENDPOINTS_SELECTOR_TEMPLATE: &str =
@@ -33,21 +67,33 @@ pub const ENDPOINTS_SELECTOR_TEMPLATE: &str =
 const DEFAULT_ROW_COLOR: &str = "#333333";
 const SELECTED_ROW_COLOR: &str = "#FFFFFF";
 
+const TREE_INDENT: &str = "  ";
+
 #[derive(Default, State)]
 pub struct EndpointsSelectorState {
     cursor: Value<u8>,
     current_first_index: Value<u8>,
     current_last_index: Value<u8>,
     visible_rows: Value<u8>,
-    window_list: Value<List<Endpoint>>,
+    window_list: Value<List<ListRow>>,
     count: Value<u8>,
     selected_item: Value<String>,
+    input_mode: Value<String>,
+    input_label: Value<String>,
+    searching: Value<bool>,
+    query: Value<String>,
+    delete_label: Value<String>,
     app_theme: Value<AppTheme>,
 }
 
 impl EndpointsSelectorState {
-    pub fn new() -> Self {
+    pub fn new(keymap: &Keymap) -> Self {
         let app_theme = get_app_theme();
+        let delete_label = keymap.label(
+            KeyContext::EndpointsSelector,
+            Action::DeleteEndpoint,
+            "Delete",
+        );
 
         EndpointsSelectorState {
             cursor: 0.into(),
@@ -57,6 +103,11 @@ impl EndpointsSelectorState {
             visible_rows: 5.into(),
             window_list: List::empty(),
             selected_item: "".to_string().into(),
+            input_mode: "".to_string().into(),
+            input_label: "".to_string().into(),
+            searching: false.into(),
+            query: "".to_string().into(),
+            delete_label: delete_label.into(),
             app_theme: app_theme.into(),
         }
     }
@@ -64,9 +115,18 @@ impl EndpointsSelectorState {
 
 #[derive(Default)]
 pub struct EndpointsSelector {
-    #[allow(dead_code)]
     component_ids: Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+    keymap: Keymap,
     items_list: Vec<PersistedEndpoint>,
+    folders: Vec<Folder>,
+    rows: Vec<TreeRow>,
+    expanded: HashSet<String>,
+    input: TreeInput,
+    input_value: String,
+
+    /// While searching the tree is replaced by the matching endpoints, best first
+    search: Option<String>,
+    highlights: Vec<Vec<usize>>,
 }
 
 impl EndpointsSelector {
@@ -74,11 +134,14 @@ impl EndpointsSelector {
         ids: &Rc<RefCell<HashMap<String, ComponentId<String>>>>,
         builder: &mut RuntimeBuilder<TuiBackend, ()>,
     ) -> anyhow::Result<()> {
+        let keymap = get_keymap();
+        let state = EndpointsSelectorState::new(&keymap);
+
         let id = builder.register_component(
             "endpoints_selector_window",
             ENDPOINTS_SELECTOR_TEMPLATE,
-            EndpointsSelector::new(ids.clone()),
-            EndpointsSelectorState::new(),
+            EndpointsSelector::new(ids.clone(), keymap),
+            state,
         )?;
 
         let mut ids_ref = ids.borrow_mut();
@@ -92,58 +155,106 @@ impl EndpointsSelector {
         state.app_theme.set(app_theme);
     }
 
-    pub fn new(component_ids: Rc<RefCell<HashMap<String, ComponentId<String>>>>) -> Self {
+    pub fn new(
+        component_ids: Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+        keymap: Keymap,
+    ) -> Self {
         EndpointsSelector {
             component_ids,
-            items_list: vec![],
+            keymap,
+            ..Default::default()
         }
     }
 
-    fn move_cursor_down(&self, state: &mut EndpointsSelectorState) {
-        let last_complete_list_index = self.items_list.len().saturating_sub(1);
-        let new_cursor = min(*state.cursor.to_ref() + 1, last_complete_list_index as u8);
-        state.cursor.set(new_cursor);
+    fn move_cursor_down(&mut self, state: &mut EndpointsSelectorState) {
+        let cursor = *state.cursor.to_ref() as usize + 1;
+        self.show_rows(cursor, state);
+    }
 
-        let mut first_index = *state.current_first_index.to_ref();
-        let mut last_index = *state.current_last_index.to_ref();
+    fn move_cursor_up(&mut self, state: &mut EndpointsSelectorState) {
+        let cursor = state.cursor.to_ref().saturating_sub(1) as usize;
+        self.show_rows(cursor, state);
+    }
 
-        if new_cursor > last_index {
-            last_index = new_cursor;
-            first_index = new_cursor - (*state.visible_rows.to_ref() - 1);
+    /// Rebuilds the tree, or the search results, and scrolls the window so the
+    /// cursor row is visible
+    fn show_rows(&mut self, cursor: usize, state: &mut EndpointsSelectorState) {
+        match &self.search {
+            Some(query) => {
+                let matches = fuzzy_search(query, &self.items_list, endpoint_search_text);
+
+                self.rows = matches
+                    .iter()
+                    .map(|(index, _)| TreeRow::Endpoint {
+                        index: *index,
+                        depth: 0,
+                    })
+                    .collect();
+                self.highlights = matches
+                    .into_iter()
+                    .map(|(_, found)| found.positions)
+                    .collect();
+            }
 
-            state.current_first_index.set(first_index);
-            state.current_last_index.set(last_index);
+            None => {
+                self.rows = tree_rows(&self.items_list, &self.folders, &self.expanded);
+                self.highlights.clear();
+            }
         }
 
-        self.update_list(
-            first_index.into(),
-            last_index.into(),
-            new_cursor.into(),
-            state,
-        );
-    }
+        let visible_rows = *state.visible_rows.to_ref() as usize;
+        let cursor = min(cursor, self.rows.len().saturating_sub(1));
+        let mut first_index = *state.current_first_index.to_ref() as usize;
 
-    fn move_cursor_up(&self, state: &mut EndpointsSelectorState) {
-        let new_cursor = max(state.cursor.to_ref().saturating_sub(1), 0);
-        state.cursor.set(new_cursor);
+        if cursor < first_index {
+            first_index = cursor;
+        }
 
-        let mut first_index = *state.current_first_index.to_ref();
-        let mut last_index = *state.current_last_index.to_ref();
+        if cursor >= first_index + visible_rows {
+            first_index = cursor + 1 - visible_rows;
+        }
 
-        if new_cursor < first_index {
-            first_index = new_cursor;
-            last_index = new_cursor + (*state.visible_rows.to_ref() - 1);
+        // NOTE: Collapsing a folder can leave the window past the end of the tree
+        first_index = min(first_index, self.rows.len().saturating_sub(visible_rows));
+        let last_index = first_index + visible_rows - 1;
 
-            state.current_first_index.set(first_index);
-            state.current_last_index.set(last_index);
-        }
+        state.cursor.set(cursor as u8);
+        state.current_first_index.set(first_index as u8);
+        state.current_last_index.set(last_index as u8);
+        state.count.set(self.rows.len() as u8);
 
-        self.update_list(
-            first_index.into(),
-            last_index.into(),
-            new_cursor.into(),
-            state,
-        );
+        self.update_list(first_index, last_index, cursor, state);
+    }
+
+    fn row_label(&self, row: &TreeRow) -> String {
+        match row {
+            TreeRow::Folder {
+                path,
+                depth,
+                expanded,
+            } => {
+                let marker = if *expanded { "▾" } else { "▸" };
+                format!(
+                    "{}{marker} {}",
+                    TREE_INDENT.repeat(*depth),
+                    folder_name(path)
+                )
+            }
+
+            TreeRow::Endpoint { index, .. } if self.search.is_some() => {
+                endpoint_search_text(&self.items_list[*index])
+            }
+
+            TreeRow::Endpoint { index, depth } => {
+                let endpoint = &self.items_list[*index];
+                format!(
+                    "{}  {} {}",
+                    TREE_INDENT.repeat(*depth),
+                    endpoint.method,
+                    endpoint.name
+                )
+            }
+        }
     }
 
     fn update_list(
@@ -153,47 +264,231 @@ impl EndpointsSelector {
         selected_index: usize,
         state: &mut EndpointsSelectorState,
     ) {
-        if self.items_list.is_empty() {
-            return;
+        let mut new_list_state = List::<ListRow>::empty();
+
+        self.rows
+            .iter()
+            .enumerate()
+            .skip(first_index)
+            .take(last_index + 1 - first_index)
+            .for_each(|(index, row)| {
+                let row_color = match index == selected_index {
+                    true => SELECTED_ROW_COLOR,
+                    false => DEFAULT_ROW_COLOR,
+                };
+
+                let highlights = self.highlights.get(index).map(Vec::as_slice);
+                let label = self.row_label(row);
+
+                new_list_state.push(ListRow::new(
+                    &label,
+                    highlights.unwrap_or_default(),
+                    row_color,
+                ));
+            });
+
+        state.window_list = new_list_state;
+    }
+
+    fn selected_row(&self, state: &EndpointsSelectorState) -> Option<TreeRow> {
+        self.rows.get(*state.cursor.to_ref() as usize).cloned()
+    }
+
+    fn selected_endpoint(&self, state: &EndpointsSelectorState) -> Option<&PersistedEndpoint> {
+        match self.selected_row(state) {
+            Some(TreeRow::Endpoint { index, .. }) => self.items_list.get(index),
+            _ => None,
         }
+    }
+
+    /// Starts, updates or with `None` ends the search, the cursor goes to the top match
+    fn set_search(&mut self, search: Option<String>, state: &mut EndpointsSelectorState) {
+        state.searching.set(search.is_some());
+        state.query.set(search.clone().unwrap_or_default());
+        state.current_first_index.set(0);
+
+        self.search = search;
+        self.show_rows(0, state);
+    }
+
+    fn set_expanded(&mut self, path: String, expanded: bool, state: &mut EndpointsSelectorState) {
+        match expanded {
+            true => self.expanded.insert(path),
+            false => self.expanded.remove(&path),
+        };
+
+        let cursor = *state.cursor.to_ref() as usize;
+        self.show_rows(cursor, state);
+    }
 
-        let mut range_end = last_index;
-        let actual_last_index = self.items_list.len().saturating_sub(1);
-        if last_index > actual_last_index {
-            range_end = actual_last_index;
+    /// Opens the inline input under the tree with the current value to edit
+    fn open_input(
+        &mut self,
+        input: TreeInput,
+        state: &mut EndpointsSelectorState,
+        context: &mut anathema::prelude::Context<'_, EndpointsSelectorState>,
+    ) {
+        let (mode, label, value) = match &input {
+            TreeInput::None => return,
+            TreeInput::Move(name) => {
+                let folder = self
+                    .items_list
+                    .iter()
+                    .find(|endpoint| endpoint.name == *name)
+                    .map(|endpoint| endpoint.folder.clone())
+                    .unwrap_or_default();
+
+                ("move", format!("Move {name} to folder"), folder)
+            }
+            TreeInput::FolderHeaders(path) => {
+                let headers: Vec<_> = self
+                    .folders
+                    .iter()
+                    .filter(|folder| normalize_folder(&folder.path) == *path)
+                    .flat_map(|folder| folder.headers.clone())
+                    .collect();
+
+                (
+                    "headers",
+                    format!("{path} headers, one Name: value per line"),
+                    format_headers(&headers),
+                )
+            }
+        };
+
+        state.input_mode.set(mode.to_string());
+        state.input_label.set(label);
+        self.input_value = value.clone();
+        self.input = input;
+
+        // NOTE: Headers are edited in a textarea, which takes its text wrapped in a message
+        let (target, message) = match self.input {
+            TreeInput::FolderHeaders(_) => (
+                "folder_headers_input",
+                serde_json::to_string(&TextAreaMessages::SetInput(value)).unwrap_or_default(),
+            ),
+            _ => ("endpoints_selector_input", value),
+        };
+
+        if let Ok(ids) = self.component_ids.try_borrow() {
+            let _ = send_message(target, message, &ids, context.emitter);
         }
 
-        let display_items = &self.items_list[first_index..=range_end];
-        let mut new_items_list: Vec<Endpoint> = vec![];
-        display_items.iter().for_each(|display_endpoint| {
-            new_items_list.push(display_endpoint.into());
-        });
-
-        loop {
-            if state.window_list.len() > 0 {
-                state.window_list.pop_front();
-            } else {
-                break;
+        context.set_focus("id", self.input_field());
+    }
+
+    /// The id of the input open under the tree, headers take several lines
+    fn input_field(&self) -> &'static str {
+        match self.input {
+            TreeInput::FolderHeaders(_) => "folder_headers_input_field",
+            _ => "endpoints_selector_input_field",
+        }
+    }
+
+    fn close_input(&mut self, state: &mut EndpointsSelectorState) {
+        self.input = TreeInput::None;
+        self.input_value = String::new();
+        state.input_mode.set(String::new());
+        state.input_label.set(String::new());
+    }
+
+    fn submit_input(
+        &mut self,
+        state: &mut EndpointsSelectorState,
+        context: &mut anathema::prelude::Context<'_, EndpointsSelectorState>,
+    ) {
+        let edit = match &self.input {
+            TreeInput::None => return,
+            TreeInput::Move(endpoint) => EndpointsTreeEdit::Move {
+                endpoint: endpoint.clone(),
+                folder: self.input_value.clone(),
+            },
+            TreeInput::FolderHeaders(path) => EndpointsTreeEdit::FolderHeaders {
+                path: path.clone(),
+                headers: self.input_value.clone(),
+            },
+        };
+
+        // NOTE: Show where a moved endpoint went
+        if let EndpointsTreeEdit::Move { folder, .. } = &edit {
+            self.expanded.extend(folder_ancestors(folder));
+        }
+
+        self.close_input(state);
+
+        if let Ok(edit) = serde_json::to_string(&edit) {
+            state.selected_item.set(edit);
+            context.publish("endpoints_selector__edit", |state| &state.selected_item);
+        }
+    }
+
+    /// Puts the endpoint under the cursor in `selected_item` to be published
+    fn select_endpoint(&self, state: &mut EndpointsSelectorState) -> bool {
+        match self.selected_endpoint(state).map(serde_json::to_string) {
+            Some(Ok(endpoint_json)) => {
+                state.selected_item.set(endpoint_json);
+                true
             }
+            _ => false,
         }
+    }
+}
 
-        let mut new_list_state = List::<Endpoint>::empty();
-        new_items_list
-            .into_iter()
-            .enumerate()
-            .for_each(|(index, mut endpoint)| {
-                let visible_index = selected_index.saturating_sub(first_index);
-                if index == visible_index {
-                    endpoint.row_color = SELECTED_ROW_COLOR.to_string().into();
-                } else {
-                    endpoint.row_color = DEFAULT_ROW_COLOR.to_string().into();
-                }
+/// What a search matches an endpoint on, and shows for it
+fn endpoint_search_text(endpoint: &PersistedEndpoint) -> String {
+    format!("{} {}  {}", endpoint.method, endpoint.name, endpoint.url)
+}
 
-                new_list_state.push(endpoint);
-            });
+/// Sends the project's tree to the selector, `refresh` keeps the cursor where it was
+pub fn send_endpoints_list(
+    project: &PersistedProject,
+    refresh: bool,
+    component_ids: &Ref<'_, HashMap<String, ComponentId<String>>>,
+    emitter: &Emitter,
+) -> anyhow::Result<()> {
+    let endpoints = project.endpoints.clone();
+    let folders = project.folders.clone();
+
+    let message = match refresh {
+        true => EndpointsSelectorMessages::EndpointsUpdate(endpoints, folders),
+        false => EndpointsSelectorMessages::EndpointsList(endpoints, folders),
+    };
+
+    send_message(
+        "endpoints_selector_window",
+        serde_json::to_string(&message)?,
+        component_ids,
+        emitter,
+    )
+}
 
-        state.window_list = new_list_state;
+/// Applies a change made in the tree to the project and saves it
+fn apply_tree_edit(edit: EndpointsTreeEdit, state: &mut DashboardState) -> PersistedProject {
+    let mut project: PersistedProject = state.project.to_ref().deref().into();
+
+    match edit {
+        EndpointsTreeEdit::Move { endpoint, folder } => {
+            let folder = normalize_folder(&folder);
+
+            project
+                .endpoints
+                .iter_mut()
+                .filter(|persisted_endpoint| persisted_endpoint.name == endpoint)
+                .for_each(|persisted_endpoint| persisted_endpoint.folder = folder.clone());
+
+            // NOTE: Saving the open endpoint would otherwise move it back
+            let mut current_endpoint = state.endpoint.to_mut();
+            if *current_endpoint.name.to_ref() == endpoint {
+                current_endpoint.folder.set(folder);
+            }
+        }
+
+        EndpointsTreeEdit::FolderHeaders { path, headers } => {
+            set_folder_headers(&mut project.folders, &path, parse_headers(&headers));
+        }
     }
+
+    project
 }
 
 impl DashboardMessageHandler for EndpointsSelector {
@@ -213,23 +508,28 @@ impl DashboardMessageHandler for EndpointsSelector {
                 context.set_focus("id", "app");
             }
 
-            "endpoints_selector__selection" => {
-                state.floating_window.set(FloatingWindow::None);
-                context.set_focus("id", "app");
-
+            "endpoints_selector__edit" => {
                 let value = &*value.to_common_str();
-                let endpoint = serde_json::from_str::<PersistedEndpoint>(value);
+                let Ok(edit) = serde_json::from_str::<EndpointsTreeEdit>(value) else {
+                    return;
+                };
 
-                match endpoint {
-                    Ok(endpoint) => {
-                        state.endpoint.set((&endpoint).into());
+                let project = apply_tree_edit(edit, state);
+
+                if let Err(error) = save_project(project.clone()) {
+                    let dashboard_message = DashboardMessages::ShowError(error.to_string());
+                    if let Ok(message) = serde_json::to_string(&dashboard_message) {
+                        let _ = send_message("dashboard", message, &component_ids, context.emitter);
                     }
-                    Err(_) => todo!(),
+                    return;
                 }
+
+                state.project.set((&project).into());
+                let _ = send_endpoints_list(&project, true, &component_ids, context.emitter);
             }
 
             "endpoints_selector__delete" => {
-                state.floating_window.set(FloatingWindow::ConfirmProject);
+                state.floating_window.set(FloatingWindow::ConfirmAction);
 
                 let value = &*value.to_common_str();
                 let endpoint = serde_json::from_str::<PersistedEndpoint>(value);
@@ -276,6 +576,24 @@ impl Component for EndpointsSelector {
         self.update_app_theme(state);
     }
 
+    fn receive(
+        &mut self,
+        ident: &str,
+        value: anathema::state::CommonVal<'_>,
+        _: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        match ident {
+            "tree_input_update" => self.input_value = value.to_string(),
+            "tree_input_escape" => context.set_focus("id", "endpoints_selector_window"),
+            "headers_input_focus" if !value.to_bool() => {
+                context.set_focus("id", "endpoints_selector_window")
+            }
+            _ => {}
+        }
+    }
+
     fn on_key(
         &mut self,
         event: anathema::component::KeyEvent,
@@ -283,62 +601,105 @@ impl Component for EndpointsSelector {
         _: anathema::widgets::Elements<'_, '_>,
         mut context: anathema::prelude::Context<'_, Self::State>,
     ) {
+        // NOTE: While the input is open Enter submits it and Esc discards it
+        if !matches!(self.input, TreeInput::None) {
+            match event.code {
+                KeyCode::Enter => self.submit_input(state, &mut context),
+                KeyCode::Esc => self.close_input(state),
+                _ => context.set_focus("id", self.input_field()),
+            }
+
+            return;
+        }
+
+        // NOTE: Typing goes to the search, the list keys that aren't characters still work
+        if let Some(query) = &self.search {
+            let mut query = query.clone();
+
+            match event.code {
+                KeyCode::Char(char) => {
+                    query.push(char);
+                    return self.set_search(Some(query), state);
+                }
+                KeyCode::Backspace => {
+                    query.pop();
+                    return self.set_search(Some(query), state);
+                }
+                KeyCode::Esc => return self.set_search(None, state),
+                _ => {}
+            }
+        }
+
+        let selected_row = self.selected_row(state);
+
         match event.code {
-            anathema::component::KeyCode::Char(char) => match char {
-                'j' => self.move_cursor_down(state),
-                'k' => self.move_cursor_up(state),
-                'd' => {
-                    let selected_index = *state.cursor.to_ref() as usize;
-                    let endpoint = self.items_list.get(selected_index);
-
-                    match endpoint {
-                        Some(endpoint) => match serde_json::to_string(endpoint) {
-                            Ok(endpoint_json) => {
-                                state.selected_item.set(endpoint_json);
-                                context.publish("endpoints_selector__delete", |state| {
-                                    &state.selected_item
-                                })
-                            }
+            KeyCode::Char(char) => {
+                let binding = KeyBinding {
+                    key: char,
+                    ctrl: event.ctrl,
+                };
+                let action = self.keymap.action(KeyContext::EndpointsSelector, binding);
+
+                match (action, selected_row) {
+                    (Some(Action::CursorDown), _) => self.move_cursor_down(state),
+                    (Some(Action::CursorUp), _) => self.move_cursor_up(state),
+                    (Some(Action::Search), _) => self.set_search(Some(String::new()), state),
+
+                    (Some(Action::ExpandFolder), Some(TreeRow::Folder { path, .. })) => {
+                        self.set_expanded(path, true, state)
+                    }
 
-                            Err(_) => {
-                                context.publish("endpoints_selector__cancel", |state| &state.cursor)
-                            }
-                        },
-                        None => {
-                            context.publish("endpoints_selector__cancel", |state| &state.cursor)
-                        }
+                    (Some(Action::EditFolderHeaders), Some(TreeRow::Folder { path, .. })) => {
+                        self.open_input(TreeInput::FolderHeaders(path), state, &mut context)
+                    }
+
+                    (Some(Action::MoveEndpoint), Some(TreeRow::Endpoint { index, .. })) => {
+                        let name = self.items_list[index].name.clone();
+                        self.open_input(TreeInput::Move(name), state, &mut context)
                     }
+
+                    (Some(Action::DeleteEndpoint), Some(TreeRow::Endpoint { .. }))
+                        if self.select_endpoint(state) =>
+                    {
+                        context.publish("endpoints_selector__delete", |state| &state.selected_item)
+                    }
+
+                    _ => {}
                 }
-                _ => {}
-            },
+            }
+
+            KeyCode::Up => self.move_cursor_up(state),
+            KeyCode::Down => self.move_cursor_down(state),
 
-            anathema::component::KeyCode::Up => self.move_cursor_up(state),
-            anathema::component::KeyCode::Down => self.move_cursor_down(state),
+            KeyCode::Right => {
+                if let Some(TreeRow::Folder { path, .. }) = selected_row {
+                    self.set_expanded(path, true, state);
+                }
+            }
 
-            anathema::component::KeyCode::Esc => {
+            KeyCode::Left => {
+                if let Some(TreeRow::Folder { path, .. }) = selected_row {
+                    self.set_expanded(path, false, state);
+                }
+            }
+
+            KeyCode::Esc => {
                 // NOTE: This sends cursor to satisfy publish() but is not used
                 context.publish("endpoints_selector__cancel", |state| &state.cursor)
             }
 
-            anathema::component::KeyCode::Enter => {
-                let selected_index = *state.cursor.to_ref() as usize;
-                let endpoint = self.items_list.get(selected_index);
-
-                match endpoint {
-                    Some(endpoint) => match serde_json::to_string(endpoint) {
-                        Ok(endpoint_json) => {
-                            state.selected_item.set(endpoint_json);
-                            context.publish("endpoints_selector__selection", |state| {
-                                &state.selected_item
-                            });
-                        }
-                        Err(_) => {
-                            context.publish("endpoints_selector__cancel", |state| &state.cursor)
-                        }
-                    },
-                    None => context.publish("endpoints_selector__cancel", |state| &state.cursor),
+            KeyCode::Enter => match selected_row {
+                Some(TreeRow::Folder { path, expanded, .. }) => {
+                    self.set_expanded(path, !expanded, state)
                 }
-            }
+
+                _ => match self.select_endpoint(state) {
+                    true => context.publish("endpoints_selector__selection", |state| {
+                        &state.selected_item
+                    }),
+                    false => context.publish("endpoints_selector__cancel", |state| &state.cursor),
+                },
+            },
 
             _ => {}
         }
@@ -356,21 +717,19 @@ impl Component for EndpointsSelector {
 
         match endpoints_selector_message {
             Ok(deserialized_message) => match deserialized_message {
-                EndpointsSelectorMessages::EndpointsList(endpoints) => {
+                EndpointsSelectorMessages::EndpointsList(endpoints, folders) => {
                     self.items_list = endpoints;
+                    self.folders = folders;
+                    self.close_input(state);
+                    self.set_search(None, state);
+                }
 
-                    let current_last_index =
-                        min(*state.visible_rows.to_ref(), self.items_list.len() as u8)
-                            .saturating_sub(1);
-                    state.cursor.set(0);
-                    state.current_first_index.set(0);
-                    state.current_last_index.set(current_last_index);
-
-                    let first_index: usize = *state.current_first_index.to_ref() as usize;
-                    let last_index: usize = *state.current_last_index.to_ref() as usize;
-                    let selected_index = 0;
+                EndpointsSelectorMessages::EndpointsUpdate(endpoints, folders) => {
+                    self.items_list = endpoints;
+                    self.folders = folders;
 
-                    self.update_list(first_index, last_index, selected_index, state);
+                    let cursor = *state.cursor.to_ref() as usize;
+                    self.show_rows(cursor, state);
                 }
             },
 
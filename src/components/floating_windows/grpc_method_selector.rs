@@ -0,0 +1,314 @@
+use std::{
+    cell::RefCell,
+    cmp::{max, min},
+    collections::HashMap,
+    rc::Rc,
+};
+
+use anathema::{
+    component::{Component, ComponentId},
+    prelude::TuiBackend,
+    runtime::RuntimeBuilder,
+    state::{List, State, Value},
+    widgets::Elements,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{
+        dashboard::{DashboardMessageHandler, DashboardState, FloatingWindow},
+        send_message,
+        textarea::TextAreaMessages,
+    },
+    projects::{PersistedProject, DEFAULT_ROW_COLOR},
+    requests::grpc::{find_method, load_descriptor_pool, request_template},
+    theme::{get_app_theme, AppTheme},
+};
+
+pub const GRPC_METHOD_SELECTOR_TEMPLATE: &str =
+    "./src/components/floating_windows/templates/grpc_method_selector.aml";
+
+const SELECTED_ROW_COLOR: &str = "#FFFFFF";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum GrpcMethodSelectorMessages {
+    MethodsList(Vec<String>),
+}
+
+#[derive(Default, State)]
+pub struct GrpcMethodRow {
+    name: Value<String>,
+    row_color: Value<String>,
+}
+
+#[derive(Default, State)]
+pub struct GrpcMethodSelectorState {
+    cursor: Value<u8>,
+    current_first_index: Value<u8>,
+    current_last_index: Value<u8>,
+    visible_rows: Value<u8>,
+    window_list: Value<List<GrpcMethodRow>>,
+    selected_item: Value<String>,
+    app_theme: Value<AppTheme>,
+}
+
+impl GrpcMethodSelectorState {
+    pub fn new() -> Self {
+        GrpcMethodSelectorState {
+            cursor: 0.into(),
+            current_first_index: 0.into(),
+            current_last_index: 4.into(),
+            visible_rows: 5.into(),
+            window_list: List::empty(),
+            selected_item: "".to_string().into(),
+            app_theme: get_app_theme().into(),
+        }
+    }
+}
+
+pub struct GrpcMethodSelector {
+    #[allow(dead_code)]
+    component_ids: Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+    items_list: Vec<String>,
+}
+
+impl GrpcMethodSelector {
+    pub fn register(
+        ids: &Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+        builder: &mut RuntimeBuilder<TuiBackend, ()>,
+    ) -> anyhow::Result<()> {
+        let id = builder.register_component(
+            "grpc_method_selector_window",
+            GRPC_METHOD_SELECTOR_TEMPLATE,
+            GrpcMethodSelector {
+                component_ids: ids.clone(),
+                items_list: vec![],
+            },
+            GrpcMethodSelectorState::new(),
+        )?;
+
+        let mut ids_ref = ids.borrow_mut();
+        ids_ref.insert(String::from("grpc_method_selector_window"), id);
+
+        Ok(())
+    }
+
+    fn move_cursor_down(&self, state: &mut GrpcMethodSelectorState) {
+        let last_complete_list_index = self.items_list.len().saturating_sub(1);
+        let new_cursor = min(*state.cursor.to_ref() + 1, last_complete_list_index as u8);
+        state.cursor.set(new_cursor);
+
+        let mut first_index = *state.current_first_index.to_ref();
+        let mut last_index = *state.current_last_index.to_ref();
+
+        if new_cursor > last_index {
+            last_index = new_cursor;
+            first_index = new_cursor - (*state.visible_rows.to_ref() - 1);
+
+            state.current_first_index.set(first_index);
+            state.current_last_index.set(last_index);
+        }
+
+        self.update_list(
+            first_index.into(),
+            last_index.into(),
+            new_cursor.into(),
+            state,
+        );
+    }
+
+    fn move_cursor_up(&self, state: &mut GrpcMethodSelectorState) {
+        let new_cursor = max(state.cursor.to_ref().saturating_sub(1), 0);
+        state.cursor.set(new_cursor);
+
+        let mut first_index = *state.current_first_index.to_ref();
+        let mut last_index = *state.current_last_index.to_ref();
+
+        if new_cursor < first_index {
+            first_index = new_cursor;
+            last_index = new_cursor + (*state.visible_rows.to_ref() - 1);
+
+            state.current_first_index.set(first_index);
+            state.current_last_index.set(last_index);
+        }
+
+        self.update_list(
+            first_index.into(),
+            last_index.into(),
+            new_cursor.into(),
+            state,
+        );
+    }
+
+    fn update_list(
+        &self,
+        first_index: usize,
+        last_index: usize,
+        selected_index: usize,
+        state: &mut GrpcMethodSelectorState,
+    ) {
+        loop {
+            if state.window_list.len() > 0 {
+                state.window_list.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if self.items_list.is_empty() {
+            return;
+        }
+
+        let range_end = min(last_index, self.items_list.len().saturating_sub(1));
+        let visible_index = selected_index.saturating_sub(first_index);
+
+        self.items_list[first_index..=range_end]
+            .iter()
+            .enumerate()
+            .for_each(|(index, method)| {
+                let row_color = match index == visible_index {
+                    true => SELECTED_ROW_COLOR,
+                    false => DEFAULT_ROW_COLOR,
+                };
+
+                state.window_list.push_back(GrpcMethodRow {
+                    name: method.clone().into(),
+                    row_color: row_color.to_string().into(),
+                });
+            });
+    }
+}
+
+impl DashboardMessageHandler for GrpcMethodSelector {
+    fn handle_message(
+        value: anathema::state::CommonVal<'_>,
+        ident: impl Into<String>,
+        state: &mut DashboardState,
+        mut context: anathema::prelude::Context<'_, DashboardState>,
+        _: Elements<'_, '_>,
+        component_ids: std::cell::Ref<'_, HashMap<String, ComponentId<String>>>,
+    ) {
+        let event: String = ident.into();
+
+        match event.as_str() {
+            "grpc_method_selector__cancel" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+            }
+
+            "grpc_method_selector__selection" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+
+                let method_path = value.to_string();
+                state.endpoint.to_mut().grpc_method.set(method_path.clone());
+
+                // NOTE: Start an empty body off with the request message's fields
+                if !state.endpoint.to_ref().body.to_ref().trim().is_empty() {
+                    return;
+                }
+
+                let project: PersistedProject = (&*state.project.to_ref()).into();
+                let template = load_descriptor_pool(&project.proto_files)
+                    .and_then(|pool| find_method(&pool, &method_path))
+                    .and_then(|method| request_template(&method));
+
+                if let Ok(template) = template {
+                    state.endpoint.to_mut().body.set(template.clone());
+
+                    let message = TextAreaMessages::SetInput(template);
+                    if let Ok(message) = serde_json::to_string(&message) {
+                        let _ = send_message(
+                            "request_body_input",
+                            message,
+                            &component_ids,
+                            context.emitter,
+                        );
+                    }
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
+
+impl Component for GrpcMethodSelector {
+    type State = GrpcMethodSelectorState;
+    type Message = String;
+
+    fn accept_focus(&self) -> bool {
+        true
+    }
+
+    fn on_focus(
+        &mut self,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        _: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        state.app_theme.set(get_app_theme());
+    }
+
+    fn on_key(
+        &mut self,
+        event: anathema::component::KeyEvent,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        match event.code {
+            anathema::component::KeyCode::Char(char) => match char {
+                'j' => self.move_cursor_down(state),
+                'k' => self.move_cursor_up(state),
+                _ => {}
+            },
+
+            anathema::component::KeyCode::Up => self.move_cursor_up(state),
+            anathema::component::KeyCode::Down => self.move_cursor_down(state),
+
+            anathema::component::KeyCode::Esc => {
+                // NOTE: This sends cursor to satisfy publish() but is not used
+                context.publish("grpc_method_selector__cancel", |state| &state.cursor)
+            }
+
+            anathema::component::KeyCode::Enter => {
+                let selected_index = *state.cursor.to_ref() as usize;
+
+                match self.items_list.get(selected_index) {
+                    Some(method) => {
+                        state.selected_item.set(method.clone());
+                        context.publish("grpc_method_selector__selection", |state| {
+                            &state.selected_item
+                        });
+                    }
+                    None => context.publish("grpc_method_selector__cancel", |state| &state.cursor),
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    fn message(
+        &mut self,
+        message: Self::Message,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        _: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        if let Ok(GrpcMethodSelectorMessages::MethodsList(methods)) =
+            serde_json::from_str::<GrpcMethodSelectorMessages>(&message)
+        {
+            self.items_list = methods;
+
+            let current_last_index =
+                min(*state.visible_rows.to_ref(), self.items_list.len() as u8).saturating_sub(1);
+            state.cursor.set(0);
+            state.current_first_index.set(0);
+            state.current_last_index.set(current_last_index);
+
+            self.update_list(0, current_last_index as usize, 0, state);
+        }
+    }
+}
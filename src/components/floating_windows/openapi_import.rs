@@ -0,0 +1,184 @@
+use std::{cell::RefCell, collections::HashMap, fs, path::PathBuf, rc::Rc};
+
+use anathema::{
+    component::{Component, ComponentId},
+    prelude::TuiBackend,
+    runtime::RuntimeBuilder,
+    state::{State, Value},
+    widgets::Elements,
+};
+
+use crate::{
+    compatibility::{
+        openapi::import_openapi,
+        postman::{import_postman, is_postman_collection},
+    },
+    components::{
+        dashboard::{DashboardMessageHandler, DashboardMessages, DashboardState, FloatingWindow},
+        project_window::open_project,
+        send_message,
+    },
+    projects::{save_project, unique_project_name},
+    theme::{get_app_theme, AppTheme},
+};
+
+const TEMPLATE: &str = "./src/components/floating_windows/templates/openapi_import.aml";
+
+pub struct OpenApiImport {
+    #[allow(dead_code)]
+    component_ids: Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+}
+
+impl OpenApiImport {
+    pub fn register(
+        ids: &Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+        builder: &mut RuntimeBuilder<TuiBackend, ()>,
+    ) -> anyhow::Result<()> {
+        let id = builder.register_component(
+            "openapi_import",
+            TEMPLATE,
+            OpenApiImport {
+                component_ids: ids.clone(),
+            },
+            OpenApiImportState {
+                app_theme: get_app_theme().into(),
+                path: String::from("").into(),
+            },
+        )?;
+
+        let mut ids_ref = ids.borrow_mut();
+        ids_ref.insert(String::from("openapi_import"), id);
+
+        Ok(())
+    }
+}
+
+#[derive(State)]
+pub struct OpenApiImportState {
+    app_theme: Value<AppTheme>,
+    path: Value<String>,
+}
+
+impl DashboardMessageHandler for OpenApiImport {
+    fn handle_message(
+        value: anathema::state::CommonVal<'_>,
+        ident: impl Into<String>,
+        state: &mut DashboardState,
+        mut context: anathema::prelude::Context<'_, DashboardState>,
+        _: Elements<'_, '_>,
+        component_ids: std::cell::Ref<'_, HashMap<String, ComponentId<String>>>,
+    ) {
+        let event: String = ident.into();
+
+        match event.as_str() {
+            "openapi_import__submit" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+
+                let path = PathBuf::from(value.to_string().trim());
+
+                // NOTE: Postman collections are JSON too, so they're told apart by their schema
+                let is_postman = fs::read_to_string(&path)
+                    .map(|text| is_postman_collection(&text))
+                    .unwrap_or_default();
+                let (format, import) = match is_postman {
+                    true => ("Postman", import_postman(&path)),
+                    false => ("OpenAPI", import_openapi(&path)),
+                };
+
+                let result = import.and_then(|mut project| {
+                    project.name = unique_project_name(&project.name)?;
+                    save_project(project.clone())?;
+                    Ok(project)
+                });
+
+                let dashboard_message = match result {
+                    Ok(project) => {
+                        open_project(&project, state, &context, &component_ids);
+
+                        DashboardMessages::ShowSucces((
+                            format!("{format} Import"),
+                            format!(
+                                "Imported {} endpoints into {}",
+                                project.endpoints.len(),
+                                project.name
+                            ),
+                        ))
+                    }
+
+                    Err(error) => {
+                        DashboardMessages::ShowError(format!("{format} import failed: {error}"))
+                    }
+                };
+
+                if let Ok(message) = serde_json::to_string(&dashboard_message) {
+                    let _ = send_message("dashboard", message, &component_ids, context.emitter);
+                }
+            }
+
+            "openapi_import__cancel" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+            }
+
+            _ => {}
+        }
+    }
+}
+
+impl Component for OpenApiImport {
+    type State = OpenApiImportState;
+    type Message = String;
+
+    fn accept_focus(&self) -> bool {
+        true
+    }
+
+    fn on_focus(
+        &mut self,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        _: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        state.app_theme.set(get_app_theme());
+    }
+
+    fn receive(
+        &mut self,
+        ident: &str,
+        value: anathema::state::CommonVal<'_>,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        match ident {
+            "path_input_escape" => context.set_focus("id", "openapi_import"),
+            "path_input_update" => state.path.set(value.to_string()),
+            _ => {}
+        }
+    }
+
+    fn on_key(
+        &mut self,
+        key: anathema::component::KeyEvent,
+        _: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        match key.code {
+            anathema::component::KeyCode::Char(char) => match char {
+                'f' => context.set_focus("id", "openapi_path_input"),
+                's' => context.publish("openapi_import__submit", |state| &state.path),
+                'c' => context.publish("openapi_import__cancel", |state| &state.path),
+
+                _ => {}
+            },
+
+            anathema::component::KeyCode::Esc => {
+                context.publish("openapi_import__cancel", |state| &state.path)
+            }
+
+            _ => {}
+        }
+    }
+}
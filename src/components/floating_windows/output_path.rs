@@ -0,0 +1,283 @@
+use std::{
+    cell::{Ref, RefCell},
+    collections::HashMap,
+    path::Path,
+    rc::Rc,
+};
+
+use anathema::{
+    component::{Component, ComponentId, KeyCode},
+    prelude::{Context, TuiBackend},
+    runtime::RuntimeBuilder,
+    state::{CommonVal, State, Value},
+    widgets::Elements,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{
+        dashboard::{DashboardMessageHandler, DashboardMessages, DashboardState, FloatingWindow},
+        send_message,
+    },
+    keymap::{get_keymap, KeyBinding, KeyContext, Keymap},
+    messages::confirm_overwrite::ConfirmOverwrite,
+    output::{
+        complete_directory, default_output_path, resolve_output_path, write_output, PendingOutput,
+    },
+    palette::Action,
+    theme::{get_app_theme, AppTheme},
+};
+
+const TEMPLATE: &str = "./src/components/floating_windows/templates/output_path.aml";
+
+const COMPLETION_HINT: &str = "(Tab) completes directories";
+
+/// Asks where a generated file should be written, starting from the project's
+/// remembered output directory
+#[derive(Default)]
+pub struct OutputPathWindow {
+    keymap: Keymap,
+    output: Option<PendingOutput>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OutputPathSubmission {
+    pub path: String,
+    pub output: PendingOutput,
+}
+
+impl OutputPathWindow {
+    pub fn register(
+        ids: &Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+        builder: &mut RuntimeBuilder<TuiBackend, ()>,
+    ) -> anyhow::Result<()> {
+        let id = builder.register_component(
+            "output_path_window",
+            TEMPLATE,
+            OutputPathWindow {
+                keymap: get_keymap(),
+                output: None,
+            },
+            OutputPathWindowState::new(),
+        )?;
+
+        let mut ids_ref = ids.borrow_mut();
+        ids_ref.insert(String::from("output_path_window"), id);
+
+        Ok(())
+    }
+
+    fn complete(&self, state: &mut OutputPathWindowState) {
+        let completion = complete_directory(&state.path.to_ref());
+        state.path.set(completion.text);
+
+        match completion.candidates.is_empty() {
+            true => state.hint.set(COMPLETION_HINT.to_string()),
+            false => state.hint.set(completion.candidates.join("  ")),
+        }
+    }
+}
+
+#[derive(State)]
+pub struct OutputPathWindowState {
+    app_theme: Value<AppTheme>,
+    title: Value<String>,
+    path: Value<String>,
+    hint: Value<String>,
+    submission: Value<String>,
+}
+
+impl OutputPathWindowState {
+    pub fn new() -> Self {
+        OutputPathWindowState {
+            app_theme: get_app_theme().into(),
+            title: String::from("").into(),
+            path: String::from("").into(),
+            hint: COMPLETION_HINT.to_string().into(),
+            submission: String::from("").into(),
+        }
+    }
+}
+
+/// Opens the path prompt for a generated file
+pub fn prompt_output_path(
+    output: PendingOutput,
+    state: &mut DashboardState,
+    context: &mut Context<'_, DashboardState>,
+    component_ids: &Ref<'_, HashMap<String, ComponentId<String>>>,
+) {
+    state.floating_window.set(FloatingWindow::OutputPath);
+    context.set_focus("id", "output_path_window");
+
+    if let Ok(message) = serde_json::to_string(&output) {
+        let _ = send_message(
+            "output_path_window",
+            message,
+            component_ids,
+            context.emitter,
+        );
+    }
+}
+
+/// Writes the output and reports the path it went to
+pub fn save_output(
+    path: &Path,
+    output: &PendingOutput,
+    context: &Context<'_, DashboardState>,
+    component_ids: &Ref<'_, HashMap<String, ComponentId<String>>>,
+) {
+    let dashboard_message = match write_output(path, output) {
+        Ok(_) => DashboardMessages::ShowSucces((
+            output.title.clone(),
+            format!("Written to {}", path.display()),
+        )),
+        Err(error) => {
+            DashboardMessages::ShowError(format!("Could not write {}: {error}", path.display()))
+        }
+    };
+
+    if let Ok(message) = serde_json::to_string(&dashboard_message) {
+        let _ = send_message("dashboard", message, component_ids, context.emitter);
+    }
+}
+
+impl DashboardMessageHandler for OutputPathWindow {
+    fn handle_message(
+        value: CommonVal<'_>,
+        ident: impl Into<String>,
+        state: &mut DashboardState,
+        mut context: Context<'_, DashboardState>,
+        _: Elements<'_, '_>,
+        component_ids: Ref<'_, HashMap<String, ComponentId<String>>>,
+    ) {
+        let event: String = ident.into();
+
+        match event.as_str() {
+            "output_path__submit" => {
+                let value = &*value.to_common_str();
+                let Ok(submission) = serde_json::from_str::<OutputPathSubmission>(value) else {
+                    return;
+                };
+
+                let path = resolve_output_path(&submission.path, &submission.output.file.file_name);
+
+                if path.exists() {
+                    state.floating_window.set(FloatingWindow::ConfirmAction);
+                    context.set_focus("id", "confirm_action_window");
+
+                    let confirm_message = ConfirmOverwrite {
+                        title: format!("Overwrite {}", submission.output.file.file_name),
+                        message: format!("{} already exists, replace it?", path.display()),
+                        path,
+                        output: submission.output,
+                    };
+
+                    if let Ok(message) = serde_json::to_string(&confirm_message) {
+                        let _ = send_message(
+                            "confirm_action_window",
+                            message,
+                            &component_ids,
+                            context.emitter,
+                        );
+                    }
+
+                    return;
+                }
+
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+
+                save_output(&path, &submission.output, &context, &component_ids);
+            }
+
+            "output_path__cancel" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+            }
+
+            _ => {}
+        }
+    }
+}
+
+impl Component for OutputPathWindow {
+    type State = OutputPathWindowState;
+    type Message = String;
+
+    fn accept_focus(&self) -> bool {
+        true
+    }
+
+    fn on_focus(
+        &mut self,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        _: Context<'_, Self::State>,
+    ) {
+        state.app_theme.set(get_app_theme());
+    }
+
+    fn message(
+        &mut self,
+        message: Self::Message,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        _: Context<'_, Self::State>,
+    ) {
+        if let Ok(output) = serde_json::from_str::<PendingOutput>(&message) {
+            state.title.set(output.title.clone());
+            state.path.set(default_output_path(&output));
+            state.hint.set(COMPLETION_HINT.to_string());
+
+            self.output = Some(output);
+        }
+    }
+
+    fn on_key(
+        &mut self,
+        key: anathema::component::KeyEvent,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: Context<'_, Self::State>,
+    ) {
+        match key.code {
+            // NOTE: Typed characters go into the path, only ctrl bindings can run actions
+            KeyCode::Char(char) if key.ctrl => {
+                let binding = KeyBinding {
+                    key: char,
+                    ctrl: true,
+                };
+                if let Some(Action::CompletePath) =
+                    self.keymap.action(KeyContext::OutputPath, binding)
+                {
+                    self.complete(state);
+                }
+            }
+            KeyCode::Char(char) => state.path.to_mut().push(char),
+            KeyCode::Backspace => {
+                state.path.to_mut().pop();
+            }
+            KeyCode::Tab => self.complete(state),
+
+            KeyCode::Enter => {
+                let Some(output) = self.output.clone() else {
+                    return;
+                };
+
+                let submission = OutputPathSubmission {
+                    path: state.path.to_ref().to_string(),
+                    output,
+                };
+
+                if let Ok(submission) = serde_json::to_string(&submission) {
+                    state.submission.set(submission);
+                    context.publish("output_path__submit", |state| &state.submission);
+                }
+            }
+
+            KeyCode::Esc => context.publish("output_path__cancel", |state| &state.path),
+
+            _ => {}
+        }
+    }
+}
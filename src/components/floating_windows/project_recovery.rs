@@ -0,0 +1,198 @@
+use std::{cell::RefCell, cmp::min, collections::HashMap, rc::Rc};
+
+use anathema::{
+    component::{Component, ComponentId, KeyCode},
+    prelude::{Context, TuiBackend},
+    runtime::RuntimeBuilder,
+    state::{List, State, Value},
+    widgets::Elements,
+};
+
+use crate::{
+    components::{
+        dashboard::{DashboardMessageHandler, DashboardState, FloatingWindow},
+        list_row::ListRow,
+    },
+    projects::{load_projects, restore_backup, ProjectFileError},
+    theme::{get_app_theme, AppTheme},
+};
+
+const TEMPLATE: &str = "./src/components/floating_windows/templates/project_recovery.aml";
+
+// TODO: Fix the default project row color to the correct gray
+const DEFAULT_ROW_COLOR: &str = "#333333";
+const SELECTED_ROW_COLOR: &str = "#FFFFFF";
+
+/// Lists the project files that failed to parse, with their error, so they can
+/// be restored from their backup
+#[derive(Default)]
+pub struct ProjectRecoveryWindow {
+    files: Vec<ProjectFileError>,
+}
+
+impl ProjectRecoveryWindow {
+    pub fn register(
+        ids: &Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+        builder: &mut RuntimeBuilder<TuiBackend, ()>,
+    ) -> anyhow::Result<()> {
+        let id = builder.register_component(
+            "project_recovery_window",
+            TEMPLATE,
+            ProjectRecoveryWindow::default(),
+            ProjectRecoveryState::new(),
+        )?;
+
+        let mut ids_ref = ids.borrow_mut();
+        ids_ref.insert(String::from("project_recovery_window"), id);
+
+        Ok(())
+    }
+
+    fn load_files(&mut self, state: &mut ProjectRecoveryState) {
+        match load_projects() {
+            Ok((_, files)) => self.files = files,
+            Err(error) => state.status.set(error.to_string()),
+        }
+    }
+
+    fn show_files(&self, cursor: usize, state: &mut ProjectRecoveryState) {
+        let cursor = min(cursor, self.files.len().saturating_sub(1));
+
+        let mut window_list = List::<ListRow>::empty();
+        self.files.iter().enumerate().for_each(|(index, file)| {
+            let row_color = match index == cursor {
+                true => SELECTED_ROW_COLOR,
+                false => DEFAULT_ROW_COLOR,
+            };
+
+            let name = file
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            let detail = match file.has_backup {
+                true => "backup",
+                false => "",
+            };
+
+            window_list.push(ListRow::new(&name, &[], row_color).with_detail(detail));
+        });
+
+        let error = match self.files.get(cursor) {
+            Some(file) => format!("{}\n{}", file.path.display(), file.error),
+            None => String::from("Every project file was loaded"),
+        };
+
+        state.cursor.set(cursor as u8);
+        state.window_list = window_list;
+        state.error.set(error);
+    }
+
+    fn restore(&mut self, cursor: usize, state: &mut ProjectRecoveryState) {
+        let Some(file) = self.files.get(cursor) else {
+            return;
+        };
+
+        if !file.has_backup {
+            state
+                .status
+                .set(String::from("There is no backup of this file"));
+            return;
+        }
+
+        let status = match restore_backup(&file.path) {
+            Ok(project) => format!("Restored {} from its backup", project.name),
+            Err(error) => error.to_string(),
+        };
+        state.status.set(status);
+
+        self.load_files(state);
+        self.show_files(cursor, state);
+    }
+}
+
+#[derive(State)]
+pub struct ProjectRecoveryState {
+    app_theme: Value<AppTheme>,
+    cursor: Value<u8>,
+    window_list: Value<List<ListRow>>,
+    error: Value<String>,
+    status: Value<String>,
+}
+
+impl ProjectRecoveryState {
+    pub fn new() -> Self {
+        ProjectRecoveryState {
+            app_theme: get_app_theme().into(),
+            cursor: 0.into(),
+            window_list: List::empty(),
+            error: String::from("").into(),
+            status: String::from("").into(),
+        }
+    }
+}
+
+impl DashboardMessageHandler for ProjectRecoveryWindow {
+    fn handle_message(
+        _: anathema::state::CommonVal<'_>,
+        ident: impl Into<String>,
+        state: &mut DashboardState,
+        mut context: Context<'_, DashboardState>,
+        _: Elements<'_, '_>,
+        _: std::cell::Ref<'_, HashMap<String, ComponentId<String>>>,
+    ) {
+        let event: String = ident.into();
+
+        #[allow(clippy::single_match)]
+        match event.as_str() {
+            "project_recovery__cancel" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+            }
+
+            _ => {}
+        }
+    }
+}
+
+impl Component for ProjectRecoveryWindow {
+    type State = ProjectRecoveryState;
+    type Message = String;
+
+    fn accept_focus(&self) -> bool {
+        true
+    }
+
+    fn on_focus(
+        &mut self,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        _: Context<'_, Self::State>,
+    ) {
+        state.app_theme.set(get_app_theme());
+        state.status.set(String::new());
+
+        self.load_files(state);
+        self.show_files(0, state);
+    }
+
+    fn on_key(
+        &mut self,
+        key: anathema::component::KeyEvent,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: Context<'_, Self::State>,
+    ) {
+        let cursor = *state.cursor.to_ref() as usize;
+
+        match key.code {
+            KeyCode::Down => self.show_files(cursor + 1, state),
+            KeyCode::Up => self.show_files(cursor.saturating_sub(1), state),
+            KeyCode::Char('r') => self.restore(cursor, state),
+            KeyCode::Esc => context.publish("project_recovery__cancel", |state| &state.status),
+
+            _ => {}
+        }
+    }
+}
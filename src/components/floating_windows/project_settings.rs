@@ -0,0 +1,303 @@
+use std::{
+    cell::{Ref, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+
+use anathema::{
+    component::{self, Component, ComponentId, KeyCode},
+    prelude::TuiBackend,
+    runtime::RuntimeBuilder,
+    state::{List, State, Value},
+    widgets::Elements,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{
+        dashboard::{DashboardMessageHandler, DashboardState, FloatingWindow},
+        send_message,
+        textarea::TextAreaMessages,
+    },
+    projects::{TlsSettings, Variable, VariableState},
+    requests::variables::{format_variables, parse_variables},
+    theme::{get_app_theme, AppTheme},
+};
+
+const TEMPLATE: &str = "./src/components/floating_windows/templates/project_settings.aml";
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ProjectSettings {
+    pub tls: TlsSettings,
+
+    #[serde(default)]
+    pub proto_files: Vec<String>,
+
+    #[serde(default)]
+    pub variables: Vec<Variable>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ProjectSettingsMessages {
+    Settings(ProjectSettings),
+}
+
+pub struct ProjectSettingsWindow {
+    #[allow(dead_code)]
+    component_ids: Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+    settings: ProjectSettings,
+}
+
+impl ProjectSettingsWindow {
+    pub fn register(
+        ids: &Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+        builder: &mut RuntimeBuilder<TuiBackend, ()>,
+    ) -> anyhow::Result<()> {
+        let id = builder.register_component(
+            "project_settings_window",
+            TEMPLATE,
+            ProjectSettingsWindow {
+                component_ids: ids.clone(),
+                settings: ProjectSettings::default(),
+            },
+            ProjectSettingsState::new(),
+        )?;
+
+        let mut ids_ref = ids.borrow_mut();
+        ids_ref.insert(String::from("project_settings_window"), id);
+
+        Ok(())
+    }
+
+    fn update_app_theme(&self, state: &mut ProjectSettingsState) {
+        let app_theme = get_app_theme();
+        state.app_theme.set(app_theme);
+    }
+
+    fn update_labels(&self, state: &mut ProjectSettingsState) {
+        let insecure = match self.settings.tls.insecure {
+            true => "on",
+            false => "off",
+        };
+
+        state.insecure.set(insecure.to_string());
+    }
+
+    fn set_inputs(&self, context: &anathema::prelude::Context<'_, ProjectSettingsState>) {
+        let Ok(ids) = self.component_ids.try_borrow() else {
+            return;
+        };
+
+        let tls = &self.settings.tls;
+        let inputs = [
+            ("tls_ca_certificates_input", tls.ca_certificates.join(", ")),
+            (
+                "tls_client_certificate_input",
+                tls.client_certificate.clone().unwrap_or_default(),
+            ),
+            (
+                "tls_client_key_input",
+                tls.client_key.clone().unwrap_or_default(),
+            ),
+            ("proto_files_input", self.settings.proto_files.join(", ")),
+        ];
+
+        for (input, value) in inputs {
+            let _ = send_message(input, value, &ids, context.emitter);
+        }
+
+        let variables = TextAreaMessages::SetInput(format_variables(&self.settings.variables));
+        if let Ok(message) = serde_json::to_string(&variables) {
+            let _ = send_message("project_variables_input", message, &ids, context.emitter);
+        }
+    }
+}
+
+fn optional_path(value: &str) -> Option<String> {
+    let path = value.trim();
+
+    match path.is_empty() {
+        true => None,
+        false => Some(path.to_string()),
+    }
+}
+
+#[derive(Default, State)]
+pub struct ProjectSettingsState {
+    insecure: Value<String>,
+    settings: Value<String>,
+    app_theme: Value<AppTheme>,
+}
+
+impl ProjectSettingsState {
+    pub fn new() -> Self {
+        let app_theme = get_app_theme();
+
+        ProjectSettingsState {
+            insecure: "off".to_string().into(),
+            settings: "".to_string().into(),
+            app_theme: app_theme.into(),
+        }
+    }
+}
+
+impl Component for ProjectSettingsWindow {
+    type State = ProjectSettingsState;
+    type Message = String;
+
+    fn accept_focus(&self) -> bool {
+        true
+    }
+
+    fn on_focus(
+        &mut self,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        _: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        self.update_app_theme(state);
+    }
+
+    fn receive(
+        &mut self,
+        ident: &str,
+        value: component::CommonVal<'_>,
+        _: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        // NOTE: Leaving the variables comes back to the window instead of the dashboard
+        if ident == "variables_input_focus" && !value.to_bool() {
+            context.set_focus("id", "project_settings_window");
+            return;
+        }
+
+        let value = value.to_string();
+
+        match ident {
+            "tls_ca_certificates_update" => {
+                self.settings.tls.ca_certificates = value
+                    .split(',')
+                    .filter_map(optional_path)
+                    .collect::<Vec<String>>();
+            }
+            "tls_client_certificate_update" => {
+                self.settings.tls.client_certificate = optional_path(&value);
+            }
+            "tls_client_key_update" => self.settings.tls.client_key = optional_path(&value),
+            "project_variables_update" => self.settings.variables = parse_variables(&value),
+            "proto_files_update" => {
+                self.settings.proto_files = value
+                    .split(',')
+                    .filter_map(optional_path)
+                    .collect::<Vec<String>>();
+            }
+            "settings_input_escape" => context.set_focus("id", "project_settings_window"),
+            _ => {}
+        }
+    }
+
+    fn on_key(
+        &mut self,
+        key: component::KeyEvent,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        match key.code {
+            KeyCode::Char(char) => match char {
+                'a' => context.set_focus("id", "tls_ca_certificates"),
+                'r' => context.set_focus("id", "tls_client_certificate"),
+                'k' => context.set_focus("id", "tls_client_key"),
+                'f' => context.set_focus("id", "proto_files"),
+                'v' => context.set_focus("id", "project_variables"),
+                'i' => {
+                    self.settings.tls.insecure = !self.settings.tls.insecure;
+                    self.update_labels(state);
+                }
+
+                's' => {
+                    if let Ok(settings) = serde_json::to_string(&self.settings) {
+                        state.settings.set(settings);
+                        context.publish("project_settings__submit", |state| &state.settings);
+                    }
+                }
+
+                'c' => context.publish("project_settings__cancel", |state| &state.settings),
+
+                _ => {}
+            },
+
+            KeyCode::Esc => context.publish("project_settings__cancel", |state| &state.settings),
+
+            _ => {}
+        }
+    }
+
+    fn message(
+        &mut self,
+        message: Self::Message,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        context: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        if let Ok(settings_message) = serde_json::from_str::<ProjectSettingsMessages>(&message) {
+            match settings_message {
+                ProjectSettingsMessages::Settings(settings) => {
+                    self.settings = settings;
+                    self.update_labels(state);
+                    self.set_inputs(&context);
+                }
+            }
+        }
+    }
+}
+
+impl DashboardMessageHandler for ProjectSettingsWindow {
+    fn handle_message(
+        value: component::CommonVal<'_>,
+        ident: impl Into<String>,
+        state: &mut DashboardState,
+        mut context: anathema::prelude::Context<'_, DashboardState>,
+        _: Elements<'_, '_>,
+        _: Ref<'_, HashMap<String, ComponentId<String>>>,
+    ) {
+        let event: String = ident.into();
+
+        match event.as_str() {
+            "project_settings__submit" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+
+                let value = &*value.to_common_str();
+                if let Ok(settings) = serde_json::from_str::<ProjectSettings>(value) {
+                    let mut project = state.project.to_mut();
+                    project.proto_files = List::from_iter(settings.proto_files);
+                    project.variables =
+                        List::from_iter(settings.variables.into_iter().map(|variable| {
+                            VariableState {
+                                name: variable.name.into(),
+                                value: variable.value.into(),
+                            }
+                        }));
+
+                    let mut tls = project.tls.to_mut();
+
+                    tls.ca_certificates = List::from_iter(settings.tls.ca_certificates);
+                    tls.client_certificate
+                        .set(settings.tls.client_certificate.unwrap_or_default());
+                    tls.client_key
+                        .set(settings.tls.client_key.unwrap_or_default());
+                    tls.insecure.set(settings.tls.insecure);
+                }
+            }
+
+            "project_settings__cancel" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+            }
+
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use std::{
+    cell::{Ref, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+
+use anathema::{
+    component::{self, Component, ComponentId, KeyCode},
+    prelude::TuiBackend,
+    runtime::RuntimeBuilder,
+    state::{State, Value},
+    widgets::Elements,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{
+        dashboard::{DashboardMessageHandler, DashboardState, FloatingWindow},
+        send_message,
+    },
+    options::ProxySettings,
+    projects::ProxySettingsState,
+    theme::{get_app_theme, AppTheme},
+};
+
+const TEMPLATE: &str = "./src/components/floating_windows/templates/proxy_settings.aml";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ProxySettingsMessages {
+    /// Settings to edit and the label for what they apply to, the global
+    /// options or the current project
+    Settings(String, ProxySettings),
+}
+
+pub struct ProxySettingsWindow {
+    component_ids: Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+    settings: ProxySettings,
+}
+
+impl ProxySettingsWindow {
+    pub fn register(
+        ids: &Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+        builder: &mut RuntimeBuilder<TuiBackend, ()>,
+    ) -> anyhow::Result<()> {
+        let id = builder.register_component(
+            "proxy_settings_window",
+            TEMPLATE,
+            ProxySettingsWindow {
+                component_ids: ids.clone(),
+                settings: ProxySettings::default(),
+            },
+            ProxySettingsWindowState::new(),
+        )?;
+
+        let mut ids_ref = ids.borrow_mut();
+        ids_ref.insert(String::from("proxy_settings_window"), id);
+
+        Ok(())
+    }
+
+    fn update_app_theme(&self, state: &mut ProxySettingsWindowState) {
+        let app_theme = get_app_theme();
+        state.app_theme.set(app_theme);
+    }
+
+    fn set_inputs(&self, context: &anathema::prelude::Context<'_, ProxySettingsWindowState>) {
+        let Ok(ids) = self.component_ids.try_borrow() else {
+            return;
+        };
+
+        let proxy = &self.settings;
+        let inputs = [
+            ("proxy_url_input", proxy.url.clone()),
+            (
+                "proxy_username_input",
+                proxy.username.clone().unwrap_or_default(),
+            ),
+            (
+                "proxy_password_input",
+                proxy.password.clone().unwrap_or_default(),
+            ),
+            ("proxy_no_proxy_input", proxy.no_proxy.join(", ")),
+        ];
+
+        for (input, value) in inputs {
+            let _ = send_message(input, value, &ids, context.emitter);
+        }
+    }
+}
+
+fn optional_value(value: &str) -> Option<String> {
+    let value = value.trim();
+
+    match value.is_empty() {
+        true => None,
+        false => Some(value.to_string()),
+    }
+}
+
+/// Turns the submitted settings into an override, an empty proxy URL means
+/// there is no proxy configured at this level
+pub fn proxy_override(settings: ProxySettings) -> Option<ProxySettings> {
+    match settings.url.trim().is_empty() {
+        true => None,
+        false => Some(settings),
+    }
+}
+
+#[derive(Default, State)]
+pub struct ProxySettingsWindowState {
+    target: Value<String>,
+    settings: Value<String>,
+    app_theme: Value<AppTheme>,
+}
+
+impl ProxySettingsWindowState {
+    pub fn new() -> Self {
+        let app_theme = get_app_theme();
+
+        ProxySettingsWindowState {
+            target: "".to_string().into(),
+            settings: "".to_string().into(),
+            app_theme: app_theme.into(),
+        }
+    }
+}
+
+impl Component for ProxySettingsWindow {
+    type State = ProxySettingsWindowState;
+    type Message = String;
+
+    fn accept_focus(&self) -> bool {
+        true
+    }
+
+    fn on_focus(
+        &mut self,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        _: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        self.update_app_theme(state);
+    }
+
+    fn receive(
+        &mut self,
+        ident: &str,
+        value: component::CommonVal<'_>,
+        _: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        let value = value.to_string();
+
+        match ident {
+            "proxy_url_update" => self.settings.url = value.trim().to_string(),
+            "proxy_username_update" => self.settings.username = optional_value(&value),
+            "proxy_password_update" => self.settings.password = optional_value(&value),
+            "proxy_no_proxy_update" => {
+                self.settings.no_proxy = value
+                    .split(',')
+                    .filter_map(optional_value)
+                    .collect::<Vec<String>>();
+            }
+            "proxy_input_escape" => context.set_focus("id", "proxy_settings_window"),
+            _ => {}
+        }
+    }
+
+    fn on_key(
+        &mut self,
+        key: component::KeyEvent,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        match key.code {
+            KeyCode::Char(char) => match char {
+                'u' => context.set_focus("id", "proxy_url"),
+                'n' => context.set_focus("id", "proxy_username"),
+                'p' => context.set_focus("id", "proxy_password"),
+                'h' => context.set_focus("id", "proxy_no_proxy"),
+
+                's' => {
+                    if let Ok(settings) = serde_json::to_string(&self.settings) {
+                        state.settings.set(settings);
+                        context.publish("proxy_settings__submit", |state| &state.settings);
+                    }
+                }
+
+                'c' => context.publish("proxy_settings__cancel", |state| &state.settings),
+
+                _ => {}
+            },
+
+            KeyCode::Esc => context.publish("proxy_settings__cancel", |state| &state.settings),
+
+            _ => {}
+        }
+    }
+
+    fn message(
+        &mut self,
+        message: Self::Message,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        context: anathema::prelude::Context<'_, Self::State>,
+    ) {
+        if let Ok(proxy_message) = serde_json::from_str::<ProxySettingsMessages>(&message) {
+            match proxy_message {
+                ProxySettingsMessages::Settings(target, settings) => {
+                    self.settings = settings;
+                    state.target.set(target);
+                    self.set_inputs(&context);
+                }
+            }
+        }
+    }
+}
+
+impl DashboardMessageHandler for ProxySettingsWindow {
+    fn handle_message(
+        value: component::CommonVal<'_>,
+        ident: impl Into<String>,
+        state: &mut DashboardState,
+        mut context: anathema::prelude::Context<'_, DashboardState>,
+        _: Elements<'_, '_>,
+        _: Ref<'_, HashMap<String, ComponentId<String>>>,
+    ) {
+        let event: String = ident.into();
+
+        match event.as_str() {
+            "proxy_settings__submit" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+
+                let value = &*value.to_common_str();
+                if let Ok(settings) = serde_json::from_str::<ProxySettings>(value) {
+                    let proxy = proxy_override(settings);
+                    state.project.to_mut().proxy = ProxySettingsState::from(proxy.as_ref()).into();
+                }
+            }
+
+            "proxy_settings__cancel" => {
+                state.floating_window.set(FloatingWindow::None);
+                context.set_focus("id", "app");
+            }
+
+            _ => {}
+        }
+    }
+}
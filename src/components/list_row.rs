@@ -0,0 +1,44 @@
+use anathema::state::{List, State, Value};
+
+use crate::fuzzy::highlight_segments;
+
+/// A row of a selector list, with the characters a search matched highlighted
+#[derive(Default, State)]
+pub struct ListRow {
+    pub spans: Value<List<MatchSpan>>,
+    pub row_color: Value<String>,
+
+    /// Shown after the text, like the keybinding of a command
+    pub detail: Value<String>,
+}
+
+#[derive(Default, State)]
+pub struct MatchSpan {
+    pub text: Value<String>,
+    pub matched: Value<bool>,
+}
+
+impl ListRow {
+    /// `positions` are the char indices of `text` to highlight
+    pub fn new(text: &str, positions: &[usize], row_color: &str) -> Self {
+        let spans = highlight_segments(text, positions)
+            .into_iter()
+            .map(|(text, matched)| MatchSpan {
+                text: text.into(),
+                matched: matched.into(),
+            });
+
+        ListRow {
+            spans: List::from_iter(spans),
+            row_color: row_color.to_string().into(),
+            detail: String::new().into(),
+        }
+    }
+
+    pub fn with_detail(self, detail: impl Into<String>) -> Self {
+        ListRow {
+            detail: detail.into().into(),
+            ..self
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    options::{save_options, Options},
+    options::{save_options, Options, ProxySettings},
     theme::{get_app_theme_by_name, AppTheme},
 };
 
@@ -15,8 +15,11 @@ use anathema::{
 use crate::options::get_options;
 
 use super::{
-    app_layout::AppLayoutMessages, dashboard::DashboardMessages,
-    response_renderer::ResponseRendererMessages, send_message,
+    app_layout::AppLayoutMessages,
+    dashboard::DashboardMessages,
+    floating_windows::proxy_settings::{proxy_override, ProxySettingsMessages},
+    response_renderer::ResponseRendererMessages,
+    send_message,
 };
 
 const TEMPLATE: &str = "./src/components/templates/options.aml";
@@ -38,6 +41,8 @@ pub struct OptionsViewState {
 struct OptionsState {
     app_theme_name: Value<String>,
     syntax_theme: Value<String>,
+    proxy: Value<String>,
+    autosave: Value<String>,
 }
 
 impl From<Options> for OptionsState {
@@ -45,10 +50,26 @@ impl From<Options> for OptionsState {
         OptionsState {
             app_theme_name: val.app_theme_name.into(),
             syntax_theme: val.syntax_theme.into(),
+            proxy: proxy_label(val.proxy.as_ref()).into(),
+            autosave: autosave_label(val.autosave).into(),
         }
     }
 }
 
+fn proxy_label(proxy: Option<&ProxySettings>) -> String {
+    match proxy {
+        Some(proxy) => proxy.url.clone(),
+        None => String::from("None (HTTP_PROXY/HTTPS_PROXY env vars)"),
+    }
+}
+
+fn autosave_label(autosave: bool) -> String {
+    match autosave {
+        true => String::from("On"),
+        false => String::from("Off"),
+    }
+}
+
 impl OptionsViewState {
     pub fn new(options: Options) -> Self {
         let app_theme = get_app_theme_by_name(&options.app_theme_name);
@@ -68,6 +89,7 @@ enum OptionsWindows {
     None,
     SyntaxThemeSelector,
     AppThemeSelector,
+    ProxySettings,
 }
 
 impl State for OptionsWindows {
@@ -75,6 +97,7 @@ impl State for OptionsWindows {
         match self {
             OptionsWindows::SyntaxThemeSelector => Some(CommonVal::Str("SyntaxThemeSelector")),
             OptionsWindows::AppThemeSelector => Some(CommonVal::Str("AppThemeSelector")),
+            OptionsWindows::ProxySettings => Some(CommonVal::Str("ProxySettings")),
             OptionsWindows::None => Some(CommonVal::Str("None")),
         }
     }
@@ -143,6 +166,35 @@ impl OptionsView {
         context.set_focus("id", "app_theme_selector");
     }
 
+    fn open_proxy_settings(
+        &self,
+        state: &mut OptionsViewState,
+        mut context: anathema::prelude::Context<'_, OptionsViewState>,
+    ) {
+        state.options_window.set(OptionsWindows::ProxySettings);
+        context.set_focus("id", "proxy_settings_window");
+
+        let settings = get_options().proxy.unwrap_or_default();
+        let message = ProxySettingsMessages::Settings(String::from("Global"), settings);
+
+        let _ = self.component_ids.try_borrow().map(|ids| {
+            if let Ok(msg) = serde_json::to_string(&message) {
+                let _ = send_message("proxy_settings_window", msg, &ids, context.emitter);
+            }
+        });
+    }
+
+    fn toggle_autosave(&self, state: &mut OptionsViewState) {
+        let mut options = get_options();
+        options.autosave = !options.autosave;
+        let label = autosave_label(options.autosave);
+
+        // TODO: add message alerts
+        if save_options(options).is_ok() {
+            state.options.to_mut().autosave.set(label);
+        }
+    }
+
     fn update_app_theme(
         &self,
         state: &mut OptionsViewState,
@@ -208,6 +260,8 @@ impl Component for OptionsView {
                 'b' => self.go_back(context),
                 'x' => self.open_theme_selector(state, context),
                 'a' => self.open_app_theme_selector(state, context),
+                'p' => self.open_proxy_settings(state, context),
+                's' => self.toggle_autosave(state),
 
                 _ => {}
             },
@@ -251,6 +305,33 @@ impl Component for OptionsView {
                 context.set_focus("id", "options");
             }
 
+            "proxy_settings__cancel" => {
+                state.options_window.set(OptionsWindows::None);
+                context.set_focus("id", "options");
+            }
+
+            "proxy_settings__submit" => {
+                state.options_window.set(OptionsWindows::None);
+                context.set_focus("id", "options");
+
+                let Ok(settings) = serde_json::from_str::<ProxySettings>(&value.to_string()) else {
+                    return;
+                };
+
+                let mut options = get_options();
+                options.proxy = proxy_override(settings);
+                let label = proxy_label(options.proxy.as_ref());
+
+                // TODO: add message alerts
+                #[allow(clippy::single_match)]
+                match save_options(options) {
+                    Ok(_) => {
+                        state.options.to_mut().proxy.set(label);
+                    }
+                    Err(_) => {}
+                }
+            }
+
             "app_theme_selector__selection" => {
                 let mut options = get_options();
 
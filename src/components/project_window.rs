@@ -1,26 +1,28 @@
 use std::{
-    cell::RefCell,
-    cmp::{max, min},
+    cell::{Ref, RefCell},
+    cmp::min,
     collections::HashMap,
     rc::Rc,
 };
 
 use anathema::{
-    component::{Component, ComponentId},
-    prelude::TuiBackend,
+    component::{Component, ComponentId, KeyCode},
+    prelude::{Context, TuiBackend},
     runtime::RuntimeBuilder,
     state::{List, State, Value},
     widgets::Elements,
 };
 
 use crate::{
+    fuzzy::{fuzzy_search, FuzzyMatch},
     messages::confirm_delete_project::ConfirmDeleteProject,
-    projects::{get_projects, Endpoint, PersistedProject, Project},
+    projects::{get_projects, PersistedEndpoint, PersistedProject},
     theme::{get_app_theme, AppTheme},
 };
 
 use super::{
-    dashboard::{DashboardMessageHandler, FloatingWindow},
+    dashboard::{DashboardMessageHandler, DashboardState, FloatingWindow},
+    list_row::ListRow,
     send_message,
     textarea::TextAreaMessages,
 };
@@ -37,9 +39,11 @@ pub struct ProjectWindowState {
     current_first_index: Value<u8>,
     current_last_index: Value<u8>,
     visible_projects: Value<u8>,
-    window_list: Value<List<Project>>,
+    window_list: Value<List<ListRow>>,
     project_count: Value<u8>,
     selected_project: Value<String>,
+    searching: Value<bool>,
+    query: Value<String>,
     app_theme: Value<AppTheme>,
 }
 
@@ -55,6 +59,8 @@ impl ProjectWindowState {
             visible_projects: 5.into(),
             window_list: List::empty(),
             selected_project: "".to_string().into(),
+            searching: false.into(),
+            query: "".to_string().into(),
             app_theme: app_theme.into(),
         }
     }
@@ -65,6 +71,8 @@ pub struct ProjectWindow {
     #[allow(dead_code)]
     component_ids: Rc<RefCell<HashMap<String, ComponentId<String>>>>,
     project_list: Vec<PersistedProject>,
+    search: Option<String>,
+    matches: Vec<(usize, FuzzyMatch)>,
 }
 
 impl ProjectWindow {
@@ -94,6 +102,8 @@ impl ProjectWindow {
         ProjectWindow {
             component_ids,
             project_list: vec![],
+            search: None,
+            matches: vec![],
         }
     }
 
@@ -104,51 +114,44 @@ impl ProjectWindow {
         Ok(())
     }
 
-    fn move_cursor_down(&self, state: &mut ProjectWindowState) {
-        let last_complete_list_index = self.project_list.len().saturating_sub(1);
-        let new_cursor = min(*state.cursor.to_ref() + 1, last_complete_list_index as u8);
-        state.cursor.set(new_cursor);
-
-        let mut first_index = *state.current_first_index.to_ref();
-        let mut last_index = *state.current_last_index.to_ref();
-
-        if new_cursor > last_index {
-            last_index = new_cursor;
-            first_index = new_cursor - (*state.visible_projects.to_ref() - 1);
-
-            state.current_first_index.set(first_index);
-            state.current_last_index.set(last_index);
-        }
+    fn move_cursor_down(&mut self, state: &mut ProjectWindowState) {
+        let cursor = *state.cursor.to_ref() as usize + 1;
+        self.show_projects(cursor, state);
+    }
 
-        self.update_project_list(
-            first_index.into(),
-            last_index.into(),
-            new_cursor.into(),
-            state,
-        );
+    fn move_cursor_up(&mut self, state: &mut ProjectWindowState) {
+        let cursor = state.cursor.to_ref().saturating_sub(1) as usize;
+        self.show_projects(cursor, state);
     }
 
-    fn move_cursor_up(&self, state: &mut ProjectWindowState) {
-        let new_cursor = max(state.cursor.to_ref().saturating_sub(1), 0);
-        state.cursor.set(new_cursor);
+    /// Filters the projects by the search and scrolls the window so the cursor is visible
+    fn show_projects(&mut self, cursor: usize, state: &mut ProjectWindowState) {
+        let query = self.search.clone().unwrap_or_default();
+        self.matches = fuzzy_search(&query, &self.project_list, |project| project.name.clone());
 
-        let mut first_index = *state.current_first_index.to_ref();
-        let mut last_index = *state.current_last_index.to_ref();
+        let visible_projects = *state.visible_projects.to_ref() as usize;
+        let cursor = min(cursor, self.matches.len().saturating_sub(1));
+        let mut first_index = *state.current_first_index.to_ref() as usize;
 
-        if new_cursor < first_index {
-            first_index = new_cursor;
-            last_index = new_cursor + (*state.visible_projects.to_ref() - 1);
+        if cursor < first_index {
+            first_index = cursor;
+        }
 
-            state.current_first_index.set(first_index);
-            state.current_last_index.set(last_index);
+        if cursor >= first_index + visible_projects {
+            first_index = cursor + 1 - visible_projects;
         }
 
-        self.update_project_list(
-            first_index.into(),
-            last_index.into(),
-            new_cursor.into(),
-            state,
+        first_index = min(
+            first_index,
+            self.matches.len().saturating_sub(visible_projects),
         );
+        let last_index = first_index + visible_projects - 1;
+
+        state.cursor.set(cursor as u8);
+        state.current_first_index.set(first_index as u8);
+        state.current_last_index.set(last_index as u8);
+
+        self.update_project_list(first_index, last_index, cursor, state);
     }
 
     fn update_project_list(
@@ -158,37 +161,84 @@ impl ProjectWindow {
         selected_index: usize,
         state: &mut ProjectWindowState,
     ) {
-        if self.project_list.is_empty() {
-            return;
-        }
-
-        let display_projects = &self.project_list[first_index..=last_index];
-        let mut new_project_list: Vec<Project> = vec![];
-        display_projects.iter().for_each(|display_project| {
-            new_project_list.push(display_project.into());
-        });
-
-        loop {
-            if state.window_list.len() > 0 {
-                state.window_list.pop_front();
-            } else {
-                break;
-            }
-        }
+        let mut new_project_list = List::<ListRow>::empty();
 
-        new_project_list
-            .into_iter()
+        self.matches
+            .iter()
             .enumerate()
-            .for_each(|(index, mut project)| {
-                let visible_index = selected_index.saturating_sub(first_index);
-                if index == visible_index {
-                    project.row_color = SELECTED_PROJECT_ROW_COLOR.to_string().into();
-                } else {
-                    project.row_color = DEFAULT_PROJECT_ROW_COLOR.to_string().into();
-                }
-
-                state.window_list.push(project);
+            .skip(first_index)
+            .take(last_index + 1 - first_index)
+            .for_each(|(index, (project_index, found))| {
+                let row_color = match index == selected_index {
+                    true => SELECTED_PROJECT_ROW_COLOR,
+                    false => DEFAULT_PROJECT_ROW_COLOR,
+                };
+
+                let name = &self.project_list[*project_index].name;
+                new_project_list.push(ListRow::new(name, &found.positions, row_color));
             });
+
+        state.window_list = new_project_list;
+    }
+
+    fn selected_project(&self, state: &ProjectWindowState) -> Option<&PersistedProject> {
+        self.matches
+            .get(*state.cursor.to_ref() as usize)
+            .and_then(|(index, _)| self.project_list.get(*index))
+    }
+
+    /// Starts, updates or with `None` ends the search, the cursor goes to the top match
+    fn set_search(&mut self, search: Option<String>, state: &mut ProjectWindowState) {
+        state.searching.set(search.is_some());
+        state.query.set(search.clone().unwrap_or_default());
+        state.current_first_index.set(0);
+
+        self.search = search;
+        self.show_projects(0, state);
+    }
+}
+
+/// Makes `project` the current project and loads its first endpoint into the editors
+pub fn open_project(
+    project: &PersistedProject,
+    state: &mut DashboardState,
+    context: &Context<'_, DashboardState>,
+    component_ids: &Ref<'_, HashMap<String, ComponentId<String>>>,
+) {
+    state.project.set(project.into());
+    state.endpoint_count.set(project.endpoints.len() as u8);
+
+    let endpoint = project.endpoints.first().cloned().unwrap_or_default();
+    open_endpoint(&endpoint, state, context, component_ids);
+}
+
+/// Makes the endpoint the current one and fills the dashboard inputs with it
+pub fn open_endpoint(
+    endpoint: &PersistedEndpoint,
+    state: &mut DashboardState,
+    context: &Context<'_, DashboardState>,
+    component_ids: &Ref<'_, HashMap<String, ComponentId<String>>>,
+) {
+    state.endpoint.set(endpoint.into());
+
+    // Update url input in dashboard
+    let url = endpoint.url.clone();
+    let _ = send_message("url_text_input", url, component_ids, context.emitter);
+
+    let inputs = [
+        ("request_body_input", endpoint.body.clone()),
+        ("graphql_query_input", endpoint.graphql.query.clone()),
+        (
+            "graphql_variables_input",
+            endpoint.graphql.variables.clone(),
+        ),
+    ];
+
+    for (input, value) in inputs {
+        let textarea_msg = TextAreaMessages::SetInput(value);
+        if let Ok(message) = serde_json::to_string(&textarea_msg) {
+            let _ = send_message(input, message, component_ids, context.emitter);
+        }
     }
 }
 
@@ -209,48 +259,8 @@ impl DashboardMessageHandler for ProjectWindow {
                 context.set_focus("id", "app");
             }
 
-            "project_window__selection" => {
-                state.floating_window.set(FloatingWindow::None);
-                context.set_focus("id", "app");
-
-                let value = &*value.to_common_str();
-                let project = serde_json::from_str::<PersistedProject>(value);
-
-                match project {
-                    Ok(project) => {
-                        state.project.set((&project).into());
-                        state.endpoint_count.set(project.endpoints.len() as u8);
-
-                        let default_endpoint: &Value<Endpoint> = &(Endpoint::new().into());
-
-                        let project = state.project.to_ref();
-                        let endpoints = project.endpoints.to_ref();
-                        let endpoint = endpoints.get(0).unwrap_or(default_endpoint);
-
-                        *state.endpoint.to_mut() = endpoint.to_ref().clone();
-
-                        // Update url input in dashboard
-                        let url = endpoint.to_ref().url.to_ref().to_string();
-                        let _ =
-                            send_message("url_text_input", url, &component_ids, context.emitter);
-
-                        let body = endpoint.to_ref().body.to_ref().to_string();
-                        let textarea_msg = TextAreaMessages::SetInput(body);
-                        if let Ok(message) = serde_json::to_string(&textarea_msg) {
-                            let _ = send_message(
-                                "request_body_input",
-                                message,
-                                &component_ids,
-                                context.emitter,
-                            );
-                        }
-                    }
-                    Err(_) => todo!(),
-                }
-            }
-
             "project_window__delete" => {
-                state.floating_window.set(FloatingWindow::ConfirmProject);
+                state.floating_window.set(FloatingWindow::ConfirmAction);
 
                 let value = &*value.to_common_str();
                 let project = serde_json::from_str::<PersistedProject>(value);
@@ -295,13 +305,31 @@ impl Component for ProjectWindow {
         _: anathema::widgets::Elements<'_, '_>,
         mut context: anathema::prelude::Context<'_, Self::State>,
     ) {
+        // NOTE: Typing goes to the search, the list keys that aren't characters still work
+        if let Some(query) = &self.search {
+            let mut query = query.clone();
+
+            match event.code {
+                KeyCode::Char(char) => {
+                    query.push(char);
+                    return self.set_search(Some(query), state);
+                }
+                KeyCode::Backspace => {
+                    query.pop();
+                    return self.set_search(Some(query), state);
+                }
+                KeyCode::Esc => return self.set_search(None, state),
+                _ => {}
+            }
+        }
+
         match event.code {
             anathema::component::KeyCode::Char(char) => match char {
                 'j' => self.move_cursor_down(state),
                 'k' => self.move_cursor_up(state),
+                '/' => self.set_search(Some(String::new()), state),
                 'd' => {
-                    let selected_index = *state.cursor.to_ref() as usize;
-                    let project = self.project_list.get(selected_index);
+                    let project = self.selected_project(state);
 
                     match project {
                         Some(project) => match serde_json::to_string(project) {
@@ -331,8 +359,7 @@ impl Component for ProjectWindow {
             }
 
             anathema::component::KeyCode::Enter => {
-                let selected_index = *state.cursor.to_ref() as usize;
-                let project = self.project_list.get(selected_index);
+                let project = self.selected_project(state);
 
                 match project {
                     Some(project) => match serde_json::to_string(project) {
@@ -361,23 +388,8 @@ impl Component for ProjectWindow {
         self.update_app_theme(state);
 
         match self.load(state) {
-            Ok(_) => {
-                // Reset navigation state
-                let current_last_index = min(
-                    *state.visible_projects.to_ref(),
-                    self.project_list.len() as u8,
-                )
-                .saturating_sub(1);
-                state.cursor.set(0);
-                state.current_first_index.set(0);
-                state.current_last_index.set(current_last_index);
-
-                let first_index: usize = *state.current_first_index.to_ref() as usize;
-                let last_index: usize = *state.current_last_index.to_ref() as usize;
-                let selected_index = 0;
-
-                self.update_project_list(first_index, last_index, selected_index, state)
-            }
+            // Reset navigation state
+            Ok(_) => self.set_search(None, state),
 
             // TODO: Figure out what to do if the list of projects can't be loaded
             Err(_) => todo!(),
@@ -6,6 +6,9 @@ use anathema::{
 pub const REQUEST_BODY_SECTION_TEMPLATE: &str =
     "./src/components/templates/request_body_section.aml";
 
+pub const GRAPHQL_BODY_SECTION_TEMPLATE: &str =
+    "./src/components/templates/graphql_body_section.aml";
+
 #[derive(Default)]
 pub struct RequestBodySection;
 
@@ -21,7 +21,13 @@ use serde::{Deserialize, Serialize};
 use syntect::highlighting::Theme;
 
 use crate::{
+    keymap::{get_keymap, KeyBinding, KeyContext, Keymap},
     options::get_syntax_theme,
+    palette::Action,
+    requests::{
+        sse::{take_pending_stream, SseStream, StreamUpdate},
+        RAW_RESPONSE_FILE,
+    },
     theme::{get_app_theme, get_app_theme_persisted, AppTheme},
 };
 
@@ -53,11 +59,21 @@ pub struct ResponseRenderer {
     viewport_height: usize,
     extension: String,
 
+    // Extension of the decoded response, kept while the raw bytes are shown
+    decoded_extension: String,
+    raw_view: bool,
+
     // All lines from the response
     response_lines: Vec<String>,
 
     code_sample: Option<String>,
     code_ext: Option<String>,
+
+    // Event stream being shown as its events arrive
+    event_stream: Option<SseStream>,
+    stream_paused: bool,
+
+    keymap: Keymap,
 }
 
 impl ResponseRenderer {
@@ -100,9 +116,14 @@ impl ResponseRenderer {
             viewport_height: 0,
             size: None,
             extension: "".to_string(),
+            decoded_extension: "".to_string(),
+            raw_view: false,
             response_lines: vec![],
             code_ext: None,
             code_sample: None,
+            event_stream: None,
+            stream_paused: false,
+            keymap: get_keymap(),
         }
     }
 
@@ -426,6 +447,95 @@ impl ResponseRenderer {
         state.viewable_response.set(viewable_response);
     }
 
+    fn start_stream(&mut self, elements: &mut Elements<'_, '_>, state: &mut ResponseRendererState) {
+        self.stop_stream(state);
+
+        let Ok(response_reader) = get_file_reader("/tmp/tome_response.txt") else {
+            return;
+        };
+
+        self.event_stream = take_pending_stream();
+        self.stream_paused = false;
+        self.response_reader = Some(response_reader);
+        self.raw_view = false;
+        self.decoded_extension = String::from("txt");
+        self.render_response(String::from("txt"), elements, state, 0);
+
+        state.stream_status.set(String::from("Streaming"));
+    }
+
+    fn poll_stream(&mut self, elements: &mut Elements<'_, '_>, state: &mut ResponseRendererState) {
+        let Some(event_stream) = &self.event_stream else {
+            return;
+        };
+
+        let updates = event_stream.updates();
+        if updates.is_empty() {
+            return;
+        }
+
+        for update in updates {
+            match update {
+                StreamUpdate::Event(event) => self.response_lines.extend(event.lines()),
+                StreamUpdate::Closed(error) => {
+                    let message = match error {
+                        Some(error) => format!("Stream closed: {error}"),
+                        None => String::from("Stream closed by the server"),
+                    };
+
+                    self.response_lines.push(message);
+                    self.event_stream = None;
+                    state.stream_status.set(String::from("Closed"));
+                }
+            }
+        }
+
+        if !self.stream_paused {
+            self.follow_stream(elements, state);
+        }
+    }
+
+    /// Scrolls to the newest events
+    fn follow_stream(
+        &mut self,
+        elements: &mut Elements<'_, '_>,
+        state: &mut ResponseRendererState,
+    ) {
+        let offset = self
+            .response_lines
+            .len()
+            .saturating_sub(self.viewport_height);
+
+        self.scroll_response(elements, state, offset);
+    }
+
+    fn toggle_stream_pause(
+        &mut self,
+        elements: &mut Elements<'_, '_>,
+        state: &mut ResponseRendererState,
+    ) {
+        if self.event_stream.is_none() {
+            return;
+        }
+
+        self.stream_paused = !self.stream_paused;
+
+        match self.stream_paused {
+            true => state.stream_status.set(String::from("Paused")),
+            false => {
+                state.stream_status.set(String::from("Streaming"));
+                self.follow_stream(elements, state);
+            }
+        }
+    }
+
+    fn stop_stream(&mut self, state: &mut ResponseRendererState) {
+        if let Some(event_stream) = self.event_stream.take() {
+            event_stream.stop();
+            state.stream_status.set(String::from("Stopped"));
+        }
+    }
+
     fn update_size(&mut self, context: Context<'_, ResponseRendererState>) {
         let size = context.viewport.size();
 
@@ -648,6 +758,9 @@ pub struct ResponseRendererState {
     pub filter_indexes: Value<List<usize>>,
     pub filter_total: Value<usize>,
     pub filter_nav_index: Value<usize>,
+
+    /// Streaming, Paused, Stopped or Closed while an event stream is shown
+    pub stream_status: Value<String>,
 }
 
 impl ResponseRendererState {
@@ -674,6 +787,7 @@ impl ResponseRendererState {
             filter_indexes: List::from_iter(vec![]),
             filter_total: 0.into(),
             filter_nav_index: 0.into(),
+            stream_status: "".to_string().into(),
         }
     }
 }
@@ -720,6 +834,16 @@ impl Component for ResponseRenderer {
         info!("response_renderer has focus");
     }
 
+    fn tick(
+        &mut self,
+        state: &mut Self::State,
+        mut elements: Elements<'_, '_>,
+        _: Context<'_, Self::State>,
+        _: std::time::Duration,
+    ) {
+        self.poll_stream(&mut elements, state);
+    }
+
     fn on_blur(&mut self, _: &mut Self::State, _: Elements<'_, '_>, _: Context<'_, Self::State>) {
         info!("response_renderer lost focus");
     }
@@ -739,7 +863,7 @@ impl Component for ResponseRenderer {
         &mut self,
         event: anathema::component::KeyEvent,
         state: &mut Self::State,
-        elements: anathema::widgets::Elements<'_, '_>,
+        mut elements: anathema::widgets::Elements<'_, '_>,
         mut context: anathema::prelude::Context<'_, Self::State>,
     ) {
         #[allow(clippy::single_match)]
@@ -750,60 +874,65 @@ impl Component for ResponseRenderer {
             }
 
             anathema::component::KeyCode::Char(char) => {
-                match event.ctrl {
-                    true => {
-                        match char {
-                            'd' => self.scroll(state, elements, context, ScrollDirection::Down),
-                            'u' => self.scroll(state, elements, context, ScrollDirection::Up),
-
-                            'p' => {
-                                // move to previous find
-                                let current_index = self.text_filter.search_navigation_cursor;
-                                let line = if current_index == 0 {
-                                    self.text_filter.indexes.len().saturating_sub(1)
-                                } else {
-                                    current_index.saturating_sub(1)
-                                };
-
-                                self.text_filter.search_navigation_cursor = line;
-                                let line = self.text_filter.indexes.get(line).unwrap_or(&0);
-
-                                scroll_to_line(state, elements, context, *line);
-                            }
-
-                            'n' => {
-                                // move to previous find
-                                let current_index = self.text_filter.search_navigation_cursor;
-                                let last_index = self.text_filter.indexes.len().saturating_sub(1);
-                                let line = if current_index == last_index {
-                                    self.text_filter.indexes.first()
-                                } else {
-                                    self.text_filter.indexes.get(current_index + 1)
-                                };
-
-                                let line = line.unwrap_or(&0);
-
-                                self.text_filter.search_navigation_cursor = *line;
-
-                                scroll_to_line(state, elements, context, *line);
-                            }
-                            _ => {}
-                        }
+                let binding = KeyBinding {
+                    key: char,
+                    ctrl: event.ctrl,
+                };
+
+                match self.keymap.action(KeyContext::Response, binding) {
+                    Some(Action::ScrollDown) => {
+                        self.scroll(state, elements, context, ScrollDirection::Down)
+                    }
+                    Some(Action::ScrollUp) => {
+                        self.scroll(state, elements, context, ScrollDirection::Up)
                     }
 
-                    false => match char {
-                        'f' => {
-                            context.set_focus("id", "response_body_input");
-                            info!("Set focus to response_body_input");
+                    Some(Action::PreviousMatch) => {
+                        // move to previous find
+                        let current_index = self.text_filter.search_navigation_cursor;
+                        let line = if current_index == 0 {
+                            self.text_filter.indexes.len().saturating_sub(1)
+                        } else {
+                            current_index.saturating_sub(1)
+                        };
+
+                        self.text_filter.search_navigation_cursor = line;
+                        let line = self.text_filter.indexes.get(line).unwrap_or(&0);
+
+                        scroll_to_line(state, elements, context, *line);
+                    }
+
+                    Some(Action::NextMatch) => {
+                        // move to previous find
+                        let current_index = self.text_filter.search_navigation_cursor;
+                        let last_index = self.text_filter.indexes.len().saturating_sub(1);
+                        let line = if current_index == last_index {
+                            self.text_filter.indexes.first()
+                        } else {
+                            self.text_filter.indexes.get(current_index + 1)
+                        };
+
+                        let line = line.unwrap_or(&0);
+
+                        self.text_filter.search_navigation_cursor = *line;
 
-                            if !state.filter.to_ref().is_empty() {
-                                let filter = state.filter.to_ref().to_string();
-                                self.apply_response_filter(filter, state, context, elements);
-                            }
+                        scroll_to_line(state, elements, context, *line);
+                    }
+
+                    Some(Action::PauseStream) => self.toggle_stream_pause(&mut elements, state),
+                    Some(Action::StopStream) => self.stop_stream(state),
+
+                    Some(Action::FilterResponse) => {
+                        context.set_focus("id", "response_body_input");
+                        info!("Set focus to response_body_input");
+
+                        if !state.filter.to_ref().is_empty() {
+                            let filter = state.filter.to_ref().to_string();
+                            self.apply_response_filter(filter, state, context, elements);
                         }
+                    }
 
-                        _ => {}
-                    },
+                    _ => {}
                 }
             }
 
@@ -827,7 +956,12 @@ impl Component for ResponseRenderer {
                     self.update_app_theme(state);
                 }
 
+                ResponseRendererMessages::StreamStart => self.start_stream(&mut elements, state),
+
                 ResponseRendererMessages::ResponseUpdate(extension) => {
+                    self.stop_stream(state);
+                    state.stream_status.set(String::new());
+
                     // TODO: Try to delete this file if the program closes/quits/crashes
                     let reader_result = get_file_reader("/tmp/tome_response.txt");
                     if reader_result.is_err() {
@@ -836,11 +970,38 @@ impl Component for ResponseRenderer {
                     }
 
                     let response_reader = reader_result.unwrap();
+                    self.response_reader = Some(response_reader);
+                    self.raw_view = false;
+                    self.decoded_extension = extension.clone();
+                    self.render_response(extension, &mut elements, state, 0);
+                }
+
+                ResponseRendererMessages::ToggleRawView => {
+                    if self.response_reader.is_none() {
+                        return;
+                    }
+
+                    self.raw_view = !self.raw_view;
+                    let (file_path, extension) = match self.raw_view {
+                        true => (format!("/tmp/{RAW_RESPONSE_FILE}"), String::from("txt")),
+                        false => (
+                            String::from("/tmp/tome_response.txt"),
+                            self.decoded_extension.clone(),
+                        ),
+                    };
+
+                    let Ok(response_reader) = get_file_reader(&file_path) else {
+                        return;
+                    };
+
                     self.response_reader = Some(response_reader);
                     self.render_response(extension, &mut elements, state, 0);
                 }
 
                 ResponseRendererMessages::SyntaxPreview(theme) => {
+                    self.stop_stream(state);
+                    state.stream_status.set(String::new());
+
                     loop {
                         if state.lines.len() == 0 {
                             break;
@@ -1009,6 +1170,10 @@ pub enum ResponseRendererMessages {
     ResponseUpdate(String),
     SyntaxPreview(Option<String>),
     ThemeUpdate,
+    ToggleRawView,
+
+    /// Starts showing the event stream parked by the request
+    StreamStart,
 }
 
 #[derive(Default, Debug, Serialize, Deserialize)]
@@ -7,12 +7,25 @@ use anathema::state::Hex;
 use log::info;
 use syntect::easy::HighlightLines;
 use syntect::highlighting::{FontStyle, Style, Theme, ThemeSet};
-use syntect::parsing::SyntaxSet;
+use syntect::parsing::{SyntaxDefinition, SyntaxSet};
 use syntect::util::LinesWithEndings;
 
 use crate::options::get_syntax_theme;
 use crate::themes::{MONOKAI_DARK, THEME_MAP};
 
+const GRAPHQL_SYNTAX: &str = include_str!("../../syntaxes/GraphQL.sublime-syntax");
+
+// NOTE: syntect's defaults plus the syntaxes it doesn't ship with
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(|| {
+    let mut builder = SyntaxSet::load_defaults_newlines().into_builder();
+
+    if let Ok(graphql) = SyntaxDefinition::load_from_str(GRAPHQL_SYNTAX, true, None) {
+        builder.add(graphql);
+    }
+
+    builder.build()
+});
+
 #[derive(Debug)]
 pub struct Span<'a> {
     pub src: &'a str,
@@ -117,27 +130,89 @@ pub fn get_highlight_theme(name: Option<String>) -> Theme {
     }
 }
 
-pub fn highlight<'a>(src: &'a str, ext: &str, name: Option<String>) -> (Box<[Line<'a>]>, Theme) {
-    let ps = SyntaxSet::load_defaults_newlines();
-    let theme = get_highlight_theme(name);
+/// Picks the syntax highlighting extension for a response, first from its
+/// media type and then by sniffing the body, falling back to plain text
+pub fn detect_extension(content_type: Option<&str>, body: &str) -> String {
+    content_type
+        .and_then(extension_for_media_type)
+        .or_else(|| sniff_extension(body))
+        .unwrap_or("txt")
+        .to_string()
+}
 
-    let mut extension = ext;
-    if ext.contains(";") {
-        if let Some((ex, _)) = ext.split_once(';') {
-            extension = ex;
+fn extension_for_media_type(content_type: &str) -> Option<&'static str> {
+    let media_type = content_type.split(';').next()?.trim().to_lowercase();
+    let (kind, subtype) = media_type.split_once('/')?;
+
+    // Structured syntax suffixes, e.g. application/vnd.api+json or image/svg+xml
+    if let Some((_, suffix)) = subtype.rsplit_once('+') {
+        match suffix {
+            "json" => return Some("json"),
+            "xml" => return Some("xml"),
+            "yaml" => return Some("yaml"),
+            _ => {}
         }
     }
 
-    let syntax = ps.find_syntax_by_extension(extension).unwrap();
+    let extension = match (kind, subtype) {
+        (_, "json" | "x-json" | "json5" | "x-ndjson" | "jsonl") => "json",
+        (_, "javascript" | "x-javascript" | "ecmascript") => "js",
+        (_, "xml") => "xml",
+        ("text", "html") | (_, "xhtml") => "html",
+        ("text", "css") => "css",
+        (_, "yaml" | "x-yaml") => "yaml",
+        ("text", "markdown" | "x-markdown") => "md",
+        (_, "sql") => "sql",
+        (_, "x-python" | "x-python3") => "py",
+        (_, "x-sh" | "x-shellscript") => "sh",
+        (_, "x-rust") => "rs",
+        (_, "graphql") => "graphql",
+        ("text", "plain" | "csv") => "txt",
+        _ => return None,
+    };
+
+    Some(extension)
+}
+
+fn sniff_extension(body: &str) -> Option<&'static str> {
+    let start = body.trim_start();
+    let lowercase_start: String = start.chars().take(15).collect::<String>().to_lowercase();
+
+    if (start.starts_with('{') || start.starts_with('['))
+        && serde_json::from_str::<serde_json::Value>(body).is_ok()
+    {
+        return Some("json");
+    }
+
+    if lowercase_start.starts_with("<!doctype html") || lowercase_start.starts_with("<html") {
+        return Some("html");
+    }
+
+    if start.starts_with('<') {
+        return Some("xml");
+    }
+
+    None
+}
+
+pub fn highlight<'a>(src: &'a str, ext: &str, name: Option<String>) -> (Box<[Line<'a>]>, Theme) {
+    let ps = &*SYNTAX_SET;
+    let theme = get_highlight_theme(name);
+
+    let syntax = ps
+        .find_syntax_by_extension(ext)
+        .or_else(|| ps.find_syntax_by_token(ext))
+        .unwrap_or_else(|| ps.find_syntax_plain_text());
     let mut h = HighlightLines::new(syntax, &theme);
     let mut output = vec![];
 
     for line in LinesWithEndings::from(src) {
         info!("Highlinting this slice: {line}");
 
+        // NOTE: Lines the syntax can't parse are shown unstyled instead of failing the render
         let mut head = h
-            .highlight_line(line, &ps)
-            .unwrap()
+            .highlight_line(line, ps)
+            .unwrap_or_else(|_| vec![(Style::default(), line)])
             .into_iter()
             .map(Span::from)
             .collect::<Vec<_>>();
@@ -244,3 +319,44 @@ impl<'a> Parser<'a> {
         }
     }
 }
+
+#[test]
+fn test_detect_extension_from_media_type() {
+    assert_eq!(
+        detect_extension(Some("application/json; charset=utf-8"), ""),
+        "json"
+    );
+    assert_eq!(
+        detect_extension(Some("application/vnd.api+json"), ""),
+        "json"
+    );
+    assert_eq!(detect_extension(Some("application/atom+xml"), ""), "xml");
+    assert_eq!(detect_extension(Some("text/javascript"), ""), "js");
+}
+
+#[test]
+fn test_detect_extension_sniffs_body() {
+    let body = "  {\"id\": 1}";
+
+    assert_eq!(
+        detect_extension(Some("application/octet-stream"), body),
+        "json"
+    );
+    assert_eq!(
+        detect_extension(None, "<!DOCTYPE html><html></html>"),
+        "html"
+    );
+    assert_eq!(detect_extension(None, "plain words"), "txt");
+}
+
+#[test]
+fn test_graphql_syntax_is_loaded() {
+    assert!(SYNTAX_SET.find_syntax_by_extension("graphql").is_some());
+}
+
+#[test]
+fn test_highlight_unknown_extension() {
+    let (lines, _) = highlight("some text\n", "vnd.unknown", None);
+
+    assert_eq!(lines.len(), 1);
+}
@@ -20,16 +20,21 @@ use anathema::{
 use arboard::Clipboard;
 use serde::{Deserialize, Serialize};
 
-use super::dashboard::DashboardMessages;
+use crate::{
+    keymap::{get_keymap, KeyBinding, KeyContext, Keymap},
+    palette::Action,
+};
+
+use super::{dashboard::DashboardMessages, textinput::TextUpdate};
 
 pub const TEXTAREA_TEMPLATE: &str = "./src/components/templates/textarea.aml";
 
 #[derive(Default)]
 pub struct TextArea {
-    #[allow(unused)]
     pub input_for: Option<String>,
     pub listeners: Vec<String>,
     pub component_ids: Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+    pub keymap: Keymap,
 }
 
 #[derive(Default, anathema::state::State)]
@@ -163,7 +168,7 @@ impl anathema::component::Component for TextArea {
                     state.input.set(value);
                 }
 
-                TextAreaMessages::InputChange(_) => {}
+                TextAreaMessages::InputUpdate(_) => {}
             }
         }
     }
@@ -255,11 +260,18 @@ impl anathema::component::Component for TextArea {
             }
 
             anathema::component::KeyCode::Char(char) => match event.ctrl {
-                true => match char {
-                    'd' => scroll(state, elements, context, ScrollDirection::Down),
-                    'u' => scroll(state, elements, context, ScrollDirection::Up),
-                    _ => {}
-                },
+                true => {
+                    let binding = KeyBinding::ctrl(char);
+                    match self.keymap.action(KeyContext::TextArea, binding) {
+                        Some(Action::ScrollDown) => {
+                            scroll(state, elements, context, ScrollDirection::Down)
+                        }
+                        Some(Action::ScrollUp) => {
+                            scroll(state, elements, context, ScrollDirection::Up)
+                        }
+                        _ => {}
+                    }
+                }
 
                 false => {
                     let emitter = context.emitter.clone();
@@ -354,10 +366,25 @@ struct CursorData {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum TextAreaMessages {
-    InputChange(String),
+    InputUpdate(TextUpdate),
     SetInput(String),
 }
 
+impl TextAreaMessages {
+    /// What a textarea sends its listeners when its text changes
+    pub fn input_update_message(id: String, value: String) -> DashboardMessages {
+        DashboardMessages::TextArea(TextAreaMessages::InputUpdate(TextUpdate { id, value }))
+    }
+
+    /// The text update in a message a textarea sent its listeners
+    pub fn input_update(message: &str) -> Option<TextUpdate> {
+        match serde_json::from_str::<DashboardMessages>(message) {
+            Ok(DashboardMessages::TextArea(TextAreaMessages::InputUpdate(update))) => Some(update),
+            _ => None,
+        }
+    }
+}
+
 impl TextArea {
     pub fn register(
         ids: &Rc<RefCell<HashMap<String, ComponentId<String>>>>,
@@ -377,6 +404,7 @@ impl TextArea {
                 component_ids: ids.clone(),
                 listeners,
                 input_for,
+                keymap: get_keymap(),
             },
             TextAreaInputState::new(),
         )?;
@@ -389,12 +417,11 @@ impl TextArea {
 
     fn send_to_listeners(&self, code: KeyCode, state: &mut TextAreaInputState, emitter: Emitter) {
         if let KeyCode::Char(_) = code {
-            // TODO: Fix the outgoing message so it is not 100% coupled to only response body
-            // editing, use InputUpdate instead of InputChange, like in edit_input.rs
             if let Ok(ids) = self.component_ids.try_borrow() {
                 let input_value = state.input.to_ref().to_string();
-                let input_change_message =
-                    DashboardMessages::TextArea(TextAreaMessages::InputChange(input_value));
+                let id = self.input_for.clone().unwrap_or_default();
+
+                let input_change_message = TextAreaMessages::input_update_message(id, input_value);
 
                 if let Ok(serialized_message) = serde_json::to_string(&input_change_message) {
                     for listener in &self.listeners {
@@ -1243,3 +1270,19 @@ fn test_get_cursor_up_x_y_target_same_length() {
         (x, y)
     );
 }
+
+#[test]
+fn test_input_update_round_trip() {
+    let message = TextAreaMessages::input_update_message(
+        String::from("curl_command"),
+        String::from("curl -X POST https://example.com"),
+    );
+    let message = serde_json::to_string(&message).unwrap();
+
+    let update = TextAreaMessages::input_update(&message).unwrap();
+    assert_eq!(update.id, "curl_command");
+    assert_eq!(update.value, "curl -X POST https://example.com");
+
+    let set_input = serde_json::to_string(&TextAreaMessages::SetInput(String::new())).unwrap();
+    assert!(TextAreaMessages::input_update(&set_input).is_none());
+}
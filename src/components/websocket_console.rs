@@ -0,0 +1,414 @@
+use std::{
+    cell::{Ref, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+
+use anathema::{
+    component::{self, Component, ComponentId, KeyCode},
+    default_widgets::Overflow,
+    geometry::Pos,
+    prelude::{Context, TuiBackend},
+    runtime::RuntimeBuilder,
+    state::{CommonVal, List, State, Value},
+    widgets::Elements,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    projects::{PersistedEndpoint, PersistedProject},
+    requests::websocket::{FrameDirection, WebSocketConnection, WebSocketEvent, WebSocketFrame},
+    theme::{get_app_theme, AppTheme},
+};
+
+use super::{
+    dashboard::{DashboardMessageHandler, DashboardState},
+    send_message,
+    textarea::TextAreaMessages,
+};
+
+const TEMPLATE: &str = "./src/components/templates/websocket_console.aml";
+
+// NOTE: Older frames are dropped so long running connections stay responsive
+const MAX_FRAMES: usize = 1000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum WebSocketConsoleMessages {
+    Connect(PersistedProject, PersistedEndpoint),
+}
+
+#[derive(Default, State)]
+pub struct WebSocketFrameState {
+    direction: Value<String>,
+    timestamp: Value<String>,
+    text: Value<String>,
+}
+
+impl From<WebSocketFrame> for WebSocketFrameState {
+    fn from(frame: WebSocketFrame) -> Self {
+        let direction = match frame.direction {
+            FrameDirection::Sent => "sent",
+            FrameDirection::Received => "received",
+            FrameDirection::Info => "info",
+        };
+
+        WebSocketFrameState {
+            direction: direction.to_string().into(),
+            timestamp: frame.timestamp.into(),
+            text: frame.text.into(),
+        }
+    }
+}
+
+#[derive(Default, State)]
+pub struct WebSocketConsoleState {
+    frames: Value<List<WebSocketFrameState>>,
+    status: Value<String>,
+    snippet_label: Value<String>,
+    snippets: Value<String>,
+    app_theme: Value<AppTheme>,
+}
+
+impl WebSocketConsoleState {
+    pub fn new() -> Self {
+        WebSocketConsoleState {
+            frames: List::empty(),
+            status: "Disconnected".to_string().into(),
+            snippet_label: "No Snippets".to_string().into(),
+            snippets: "[]".to_string().into(),
+            app_theme: get_app_theme().into(),
+        }
+    }
+}
+
+pub struct WebSocketConsole {
+    component_ids: Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+    connection: Option<WebSocketConnection>,
+
+    /// Text currently in the composer
+    message: String,
+
+    snippets: Vec<String>,
+    snippet_index: usize,
+}
+
+impl WebSocketConsole {
+    pub fn register(
+        ids: &Rc<RefCell<HashMap<String, ComponentId<String>>>>,
+        builder: &mut RuntimeBuilder<TuiBackend, ()>,
+    ) -> anyhow::Result<()> {
+        let id = builder.register_component(
+            "websocket_console",
+            TEMPLATE,
+            WebSocketConsole {
+                component_ids: ids.clone(),
+                connection: None,
+                message: String::new(),
+                snippets: vec![],
+                snippet_index: 0,
+            },
+            WebSocketConsoleState::new(),
+        )?;
+
+        let mut ids_ref = ids.borrow_mut();
+        ids_ref.insert(String::from("websocket_console"), id);
+
+        Ok(())
+    }
+
+    fn connect(
+        &mut self,
+        project: PersistedProject,
+        endpoint: PersistedEndpoint,
+        state: &mut WebSocketConsoleState,
+        elements: Elements<'_, '_>,
+    ) {
+        self.disconnect();
+        self.snippets = endpoint.snippets.clone();
+        self.snippet_index = 0;
+        self.update_snippet_label(state);
+
+        loop {
+            if state.frames.len() == 0 {
+                break;
+            }
+
+            state.frames.remove(0);
+        }
+
+        state.status.set(format!("Connecting to {}", endpoint.url));
+
+        match WebSocketConnection::connect(&project, &endpoint) {
+            Ok(connection) => {
+                self.connection = Some(connection);
+                state.status.set(format!("Connected to {}", endpoint.url));
+                self.poll_events(state, elements);
+            }
+
+            Err(error) => {
+                state.status.set(String::from("Disconnected"));
+
+                let frame = WebSocketFrame::new(
+                    FrameDirection::Info,
+                    format!("Connection failed: {error}"),
+                );
+                state.frames.push_back(frame.into());
+            }
+        }
+    }
+
+    fn disconnect(&mut self) {
+        if let Some(connection) = self.connection.take() {
+            connection.close();
+        }
+    }
+
+    fn poll_events(&mut self, state: &mut WebSocketConsoleState, mut elements: Elements<'_, '_>) {
+        let Some(connection) = &self.connection else {
+            return;
+        };
+
+        let events = connection.events();
+        if events.is_empty() {
+            return;
+        }
+
+        for event in events {
+            match event {
+                WebSocketEvent::Frame(frame) => state.frames.push_back(frame.into()),
+                WebSocketEvent::Closed => {
+                    self.connection = None;
+                    state.status.set(String::from("Disconnected"));
+                }
+            }
+        }
+
+        while state.frames.len() > MAX_FRAMES {
+            state.frames.remove(0);
+        }
+
+        // Keep the newest frames in view
+        let bottom = state.frames.len() as i32;
+        elements
+            .by_attribute("id", "frames_container")
+            .each(|element, _| {
+                let overflow = element.to::<Overflow>();
+                overflow.scroll_to(Pos { x: 0, y: bottom });
+                overflow.scroll_up_by(0);
+            });
+    }
+
+    fn send(&mut self, state: &mut WebSocketConsoleState) {
+        if self.message.is_empty() {
+            return;
+        }
+
+        let result = match &self.connection {
+            Some(connection) => connection.send(self.message.clone()),
+            None => Err(anyhow::Error::msg("Not connected, press (R) to connect")),
+        };
+
+        if let Err(error) = result {
+            let frame = WebSocketFrame::new(FrameDirection::Info, error.to_string());
+            state.frames.push_back(frame.into());
+        }
+    }
+
+    fn save_snippet(
+        &mut self,
+        state: &mut WebSocketConsoleState,
+        context: &mut Context<'_, WebSocketConsoleState>,
+    ) {
+        if self.message.is_empty() || self.snippets.contains(&self.message) {
+            return;
+        }
+
+        self.snippets.push(self.message.clone());
+        self.snippet_index = self.snippets.len() - 1;
+        self.publish_snippets(state, context);
+    }
+
+    fn delete_snippet(
+        &mut self,
+        state: &mut WebSocketConsoleState,
+        context: &mut Context<'_, WebSocketConsoleState>,
+    ) {
+        if self.snippet_index >= self.snippets.len() {
+            return;
+        }
+
+        self.snippets.remove(self.snippet_index);
+        self.snippet_index = self
+            .snippet_index
+            .min(self.snippets.len().saturating_sub(1));
+        self.publish_snippets(state, context);
+    }
+
+    fn load_snippet(&mut self, offset: isize, context: &Context<'_, WebSocketConsoleState>) {
+        if self.snippets.is_empty() {
+            return;
+        }
+
+        let count = self.snippets.len() as isize;
+        self.snippet_index = (self.snippet_index as isize + offset).rem_euclid(count) as usize;
+        self.message = self.snippets[self.snippet_index].clone();
+
+        let Ok(component_ids) = self.component_ids.try_borrow() else {
+            return;
+        };
+
+        let message = TextAreaMessages::SetInput(self.message.clone());
+        if let Ok(message) = serde_json::to_string(&message) {
+            let _ = send_message(
+                "websocket_message_input",
+                message,
+                &component_ids,
+                context.emitter,
+            );
+        }
+    }
+
+    fn publish_snippets(
+        &self,
+        state: &mut WebSocketConsoleState,
+        context: &mut Context<'_, WebSocketConsoleState>,
+    ) {
+        self.update_snippet_label(state);
+
+        if let Ok(snippets) = serde_json::to_string(&self.snippets) {
+            state.snippets.set(snippets);
+            context.publish("websocket_console__snippets", |state| &state.snippets);
+        }
+    }
+
+    fn update_snippet_label(&self, state: &mut WebSocketConsoleState) {
+        let label = match self.snippets.len() {
+            0 => String::from("No Snippets"),
+            count => format!("Snippet {}/{count}", self.snippet_index + 1),
+        };
+
+        state.snippet_label.set(label);
+    }
+}
+
+impl Component for WebSocketConsole {
+    type State = WebSocketConsoleState;
+    type Message = String;
+
+    fn accept_focus(&self) -> bool {
+        true
+    }
+
+    fn on_focus(
+        &mut self,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        _: Context<'_, Self::State>,
+    ) {
+        state.app_theme.set(get_app_theme());
+    }
+
+    fn tick(
+        &mut self,
+        state: &mut Self::State,
+        elements: Elements<'_, '_>,
+        _: Context<'_, Self::State>,
+        _: std::time::Duration,
+    ) {
+        self.poll_events(state, elements);
+    }
+
+    fn receive(
+        &mut self,
+        ident: &str,
+        value: CommonVal<'_>,
+        _: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: Context<'_, Self::State>,
+    ) {
+        // NOTE: Leaving the composer comes back to the console instead of the dashboard
+        if ident == "composer_focus" && !value.to_bool() {
+            context.set_focus("id", "websocket_console");
+        }
+    }
+
+    fn on_key(
+        &mut self,
+        key: component::KeyEvent,
+        state: &mut Self::State,
+        _: Elements<'_, '_>,
+        mut context: Context<'_, Self::State>,
+    ) {
+        match key.code {
+            KeyCode::Char(char) => match char {
+                'c' => context.set_focus("id", "websocket_message"),
+                's' => self.send(state),
+                'a' => self.save_snippet(state, &mut context),
+                'd' => self.delete_snippet(state, &mut context),
+                'n' => {
+                    self.load_snippet(1, &context);
+                    self.update_snippet_label(state);
+                }
+                'p' => {
+                    self.load_snippet(-1, &context);
+                    self.update_snippet_label(state);
+                }
+                'x' => self.disconnect(),
+
+                _ => {}
+            },
+
+            KeyCode::Esc => context.set_focus("id", "app"),
+
+            _ => {}
+        }
+    }
+
+    fn message(
+        &mut self,
+        message: Self::Message,
+        state: &mut Self::State,
+        elements: Elements<'_, '_>,
+        _: Context<'_, Self::State>,
+    ) {
+        if let Ok(console_message) = serde_json::from_str::<WebSocketConsoleMessages>(&message) {
+            match console_message {
+                WebSocketConsoleMessages::Connect(project, endpoint) => {
+                    self.connect(project, endpoint, state, elements);
+                }
+            }
+
+            return;
+        }
+
+        if let Some(update) = TextAreaMessages::input_update(&message) {
+            self.message = update.value;
+        }
+    }
+}
+
+impl DashboardMessageHandler for WebSocketConsole {
+    fn handle_message(
+        value: CommonVal<'_>,
+        ident: impl Into<String>,
+        state: &mut DashboardState,
+        _: Context<'_, DashboardState>,
+        _: Elements<'_, '_>,
+        _: Ref<'_, HashMap<String, ComponentId<String>>>,
+    ) {
+        let event: String = ident.into();
+
+        #[allow(clippy::single_match)]
+        match event.as_str() {
+            "websocket_console__snippets" => {
+                let value = &*value.to_common_str();
+                if let Ok(snippets) = serde_json::from_str::<Vec<String>>(value) {
+                    let mut endpoint = state.endpoint.to_mut();
+                    endpoint.snippets = List::from_iter(snippets);
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
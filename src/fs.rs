@@ -1,4 +1,8 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 use directories::{ProjectDirs, UserDirs};
 
@@ -19,6 +23,22 @@ pub fn get_app_dir(path: &str) -> anyhow::Result<PathBuf> {
     get_project_directory("Tome", path)
 }
 
+/// Writes to a temporary file next to `path` and renames it over `path`, so a
+/// crash mid-write leaves the previous contents in place
+pub fn write_atomically(path: &Path, contents: &str) -> anyhow::Result<()> {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+
+    let mut file = File::create(&temp_path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+
+    fs::rename(&temp_path, path)?;
+
+    Ok(())
+}
+
 pub fn get_documents_dir() -> anyhow::Result<PathBuf> {
     let user_dirs = UserDirs::new();
     let dirs = user_dirs.ok_or(Err(anyhow::Error::msg("Could not get user directories")));
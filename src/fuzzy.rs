@@ -0,0 +1,208 @@
+/// How well a query matched a text and which characters of the text it matched
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i32,
+
+    /// Char indices into the text, in order
+    pub positions: Vec<usize>,
+}
+
+const MATCH_SCORE: i32 = 16;
+const CONSECUTIVE_BONUS: i32 = 15;
+const BOUNDARY_BONUS: i32 = 10;
+
+// NOTE: Characters skipped before the first match cost less than gaps between matches,
+// so a match late in a long URL still beats no match at all
+const LEADING_GAP_DIVISOR: i32 = 4;
+
+/// Matches at the start of a word, like the `u` in `list_users` or `listUsers`
+fn boundary_bonus(text: &[char], index: usize) -> i32 {
+    let Some(previous) = index.checked_sub(1).map(|previous| text[previous]) else {
+        return BOUNDARY_BONUS;
+    };
+
+    let current = text[index];
+    let starts_word = !previous.is_alphanumeric() && current.is_alphanumeric();
+    let camel_case = previous.is_lowercase() && current.is_uppercase();
+
+    match starts_word || camel_case {
+        true => BOUNDARY_BONUS,
+        false => 0,
+    }
+}
+
+fn fold_case(char: char) -> char {
+    char.to_lowercase().next().unwrap_or(char)
+}
+
+/// Finds the query's characters in order in the text, ignoring case and whitespace
+/// in the query, and scores the best placement. Runs of consecutive characters and
+/// characters at the start of words score higher, skipped characters cost a point each.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|char| !char.is_whitespace())
+        .map(fold_case)
+        .collect();
+
+    if query.is_empty() {
+        return Some(FuzzyMatch::default());
+    }
+
+    let text: Vec<char> = text.chars().collect();
+    let folded: Vec<char> = text.iter().copied().map(fold_case).collect();
+
+    // NOTE: scores[i][j] is the best score with query[i] matched at text[j],
+    // and previous[i][j] where query[i - 1] was matched for it
+    let mut scores: Vec<Vec<Option<i32>>> = vec![vec![None; text.len()]; query.len()];
+    let mut previous: Vec<Vec<usize>> = vec![vec![0; text.len()]; query.len()];
+
+    for (query_index, query_char) in query.iter().enumerate() {
+        // The best of scores[i - 1][k] + k for k < j, a gap from k to j costs j - k - 1
+        let mut best_gapped: Option<(i32, usize)> = None;
+
+        for text_index in 0..text.len() {
+            if query_index > 0 && text_index > 0 {
+                let candidate = text_index - 1;
+
+                if let Some(score) = scores[query_index - 1][candidate] {
+                    let gapped = score + candidate as i32;
+                    if best_gapped.is_none_or(|(best, _)| gapped > best) {
+                        best_gapped = Some((gapped, candidate));
+                    }
+                }
+            }
+
+            if folded[text_index] != *query_char {
+                continue;
+            }
+
+            let bonus = MATCH_SCORE + boundary_bonus(&text, text_index);
+
+            if query_index == 0 {
+                scores[0][text_index] = Some(bonus - text_index as i32 / LEADING_GAP_DIVISOR);
+                continue;
+            }
+
+            let consecutive = text_index
+                .checked_sub(1)
+                .and_then(|candidate| scores[query_index - 1][candidate])
+                .map(|score| (score + CONSECUTIVE_BONUS, text_index - 1));
+
+            let gapped =
+                best_gapped.map(|(best, candidate)| (best - text_index as i32 + 1, candidate));
+
+            let best = match (consecutive, gapped) {
+                (Some(consecutive), Some(gapped)) if gapped.0 > consecutive.0 => Some(gapped),
+                (Some(consecutive), _) => Some(consecutive),
+                (None, gapped) => gapped,
+            };
+
+            if let Some((score, candidate)) = best {
+                scores[query_index][text_index] = Some(score + bonus);
+                previous[query_index][text_index] = candidate;
+            }
+        }
+    }
+
+    let last = query.len() - 1;
+    let (mut position, score) = scores[last]
+        .iter()
+        .enumerate()
+        .filter_map(|(index, score)| score.map(|score| (index, score)))
+        .max_by_key(|(index, score)| (*score, std::cmp::Reverse(*index)))?;
+
+    let mut positions = vec![position];
+    for query_index in (1..query.len()).rev() {
+        position = previous[query_index][position];
+        positions.push(position);
+    }
+    positions.reverse();
+
+    Some(FuzzyMatch { score, positions })
+}
+
+/// The items the query matches, best first. Items that score the same keep their order.
+pub fn fuzzy_search<T>(
+    query: &str,
+    items: &[T],
+    text: impl Fn(&T) -> String,
+) -> Vec<(usize, FuzzyMatch)> {
+    let mut matches: Vec<(usize, FuzzyMatch)> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| fuzzy_match(query, &text(item)).map(|found| (index, found)))
+        .collect();
+
+    matches.sort_by_key(|(_, found)| std::cmp::Reverse(found.score));
+
+    matches
+}
+
+/// Splits the text into runs of matched and unmatched characters for highlighting
+pub fn highlight_segments(text: &str, positions: &[usize]) -> Vec<(String, bool)> {
+    let mut segments: Vec<(String, bool)> = vec![];
+
+    for (index, char) in text.chars().enumerate() {
+        let matched = positions.contains(&index);
+
+        match segments.last_mut() {
+            Some((segment, segment_matched)) if *segment_matched == matched => segment.push(char),
+            _ => segments.push((char.to_string(), matched)),
+        }
+    }
+
+    segments
+}
+
+#[test]
+fn test_fuzzy_match() {
+    let found = fuzzy_match("lsu", "List Users").unwrap();
+    assert_eq!(found.positions, vec![0, 2, 5]);
+
+    // NOTE: The word start is preferred over the first `u` in the text
+    let found = fuzzy_match("us", "GET /user/users").unwrap();
+    assert_eq!(found.positions, vec![5, 6]);
+
+    assert_eq!(
+        fuzzy_match("USR", "list users").unwrap().positions,
+        vec![5, 6, 8]
+    );
+    assert_eq!(
+        fuzzy_match("get users", "GET Users")
+            .unwrap()
+            .positions
+            .len(),
+        8
+    );
+    assert!(fuzzy_match("xyz", "List Users").is_none());
+    assert!(fuzzy_match("sl", "List").is_none());
+    assert_eq!(fuzzy_match("", "List"), Some(FuzzyMatch::default()));
+}
+
+#[test]
+fn test_fuzzy_search() {
+    let names = [
+        "Update Password",
+        "Delete Current User",
+        "Get Users",
+        "Health",
+    ];
+    let matches = fuzzy_search("user", &names, |name| name.to_string());
+
+    let found: Vec<&str> = matches.iter().map(|(index, _)| names[*index]).collect();
+    assert_eq!(found, vec!["Get Users", "Delete Current User"]);
+
+    let all = fuzzy_search("", &names, |name| name.to_string());
+    assert_eq!(all.len(), names.len());
+    assert_eq!(all[0].0, 0);
+
+    assert_eq!(
+        highlight_segments("Get Users", &matches[0].1.positions),
+        vec![
+            (String::from("Get "), false),
+            (String::from("User"), true),
+            (String::from("s"), false)
+        ]
+    );
+}
@@ -0,0 +1,517 @@
+use std::{
+    collections::HashMap, fmt::Display, fs::File, io::BufReader, str::FromStr, sync::LazyLock,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{fs::get_app_dir, palette::Action};
+
+/// A key and its modifiers, written like `r` or `ctrl+d` in the keymap file
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyBinding {
+    pub key: char,
+    pub ctrl: bool,
+}
+
+impl KeyBinding {
+    pub const fn key(key: char) -> Self {
+        KeyBinding { key, ctrl: false }
+    }
+
+    pub const fn ctrl(key: char) -> Self {
+        KeyBinding { key, ctrl: true }
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (ctrl, key) = match value.split_once('+') {
+            Some((modifier, key)) if modifier.eq_ignore_ascii_case("ctrl") => (true, key),
+            Some((modifier, _)) if !modifier.is_empty() => {
+                return Err(anyhow::Error::msg(format!(
+                    "Unknown modifier \"{modifier}\" in \"{value}\""
+                )))
+            }
+            _ => (false, value),
+        };
+
+        let mut chars = key.chars();
+        match (chars.next(), chars.next()) {
+            (Some(key), None) => Ok(KeyBinding { key, ctrl }),
+            _ => Err(anyhow::Error::msg(format!(
+                "\"{value}\" should be a single key, like \"r\" or \"ctrl+r\""
+            ))),
+        }
+    }
+}
+
+impl TryFrom<String> for KeyBinding {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<KeyBinding> for String {
+    fn from(binding: KeyBinding) -> Self {
+        binding.to_string()
+    }
+}
+
+impl Display for KeyBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.ctrl {
+            true => write!(f, "ctrl+{}", self.key),
+            false => write!(f, "{}", self.key),
+        }
+    }
+}
+
+/// Where a key is pressed, a key can only be bound once per context
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyContext {
+    Dashboard,
+    Commands,
+    Response,
+    TextArea,
+    EndpointsSelector,
+    CurlImport,
+    Confirm,
+    CodeGen,
+    OutputPath,
+}
+
+impl KeyContext {
+    const ALL: [KeyContext; 9] = [
+        KeyContext::Dashboard,
+        KeyContext::Commands,
+        KeyContext::Response,
+        KeyContext::TextArea,
+        KeyContext::EndpointsSelector,
+        KeyContext::CurlImport,
+        KeyContext::Confirm,
+        KeyContext::CodeGen,
+        KeyContext::OutputPath,
+    ];
+
+    /// Plain keys are typed into these contexts, only ctrl keys reach the bindings
+    fn is_typed(&self) -> bool {
+        matches!(self, KeyContext::TextArea | KeyContext::OutputPath)
+    }
+
+    fn default_bindings(&self) -> Vec<(Action, KeyBinding)> {
+        let bindings = match self {
+            KeyContext::Dashboard => vec![
+                (Action::SendRequest, 'r'),
+                (Action::SaveProject, 's'),
+                (Action::SaveEndpoint, 'i'),
+                (Action::NewEndpoint, 't'),
+                (Action::NewProject, 'w'),
+                (Action::SwitchProject, 'p'),
+                (Action::SwitchEndpoint, 'e'),
+                (Action::RenameEndpoint, 'n'),
+                (Action::RenameProject, 'j'),
+                (Action::EditUrl, 'u'),
+                (Action::ChangeMethod, 'm'),
+                (Action::CycleEndpointKind, 'k'),
+                (Action::EditRequestHeaders, 'd'),
+                (Action::ToggleBodyMode, 'g'),
+                (Action::LoadGraphQLSchema, 'l'),
+                (Action::Yank, 'y'),
+                (Action::FocusBody, 'b'),
+                (Action::ShowHeaders, 'h'),
+                (Action::AddHeader, 'a'),
+                (Action::SaveResponseBody, 'v'),
+                (Action::ToggleHexDump, 'x'),
+                (Action::NextTab, ']'),
+                (Action::PreviousTab, '['),
+                (Action::CloseTab, '-'),
+                (Action::OpenOptions, 'o'),
+                (Action::OpenCommands, 'c'),
+                (Action::OpenCommandPalette, ':'),
+                (Action::Quit, 'q'),
+            ],
+
+            KeyContext::Commands => vec![
+                (Action::GenerateCode, 'g'),
+                (Action::ExportPostman, 'e'),
+                (Action::ImportOpenApi, 'i'),
+                (Action::ExportOpenApi, 'o'),
+                (Action::ImportCurl, 'c'),
+                (Action::EndpointSettings, 's'),
+                (Action::ProjectSettings, 'p'),
+                (Action::ProxySettings, 'x'),
+            ],
+
+            KeyContext::Response => {
+                return vec![
+                    (Action::ScrollDown, KeyBinding::ctrl('d')),
+                    (Action::ScrollUp, KeyBinding::ctrl('u')),
+                    (Action::NextMatch, KeyBinding::ctrl('n')),
+                    (Action::PreviousMatch, KeyBinding::ctrl('p')),
+                    (Action::FilterResponse, KeyBinding::key('f')),
+                    (Action::PauseStream, KeyBinding::key('p')),
+                    (Action::StopStream, KeyBinding::key('s')),
+                ]
+            }
+
+            KeyContext::TextArea => {
+                return vec![
+                    (Action::ScrollDown, KeyBinding::ctrl('d')),
+                    (Action::ScrollUp, KeyBinding::ctrl('u')),
+                ]
+            }
+
+            KeyContext::EndpointsSelector => vec![
+                (Action::CursorDown, 'j'),
+                (Action::CursorUp, 'k'),
+                (Action::Search, '/'),
+                (Action::ExpandFolder, 'l'),
+                (Action::EditFolderHeaders, 'h'),
+                (Action::MoveEndpoint, 'm'),
+                (Action::DeleteEndpoint, 'd'),
+            ],
+
+            KeyContext::CurlImport => vec![
+                (Action::EditCommand, 'e'),
+                (Action::PasteCommand, 'p'),
+                (Action::Submit, 's'),
+                (Action::Cancel, 'c'),
+            ],
+
+            KeyContext::Confirm => vec![
+                (Action::Confirm, 'y'),
+                (Action::Deny, 'n'),
+                (Action::SaveChanges, 's'),
+                (Action::DiscardChanges, 'd'),
+                (Action::Cancel, 'c'),
+            ],
+
+            KeyContext::CodeGen => vec![
+                (Action::GenerateRust, 'r'),
+                (Action::GenerateReqwest, 'q'),
+                (Action::GenerateTypeScript, 't'),
+                (Action::GenerateJavaScript, 'j'),
+                (Action::GeneratePythonRequests, 'y'),
+                (Action::GeneratePythonHttpx, 'x'),
+                (Action::GenerateGo, 'g'),
+                (Action::GeneratePhp, 'p'),
+                (Action::GenerateCurl, 'c'),
+                (Action::GenerateHttpie, 'h'),
+                (Action::GenerateWget, 'w'),
+            ],
+
+            KeyContext::OutputPath => return vec![(Action::CompletePath, KeyBinding::ctrl('n'))],
+        };
+
+        bindings
+            .into_iter()
+            .map(|(action, key)| (action, KeyBinding::key(key)))
+            .collect()
+    }
+}
+
+/// The keys of every action, the defaults with the keymap file's bindings on top
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    bindings: HashMap<KeyContext, Vec<(Action, KeyBinding)>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = KeyContext::ALL
+            .iter()
+            .map(|context| (*context, context.default_bindings()))
+            .collect();
+
+        Keymap { bindings }
+    }
+}
+
+impl Keymap {
+    /// Applies the bindings of a keymap file, which only lists the keys it changes
+    pub fn with_overrides(
+        overrides: HashMap<KeyContext, HashMap<Action, KeyBinding>>,
+    ) -> anyhow::Result<Self> {
+        let mut keymap = Keymap::default();
+
+        for (context, actions) in overrides {
+            let bindings = keymap.bindings.entry(context).or_default();
+
+            for (action, binding) in actions {
+                let Some(entry) = bindings.iter_mut().find(|(bound, _)| *bound == action) else {
+                    return Err(anyhow::Error::msg(format!(
+                        "{} can not be bound in {}",
+                        action_name(action),
+                        context_name(context)
+                    )));
+                };
+
+                entry.1 = binding;
+            }
+        }
+
+        keymap.validate()?;
+
+        Ok(keymap)
+    }
+
+    /// Checks that no key is bound twice in the same context
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let mut errors = vec![];
+
+        for context in KeyContext::ALL {
+            let bindings = self.bindings(context);
+
+            for (index, (action, binding)) in bindings.iter().enumerate() {
+                let conflict = bindings[..index]
+                    .iter()
+                    .find(|(_, earlier)| earlier == binding);
+
+                if let Some((earlier, _)) = conflict {
+                    errors.push(format!(
+                        "\"{binding}\" is bound to both {} and {} in {}",
+                        action_name(*earlier),
+                        action_name(*action),
+                        context_name(context)
+                    ));
+                }
+
+                if context.is_typed() && !binding.ctrl {
+                    errors.push(format!(
+                        "\"{binding}\" for {} in {} needs ctrl",
+                        action_name(*action),
+                        context_name(context)
+                    ));
+                }
+            }
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(anyhow::Error::msg(errors.join("\n"))),
+        }
+    }
+
+    fn bindings(&self, context: KeyContext) -> &[(Action, KeyBinding)] {
+        self.bindings.get(&context).map_or(&[], Vec::as_slice)
+    }
+
+    /// The action a key press runs in the context
+    pub fn action(&self, context: KeyContext, binding: KeyBinding) -> Option<Action> {
+        self.bindings(context)
+            .iter()
+            .find(|(_, bound)| *bound == binding)
+            .map(|(action, _)| *action)
+    }
+
+    pub fn binding(&self, context: KeyContext, action: Action) -> Option<KeyBinding> {
+        self.bindings(context)
+            .iter()
+            .find(|(bound, _)| *bound == action)
+            .map(|(_, binding)| *binding)
+    }
+
+    /// The keys to press from the dashboard, like `r` or `c g`
+    pub fn keybinding(&self, action: Action) -> String {
+        if let Some(binding) = self.binding(KeyContext::Dashboard, action) {
+            return binding.to_string();
+        }
+
+        let commands = self.binding(KeyContext::Dashboard, Action::OpenCommands);
+        match (commands, self.binding(KeyContext::Commands, action)) {
+            (Some(commands), Some(binding)) => format!("{commands} {binding}"),
+            _ => String::new(),
+        }
+    }
+
+    /// A menu label with the action's key marked, like `(S)ave Project`
+    pub fn label(&self, context: KeyContext, action: Action, title: &str) -> String {
+        match self.binding(context, action) {
+            Some(binding) => menu_label(title, binding),
+            None => title.to_string(),
+        }
+    }
+}
+
+/// Marks the key in the title, preferring the start of a word, or puts it in front
+pub fn menu_label(title: &str, binding: KeyBinding) -> String {
+    let chars: Vec<char> = title.chars().collect();
+    let matches = |c: &char| c.to_lowercase().eq(binding.key.to_lowercase());
+
+    let word_start = (0..chars.len())
+        .find(|index| (*index == 0 || chars[index - 1] == ' ') && matches(&chars[*index]));
+    let position = word_start.or_else(|| chars.iter().position(matches));
+
+    match (binding.ctrl, position) {
+        (false, Some(position)) => {
+            let before: String = chars[..position].iter().collect();
+            let after: String = chars[position + 1..].iter().collect();
+
+            format!("{before}({}){after}", chars[position])
+        }
+
+        _ => format!("({binding}) {title}"),
+    }
+}
+
+fn action_name(action: Action) -> String {
+    serde_json::to_string(&action)
+        .unwrap_or_default()
+        .trim_matches('"')
+        .to_string()
+}
+
+fn context_name(context: KeyContext) -> String {
+    serde_json::to_string(&context)
+        .unwrap_or_default()
+        .trim_matches('"')
+        .to_string()
+}
+
+/// Reads `keymap.json` from the options directory, the defaults are used without one
+pub fn load_keymap() -> anyhow::Result<Keymap> {
+    let mut path = get_app_dir("options")?;
+    path.push("keymap.json");
+
+    let Ok(file) = File::open(&path) else {
+        return Ok(Keymap::default());
+    };
+
+    let overrides = serde_json::from_reader(BufReader::new(file))
+        .map_err(|error| anyhow::Error::msg(format!("Invalid keymap.json: {error}")))?;
+
+    Keymap::with_overrides(overrides)
+}
+
+// NOTE: The keymap file is read once at startup and shared by every component. A
+// keymap with errors is not applied, the defaults are used until it's fixed
+static KEYMAP: LazyLock<(Keymap, Option<String>)> = LazyLock::new(|| match load_keymap() {
+    Ok(keymap) => (keymap, None),
+    Err(error) => (Keymap::default(), Some(error.to_string())),
+});
+
+/// The keymap, or the defaults when the keymap file has errors
+pub fn get_keymap() -> Keymap {
+    KEYMAP.0.clone()
+}
+
+/// Why the keymap file was not applied, shown by the dashboard at startup
+pub fn keymap_error() -> Option<&'static str> {
+    KEYMAP.1.as_deref()
+}
+
+#[test]
+fn test_key_binding() {
+    assert_eq!("r".parse::<KeyBinding>().unwrap(), KeyBinding::key('r'));
+    assert_eq!(
+        "ctrl+d".parse::<KeyBinding>().unwrap(),
+        KeyBinding::ctrl('d')
+    );
+    assert_eq!("+".parse::<KeyBinding>().unwrap(), KeyBinding::key('+'));
+    assert_eq!(KeyBinding::ctrl('d').to_string(), "ctrl+d");
+    assert!("alt+d".parse::<KeyBinding>().is_err());
+    assert!("enter".parse::<KeyBinding>().is_err());
+}
+
+#[test]
+fn test_keymap() {
+    let keymap = Keymap::default();
+    assert!(keymap.validate().is_ok());
+    assert_eq!(
+        keymap.action(KeyContext::Dashboard, KeyBinding::key('r')),
+        Some(Action::SendRequest)
+    );
+    assert_eq!(keymap.keybinding(Action::GenerateCode), "c g");
+
+    let overrides = serde_json::from_str(
+        r#"{ "dashboard": { "send_request": "ctrl+r", "open_commands": "C" } }"#,
+    )
+    .unwrap();
+    let keymap = Keymap::with_overrides(overrides).unwrap();
+    assert_eq!(
+        keymap.action(KeyContext::Dashboard, KeyBinding::key('r')),
+        None
+    );
+    assert_eq!(keymap.keybinding(Action::SendRequest), "ctrl+r");
+    assert_eq!(keymap.keybinding(Action::ImportCurl), "C c");
+
+    let conflict = serde_json::from_str(r#"{ "dashboard": { "send_request": "s" } }"#).unwrap();
+    assert!(Keymap::with_overrides(conflict).is_err());
+
+    let wrong_context = serde_json::from_str(r#"{ "commands": { "quit": "z" } }"#).unwrap();
+    assert!(Keymap::with_overrides(wrong_context).is_err());
+
+    let typed = serde_json::from_str(r#"{ "text_area": { "scroll_up": "k" } }"#).unwrap();
+    assert!(Keymap::with_overrides(typed).is_err());
+
+    let typed = serde_json::from_str(r#"{ "output_path": { "complete_path": "t" } }"#).unwrap();
+    assert!(Keymap::with_overrides(typed).is_err());
+}
+
+#[test]
+fn test_window_keymaps() {
+    let overrides = serde_json::from_str(
+        r#"{
+            "endpoints_selector": { "delete_endpoint": "x" },
+            "confirm": { "confirm": "o" },
+            "code_gen": { "generate_reqwest": "R" }
+        }"#,
+    )
+    .unwrap();
+    let keymap = Keymap::with_overrides(overrides).unwrap();
+
+    assert_eq!(
+        keymap.action(KeyContext::EndpointsSelector, KeyBinding::key('x')),
+        Some(Action::DeleteEndpoint)
+    );
+    assert_eq!(
+        keymap.action(KeyContext::EndpointsSelector, KeyBinding::key('d')),
+        None
+    );
+    assert_eq!(
+        keymap.action(KeyContext::Confirm, KeyBinding::key('o')),
+        Some(Action::Confirm)
+    );
+    assert_eq!(
+        keymap.action(KeyContext::CurlImport, KeyBinding::key('p')),
+        Some(Action::PasteCommand)
+    );
+    assert_eq!(
+        keymap.label(KeyContext::CodeGen, Action::GenerateReqwest, "Rust Reqwest"),
+        "(R)ust Reqwest"
+    );
+
+    let conflict = serde_json::from_str(r#"{ "confirm": { "deny": "y" } }"#).unwrap();
+    assert!(Keymap::with_overrides(conflict).is_err());
+}
+
+#[test]
+fn test_menu_label() {
+    assert_eq!(
+        menu_label("Save Project", KeyBinding::key('s')),
+        "(S)ave Project"
+    );
+    assert_eq!(
+        menu_label("Save Endpoint", KeyBinding::key('i')),
+        "Save Endpo(i)nt"
+    );
+    assert_eq!(
+        menu_label("Swap Project", KeyBinding::key('p')),
+        "Swap (P)roject"
+    );
+    assert_eq!(
+        menu_label("Proxy Settings", KeyBinding::key('x')),
+        "Pro(x)y Settings"
+    );
+    assert_eq!(menu_label("Palette", KeyBinding::key(':')), "(:) Palette");
+    assert_eq!(
+        menu_label("Save Project", KeyBinding::ctrl('s')),
+        "(ctrl+s) Save Project"
+    );
+}
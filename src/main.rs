@@ -8,8 +8,12 @@ mod code_gen;
 mod compatibility;
 mod components;
 mod fs;
+mod fuzzy;
+mod keymap;
 mod messages;
 mod options;
+mod output;
+mod palette;
 mod projects;
 mod requests;
 mod theme;
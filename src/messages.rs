@@ -1,2 +1,4 @@
 pub(crate) mod confirm_delete_endpoint;
 pub(crate) mod confirm_delete_project;
+pub(crate) mod confirm_overwrite;
+pub(crate) mod confirm_unsaved_changes;
@@ -0,0 +1,21 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::output::PendingOutput;
+
+use super::confirm_delete_project::ComponentMessage;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfirmOverwrite {
+    pub path: PathBuf,
+    pub output: PendingOutput,
+    pub title: String,
+    pub message: String,
+}
+
+impl ComponentMessage for ConfirmOverwrite {
+    fn get_message_type(&self) -> String {
+        String::from("confirm_overwrite")
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+use super::confirm_delete_project::ComponentMessage;
+
+/// What was about to happen when unsaved changes were found, it continues after
+/// the changes are saved or discarded
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnsavedChangesTransition {
+    NewEndpoint,
+    NewProject,
+    CloseTab,
+    Quit,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfirmUnsavedChanges {
+    pub transition: UnsavedChangesTransition,
+    pub title: String,
+    pub message: String,
+}
+
+impl ComponentMessage for ConfirmUnsavedChanges {
+    fn get_message_type(&self) -> String {
+        String::from("confirm_unsaved_changes")
+    }
+}
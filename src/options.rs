@@ -15,6 +15,29 @@ const SYNTAX_THEMES_LIST: &str = include_str!("../themes/themes.txt");
 pub struct Options {
     pub syntax_theme: String,
     pub app_theme_name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxySettings>,
+
+    /// Saves the endpoint and project after edits, once both are named
+    #[serde(default)]
+    pub autosave: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProxySettings {
+    /// Proxy URL, either http:// or socks5://, https:// proxies are connected to as http://
+    pub url: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+
+    /// Hosts that are requested directly, subdomains are matched as well
+    #[serde(default)]
+    pub no_proxy: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -41,6 +64,8 @@ pub fn get_default_options() -> Options {
     Options {
         syntax_theme: String::from(DEFAULT_SYNTAX_THEME),
         app_theme_name: String::from(DEFAULT_APP_THEME),
+        proxy: None,
+        autosave: false,
     }
 }
 
@@ -0,0 +1,211 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{is_separator, Path, PathBuf},
+};
+
+use directories::UserDirs;
+use serde::{Deserialize, Serialize};
+
+use crate::fs::{get_app_dir, get_documents_dir};
+
+/// A generated file that hasn't been written yet, the user picks where it goes
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputFile {
+    pub file_name: String,
+    pub contents: String,
+}
+
+impl OutputFile {
+    pub fn new(file_name: impl Into<String>, contents: impl Into<String>) -> Self {
+        OutputFile {
+            file_name: file_name.into(),
+            contents: contents.into(),
+        }
+    }
+}
+
+/// An output file waiting on the path prompt, with what to report once written
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingOutput {
+    pub project_name: String,
+    pub title: String,
+    pub file: OutputFile,
+}
+
+/// Tab completion of the directory at the end of a typed path
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Completion {
+    pub text: String,
+    pub candidates: Vec<String>,
+}
+
+fn output_dirs_path() -> anyhow::Result<PathBuf> {
+    Ok(get_app_dir("options")?.join("output_dirs.json"))
+}
+
+fn load_output_dirs() -> HashMap<String, PathBuf> {
+    output_dirs_path()
+        .and_then(|path| Ok(fs::read_to_string(path)?))
+        .and_then(|json| Ok(serde_json::from_str(&json)?))
+        .unwrap_or_default()
+}
+
+/// The directory the project's last output was written to, or the documents dir
+pub fn get_output_dir(project_name: &str) -> PathBuf {
+    load_output_dirs()
+        .remove(project_name)
+        .filter(|dir| dir.is_dir())
+        .or_else(|| get_documents_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+pub fn remember_output_dir(project_name: &str, dir: &Path) -> anyhow::Result<()> {
+    let mut output_dirs = load_output_dirs();
+    output_dirs.insert(project_name.to_string(), dir.to_path_buf());
+
+    fs::write(output_dirs_path()?, serde_json::to_string(&output_dirs)?)?;
+
+    Ok(())
+}
+
+/// What the path prompt starts with, the file name in the remembered directory
+pub fn default_output_path(output: &PendingOutput) -> String {
+    get_output_dir(&output.project_name)
+        .join(&output.file.file_name)
+        .to_string_lossy()
+        .to_string()
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    let home = UserDirs::new().map(|user_dirs| user_dirs.home_dir().to_path_buf());
+
+    match (path.strip_prefix('~'), home) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with(is_separator) => {
+            home.join(rest.trim_start_matches(is_separator))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+/// The file a typed path refers to, directories get the output's file name
+pub fn resolve_output_path(input: &str, file_name: &str) -> PathBuf {
+    let input = input.trim();
+    let path = expand_home(input);
+
+    if input.is_empty() || input.ends_with(is_separator) || path.is_dir() {
+        return path.join(file_name);
+    }
+
+    path
+}
+
+/// Writes the output, creating any missing directories, and remembers where it went
+pub fn write_output(path: &Path, output: &PendingOutput) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+        remember_output_dir(&output.project_name, dir)?;
+    }
+
+    fs::write(path, &output.file.contents)?;
+
+    Ok(())
+}
+
+/// Completes the last segment of the path to the subdirectories it's a prefix of,
+/// as far as they agree. Hidden directories are only offered after a typed dot.
+pub fn complete_directory(input: &str) -> Completion {
+    let split = input.rfind(is_separator).map_or(0, |index| index + 1);
+    let (parent, partial) = input.split_at(split);
+
+    let dir = match parent {
+        "" => PathBuf::from("."),
+        parent => expand_home(parent),
+    };
+
+    let mut candidates: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter(|name| name.starts_with(partial))
+                .filter(|name| partial.starts_with('.') || !name.starts_with('.'))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    candidates.sort();
+
+    let text = match candidates.as_slice() {
+        [] => input.to_string(),
+        [directory] => format!("{parent}{directory}{}", std::path::MAIN_SEPARATOR),
+        [first, rest @ ..] => {
+            let common = rest.iter().fold(first.as_str(), |common, candidate| {
+                common_prefix(common, candidate)
+            });
+
+            format!("{parent}{common}")
+        }
+    };
+
+    if candidates.len() == 1 {
+        candidates.clear();
+    }
+
+    Completion { text, candidates }
+}
+
+fn common_prefix<'a>(first: &'a str, second: &str) -> &'a str {
+    let length = first
+        .chars()
+        .zip(second.chars())
+        .take_while(|(first, second)| first == second)
+        .map(|(char, _)| char.len_utf8())
+        .sum();
+
+    &first[..length]
+}
+
+#[test]
+fn test_complete_directory() {
+    let root = std::env::temp_dir().join(format!("output_completion_{}", std::process::id()));
+    for dir in ["exports", "examples", "src", ".git"] {
+        fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    fs::write(root.join("example.json"), "{}").unwrap();
+
+    let root_text = format!("{}{}", root.display(), std::path::MAIN_SEPARATOR);
+    let complete = |typed: &str| complete_directory(&format!("{root_text}{typed}"));
+
+    assert_eq!(
+        complete("s").text,
+        format!("{root_text}src{}", std::path::MAIN_SEPARATOR)
+    );
+    assert!(complete("s").candidates.is_empty());
+
+    assert_eq!(complete("e").text, format!("{root_text}ex"));
+    assert_eq!(complete("e").candidates, vec!["examples", "exports"]);
+
+    assert_eq!(complete("").candidates, vec!["examples", "exports", "src"]);
+    assert_eq!(
+        complete(".").text,
+        format!("{root_text}.git{}", std::path::MAIN_SEPARATOR)
+    );
+    assert_eq!(complete("x").text, format!("{root_text}x"));
+
+    assert_eq!(
+        resolve_output_path(&root_text, "api.ts"),
+        root.join("api.ts")
+    );
+    assert_eq!(
+        resolve_output_path(&root.join("src").to_string_lossy(), "api.ts"),
+        root.join("src").join("api.ts")
+    );
+    assert_eq!(
+        resolve_output_path(&format!("{root_text}client.ts "), "api.ts"),
+        root.join("client.ts")
+    );
+
+    fs::remove_dir_all(root).unwrap();
+}
@@ -0,0 +1,254 @@
+use serde::{Deserialize, Serialize};
+
+use crate::fuzzy::{fuzzy_search, FuzzyMatch};
+
+/// Something the user can do from the keyboard or the command palette
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    SendRequest,
+    SaveProject,
+    SaveEndpoint,
+    NewEndpoint,
+    NewProject,
+    SwitchProject,
+    SwitchEndpoint,
+    RenameEndpoint,
+    RenameProject,
+    EditUrl,
+    ChangeMethod,
+    CycleEndpointKind,
+    EditRequestHeaders,
+    ToggleBodyMode,
+    LoadGraphQLSchema,
+    Yank,
+    OpenOptions,
+    OpenCommands,
+    OpenCommandPalette,
+    FocusBody,
+    ShowHeaders,
+    AddHeader,
+    SaveResponseBody,
+    ToggleHexDump,
+    NextTab,
+    PreviousTab,
+    CloseTab,
+    RecoverProjects,
+    Quit,
+
+    GenerateCode,
+    ExportPostman,
+    ExportOpenApi,
+    ImportOpenApi,
+    ImportCurl,
+    EndpointSettings,
+    ProjectSettings,
+    ProxySettings,
+
+    ScrollDown,
+    ScrollUp,
+    NextMatch,
+    PreviousMatch,
+    FilterResponse,
+    PauseStream,
+    StopStream,
+
+    CursorDown,
+    CursorUp,
+    Search,
+    ExpandFolder,
+    EditFolderHeaders,
+    MoveEndpoint,
+    DeleteEndpoint,
+    EditCommand,
+    PasteCommand,
+    Submit,
+    Cancel,
+    Confirm,
+    Deny,
+    SaveChanges,
+    DiscardChanges,
+    CompletePath,
+
+    GenerateRust,
+    GenerateReqwest,
+    GenerateTypeScript,
+    GenerateJavaScript,
+    GeneratePythonRequests,
+    GeneratePythonHttpx,
+    GenerateGo,
+    GeneratePhp,
+    GenerateCurl,
+    GenerateHttpie,
+    GenerateWget,
+}
+
+/// Where the key of an action is pressed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActionScope {
+    Dashboard,
+
+    /// In the commands window, opened from the dashboard with its own key
+    Commands,
+
+    /// Inside the response or a body editor, not run from the palette
+    Editor,
+
+    /// Inside a floating window, not run from the palette
+    Window,
+}
+
+impl Action {
+    pub fn scope(&self) -> ActionScope {
+        match self {
+            Action::GenerateCode
+            | Action::ExportPostman
+            | Action::ExportOpenApi
+            | Action::ImportOpenApi
+            | Action::ImportCurl
+            | Action::EndpointSettings
+            | Action::ProjectSettings
+            | Action::ProxySettings => ActionScope::Commands,
+
+            Action::ScrollDown
+            | Action::ScrollUp
+            | Action::NextMatch
+            | Action::PreviousMatch
+            | Action::FilterResponse
+            | Action::PauseStream
+            | Action::StopStream => ActionScope::Editor,
+
+            Action::CursorDown
+            | Action::CursorUp
+            | Action::Search
+            | Action::ExpandFolder
+            | Action::EditFolderHeaders
+            | Action::MoveEndpoint
+            | Action::DeleteEndpoint
+            | Action::EditCommand
+            | Action::PasteCommand
+            | Action::Submit
+            | Action::Cancel
+            | Action::Confirm
+            | Action::Deny
+            | Action::SaveChanges
+            | Action::DiscardChanges
+            | Action::CompletePath
+            | Action::GenerateRust
+            | Action::GenerateReqwest
+            | Action::GenerateTypeScript
+            | Action::GenerateJavaScript
+            | Action::GeneratePythonRequests
+            | Action::GeneratePythonHttpx
+            | Action::GenerateGo
+            | Action::GeneratePhp
+            | Action::GenerateCurl
+            | Action::GenerateHttpie
+            | Action::GenerateWget => ActionScope::Window,
+
+            _ => ActionScope::Dashboard,
+        }
+    }
+}
+
+/// An entry of the command palette
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaletteCommand {
+    pub title: String,
+    pub action: Action,
+}
+
+/// The commands the palette lists, new features register theirs in `Default`
+#[derive(Clone, Debug)]
+pub struct CommandRegistry {
+    commands: Vec<PaletteCommand>,
+}
+
+impl CommandRegistry {
+    pub fn empty() -> Self {
+        CommandRegistry { commands: vec![] }
+    }
+
+    pub fn register(&mut self, title: impl Into<String>, action: Action) -> &mut Self {
+        self.commands.push(PaletteCommand {
+            title: title.into(),
+            action,
+        });
+
+        self
+    }
+
+    /// The commands matching the query, best first, all of them for an empty query
+    pub fn search(&self, query: &str) -> Vec<(&PaletteCommand, FuzzyMatch)> {
+        fuzzy_search(query, &self.commands, |command| command.title.clone())
+            .into_iter()
+            .map(|(index, found)| (&self.commands[index], found))
+            .collect()
+    }
+}
+
+impl Default for CommandRegistry {
+    fn default() -> Self {
+        let mut registry = CommandRegistry::empty();
+
+        registry
+            .register("Send request", Action::SendRequest)
+            .register("Save project", Action::SaveProject)
+            .register("Save endpoint", Action::SaveEndpoint)
+            .register("New endpoint", Action::NewEndpoint)
+            .register("New project", Action::NewProject)
+            .register("Switch project", Action::SwitchProject)
+            .register("Switch endpoint", Action::SwitchEndpoint)
+            .register("Rename endpoint", Action::RenameEndpoint)
+            .register("Rename project", Action::RenameProject)
+            .register("Edit URL", Action::EditUrl)
+            .register("Change request method", Action::ChangeMethod)
+            .register("Cycle HTTP / WebSocket / gRPC", Action::CycleEndpointKind)
+            .register("Edit request headers", Action::EditRequestHeaders)
+            .register("Toggle GraphQL request body", Action::ToggleBodyMode)
+            .register("Load GraphQL schema", Action::LoadGraphQLSchema)
+            .register("Copy request as cURL or response body", Action::Yank)
+            .register("Focus request or response body", Action::FocusBody)
+            .register("Show response headers", Action::ShowHeaders)
+            .register("Add header or edit GraphQL variables", Action::AddHeader)
+            .register(
+                "Save response body or preview GraphQL query",
+                Action::SaveResponseBody,
+            )
+            .register("Toggle response hex dump", Action::ToggleHexDump)
+            .register("Next tab", Action::NextTab)
+            .register("Previous tab", Action::PreviousTab)
+            .register("Close tab", Action::CloseTab)
+            .register("Generate code", Action::GenerateCode)
+            .register("Export to Postman", Action::ExportPostman)
+            .register("Export OpenAPI", Action::ExportOpenApi)
+            .register("Import OpenAPI or Postman", Action::ImportOpenApi)
+            .register("Import cURL", Action::ImportCurl)
+            .register("Endpoint settings", Action::EndpointSettings)
+            .register("Project settings", Action::ProjectSettings)
+            .register("Proxy settings", Action::ProxySettings)
+            .register("Open options, change theme", Action::OpenOptions)
+            .register("Open commands", Action::OpenCommands)
+            .register("Recover project files", Action::RecoverProjects)
+            .register("Quit", Action::Quit);
+
+        registry
+    }
+}
+
+#[test]
+fn test_command_registry() {
+    let registry = CommandRegistry::default();
+    assert_eq!(registry.search("").len(), registry.commands.len());
+
+    let (command, _) = &registry.search("gen code")[0];
+    assert_eq!(command.action, Action::GenerateCode);
+    assert_eq!(command.action.scope(), ActionScope::Commands);
+
+    let (command, _) = &registry.search("send")[0];
+    assert_eq!(command.action, Action::SendRequest);
+
+    let mut registry = CommandRegistry::empty();
+    registry.register("Quit", Action::Quit);
+    assert!(registry.search("save").is_empty());
+}
@@ -1,8 +1,22 @@
-use anathema::state::{List, State, Value};
+pub mod folders;
+pub mod tabs;
+
+use anathema::state::{CommonVal, List, State, Value};
 use serde::{Deserialize, Serialize};
-use std::{fs, ops::Deref};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    ops::Deref,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use crate::{
+    fs::{get_app_dir, write_atomically},
+    options::ProxySettings,
+};
 
-use crate::fs::get_app_dir;
+use self::folders::Folder;
 
 // TODO: Fix the default project row color to the correct gray
 pub const DEFAULT_ROW_COLOR: &str = "#333333";
@@ -13,12 +27,28 @@ pub const SELECTED_ROW_COLOR: &str = "#FFFFFF";
 
 pub const DEFAULT_PROJECT_NAME: &str = "Unnamed";
 pub const DEFAULT_ENDPOINT_NAME: &str = "Unnamed";
+pub const DEFAULT_MAX_REDIRECTS: usize = 5;
+
+const PROJECT_EXTENSION: &str = "project";
+const BACKUP_EXTENSION: &str = "bak";
+
+// NOTE: Projects as they were last read from or written to disk, unsaved changes
+// are whatever differs from these
+static SAVED_PROJECTS: Mutex<BTreeMap<String, PersistedProject>> = Mutex::new(BTreeMap::new());
+
+// NOTE: Projects whose backup was already taken, it's kept for the rest of the session
+static BACKED_UP_PROJECTS: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
 
 #[derive(anathema::state::State)]
 pub struct Project {
     pub name: Value<String>,
     pub endpoints: Value<List<Endpoint>>,
     pub row_color: Value<String>,
+    pub tls: Value<TlsSettingsState>,
+    pub proxy: Value<ProxySettingsState>,
+    pub proto_files: Value<List<String>>,
+    pub variables: Value<List<VariableState>>,
+    pub folders: Value<List<FolderState>>,
 }
 
 impl Project {
@@ -27,6 +57,50 @@ impl Project {
             name: String::from(DEFAULT_PROJECT_NAME).into(),
             row_color: DEFAULT_ROW_COLOR.to_string().into(),
             endpoints: List::empty(),
+            tls: TlsSettingsState::new().into(),
+            proxy: ProxySettingsState::new().into(),
+            proto_files: List::empty(),
+            variables: List::empty(),
+            folders: List::empty(),
+        }
+    }
+}
+
+#[derive(Debug, Default, State)]
+pub struct TlsSettingsState {
+    pub ca_certificates: Value<List<String>>,
+    pub client_certificate: Value<String>,
+    pub client_key: Value<String>,
+    pub insecure: Value<bool>,
+}
+
+impl TlsSettingsState {
+    pub fn new() -> Self {
+        TlsSettingsState {
+            ca_certificates: List::empty(),
+            client_certificate: String::from("").into(),
+            client_key: String::from("").into(),
+            insecure: false.into(),
+        }
+    }
+}
+
+/// Per project proxy override, an empty url falls back to the global proxy option
+#[derive(Debug, Default, State)]
+pub struct ProxySettingsState {
+    pub url: Value<String>,
+    pub username: Value<String>,
+    pub password: Value<String>,
+    pub no_proxy: Value<List<String>>,
+}
+
+impl ProxySettingsState {
+    pub fn new() -> Self {
+        ProxySettingsState {
+            url: String::from("").into(),
+            username: String::from("").into(),
+            password: String::from("").into(),
+            no_proxy: List::empty(),
         }
     }
 }
@@ -39,6 +113,15 @@ pub struct Endpoint {
     pub headers: Value<List<HeaderState>>,
     pub body: Value<String>,
     pub row_color: Value<String>,
+    pub follow_redirects: Value<bool>,
+    pub max_redirects: Value<usize>,
+    pub body_mode: Value<BodyMode>,
+    pub graphql_query: Value<String>,
+    pub graphql_variables: Value<String>,
+    pub kind: Value<EndpointKind>,
+    pub snippets: Value<List<String>>,
+    pub grpc_method: Value<String>,
+    pub folder: Value<String>,
 }
 
 impl Endpoint {
@@ -50,6 +133,15 @@ impl Endpoint {
             body: String::from("").into(),
             headers: List::from_iter(get_default_headers()),
             row_color: DEFAULT_ROW_COLOR.to_string().into(),
+            follow_redirects: true.into(),
+            max_redirects: DEFAULT_MAX_REDIRECTS.into(),
+            body_mode: BodyMode::Raw.into(),
+            graphql_query: String::from("").into(),
+            graphql_variables: String::from("").into(),
+            kind: EndpointKind::Http.into(),
+            snippets: List::empty(),
+            grpc_method: String::from("").into(),
+            folder: String::from("").into(),
         }
     }
 
@@ -67,10 +159,100 @@ impl Endpoint {
             body: self.body.to_ref().to_string().into(),
             row_color: DEFAULT_ROW_COLOR.to_string().into(),
             headers: List::from_iter(headers),
+            follow_redirects: (*self.follow_redirects.to_ref()).into(),
+            max_redirects: (*self.max_redirects.to_ref()).into(),
+            body_mode: (*self.body_mode.to_ref()).into(),
+            graphql_query: self.graphql_query.to_ref().to_string().into(),
+            graphql_variables: self.graphql_variables.to_ref().to_string().into(),
+            kind: (*self.kind.to_ref()).into(),
+            snippets: List::from_iter(
+                self.snippets
+                    .to_ref()
+                    .iter()
+                    .map(|snippet| snippet.to_ref().to_string()),
+            ),
+            grpc_method: self.grpc_method.to_ref().to_string().into(),
+            folder: self.folder.to_ref().to_string().into(),
+        }
+    }
+}
+
+/// How the request body of an endpoint is authored and sent
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BodyMode {
+    #[default]
+    #[serde(rename = "raw")]
+    Raw,
+
+    #[serde(rename = "graphql")]
+    GraphQL,
+}
+
+/// Whether an endpoint is a plain HTTP request, a WebSocket connection or a gRPC call
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EndpointKind {
+    #[default]
+    #[serde(rename = "http")]
+    Http,
+
+    #[serde(rename = "websocket")]
+    WebSocket,
+
+    #[serde(rename = "grpc")]
+    Grpc,
+}
+
+impl EndpointKind {
+    /// ws:// and wss:// URLs are always WebSocket endpoints, grpc:// and
+    /// grpcs:// URLs are always gRPC endpoints
+    pub fn for_url(url: &str) -> Option<EndpointKind> {
+        let url = url.trim().to_lowercase();
+
+        if url.starts_with("ws://") || url.starts_with("wss://") {
+            return Some(EndpointKind::WebSocket);
+        }
+
+        if url.starts_with("grpc://") || url.starts_with("grpcs://") {
+            return Some(EndpointKind::Grpc);
+        }
+
+        None
+    }
+}
+
+impl State for EndpointKind {
+    fn to_common(&self) -> Option<CommonVal<'_>> {
+        match self {
+            EndpointKind::Http => Some(CommonVal::Str("http")),
+            EndpointKind::WebSocket => Some(CommonVal::Str("websocket")),
+            EndpointKind::Grpc => Some(CommonVal::Str("grpc")),
+        }
+    }
+}
+
+impl State for BodyMode {
+    fn to_common(&self) -> Option<CommonVal<'_>> {
+        match self {
+            BodyMode::Raw => Some(CommonVal::Str("raw")),
+            BodyMode::GraphQL => Some(CommonVal::Str("graphql")),
         }
     }
 }
 
+/// A `{{name}}` placeholder that is replaced with `value` when a request is sent
+#[derive(Debug, Default, State)]
+pub struct VariableState {
+    pub name: Value<String>,
+    pub value: Value<String>,
+}
+
+/// A folder's settings, see `folders::Folder`
+#[derive(Debug, Default, State)]
+pub struct FolderState {
+    pub path: Value<String>,
+    pub headers: Value<List<HeaderState>>,
+}
+
 #[derive(Debug, Default, State)]
 pub struct HeaderState {
     pub name: Value<String>,
@@ -86,27 +268,152 @@ impl HeaderState {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PersistedProject {
     pub name: String,
     pub endpoints: Vec<PersistedEndpoint>,
+
+    #[serde(default)]
+    pub tls: TlsSettings,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxySettings>,
+
+    /// .proto files describing the services called by gRPC endpoints
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub proto_files: Vec<String>,
+
+    /// Values for the `{{name}}` placeholders used in endpoint URLs, headers and bodies
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<Variable>,
+
+    /// Settings of the folders endpoints are organized in
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub folders: Vec<Folder>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TlsSettings {
+    /// Extra PEM files with CA certificates, trusted on top of the built in roots
+    #[serde(default)]
+    pub ca_certificates: Vec<String>,
+
+    /// PEM file with the client certificate chain used for mutual TLS
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_certificate: Option<String>,
+
+    /// PEM file with the private key for the client certificate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_key: Option<String>,
+
+    /// Skips server certificate verification entirely
+    #[serde(default)]
+    pub insecure: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PersistedEndpoint {
     pub name: String,
     pub url: String,
     pub method: String,
     pub headers: Vec<Header>,
     pub body: String,
+
+    #[serde(default = "default_follow_redirects")]
+    pub follow_redirects: bool,
+
+    #[serde(default = "default_max_redirects")]
+    pub max_redirects: usize,
+
+    #[serde(default)]
+    pub body_mode: BodyMode,
+
+    #[serde(default, skip_serializing_if = "GraphQLBody::is_empty")]
+    pub graphql: GraphQLBody,
+
+    #[serde(default)]
+    pub kind: EndpointKind,
+
+    /// Saved WebSocket messages that can be loaded into the composer
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub snippets: Vec<String>,
+
+    /// Method called by gRPC endpoints, as `package.Service/Method`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub grpc_method: String,
+
+    /// Path of the folder the endpoint is in, like `Users/Admin`, empty at the top level
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub folder: String,
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphQLBody {
+    #[serde(default)]
+    pub query: String,
+
+    /// JSON object with the query variables, kept as the text that was typed
+    #[serde(default)]
+    pub variables: String,
+}
+
+impl GraphQLBody {
+    pub fn is_empty(&self) -> bool {
+        self.query.is_empty() && self.variables.is_empty()
+    }
+}
+
+/// A new endpoint with the same defaults as `Endpoint::new()`, for endpoints
+/// that are built outside of the UI like imports
+impl Default for PersistedEndpoint {
+    fn default() -> Self {
+        PersistedEndpoint {
+            name: DEFAULT_ENDPOINT_NAME.to_string(),
+            url: String::new(),
+            method: String::from("GET"),
+            headers: vec![
+                Header {
+                    name: "user-agent".to_string(),
+                    value: "tome-tui".to_string(),
+                },
+                Header {
+                    name: "content-type".to_string(),
+                    value: "application/json".to_string(),
+                },
+            ],
+            body: String::new(),
+            follow_redirects: default_follow_redirects(),
+            max_redirects: default_max_redirects(),
+            body_mode: BodyMode::Raw,
+            graphql: GraphQLBody::default(),
+            kind: EndpointKind::Http,
+            snippets: vec![],
+            grpc_method: String::new(),
+            folder: String::new(),
+        }
+    }
+}
+
+fn default_follow_redirects() -> bool {
+    true
 }
 
-#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+fn default_max_redirects() -> usize {
+    DEFAULT_MAX_REDIRECTS
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Header {
     pub name: String,
     pub value: String,
 }
 
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Variable {
+    pub name: String,
+    pub value: String,
+}
+
 fn get_default_headers() -> Vec<HeaderState> {
     vec![
         HeaderState {
@@ -120,6 +427,36 @@ fn get_default_headers() -> Vec<HeaderState> {
     ]
 }
 
+/// Endpoints are saved by name, so repeated names get a counter
+pub fn unique_endpoint_name(name: &str, endpoints: &[PersistedEndpoint]) -> String {
+    unique_name(name, |candidate| {
+        endpoints.iter().any(|endpoint| endpoint.name == candidate)
+    })
+}
+
+/// Projects are saved to a file named after them, so an imported project gets a
+/// counter instead of replacing a project with the same name
+pub fn unique_project_name(name: &str) -> anyhow::Result<String> {
+    let project_dir = get_app_dir("projects")?;
+
+    Ok(unique_name(name, |candidate| {
+        project_dir
+            .join(format!("{candidate}.{PROJECT_EXTENSION}"))
+            .exists()
+    }))
+}
+
+fn unique_name(name: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(name) {
+        return name.to_string();
+    }
+
+    (2..)
+        .map(|count| format!("{name} ({count})"))
+        .find(|candidate| !taken(candidate))
+        .unwrap_or_default()
+}
+
 pub fn save_project(project: PersistedProject) -> anyhow::Result<()> {
     if project.name.trim() == "" {
         return Err(anyhow::Error::msg("Project must have name"));
@@ -136,21 +473,40 @@ pub fn save_project(project: PersistedProject) -> anyhow::Result<()> {
         return Err(anyhow::Error::msg("Unable to access projects directory"));
     }
 
-    let mut project_dir = dir_result.unwrap();
+    let mut project_path = dir_result.unwrap();
     let serialized_project = serialization_result.unwrap();
-    project_dir.push(format!("{}.project", project.name));
+    project_path.push(format!("{}.{PROJECT_EXTENSION}", project.name));
 
-    let write_result = fs::write(project_dir, serialized_project);
-    if write_result.is_err() {
-        let write_error = write_result.unwrap_err();
+    // NOTE: Only the first save of a session takes a backup, later saves would leave
+    // a version that's seconds old. A file that doesn't parse would replace a good
+    // backup, so it isn't kept
+    if first_save(&project.name) && read_project(&project_path).is_ok() {
+        fs::copy(&project_path, backup_path(&project_path))?;
+    }
 
+    if let Err(write_error) = write_atomically(&project_path, &serialized_project) {
         return Err(anyhow::Error::msg(write_error.to_string()));
     }
 
+    remember_saved(&project);
+
     Ok(())
 }
 
+/// A project file that couldn't be read, listed so it can be recovered
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectFileError {
+    pub path: PathBuf,
+    pub error: String,
+    pub has_backup: bool,
+}
+
 pub fn get_projects() -> anyhow::Result<Vec<PersistedProject>> {
+    load_projects().map(|(projects, _)| projects)
+}
+
+/// Reads every project file, the ones that fail to parse are returned with their error
+pub fn load_projects() -> anyhow::Result<(Vec<PersistedProject>, Vec<ProjectFileError>)> {
     let dir_result = get_app_dir("projects");
     if dir_result.is_err() {
         return Err(anyhow::Error::msg("Unable to access projects directory"));
@@ -160,11 +516,112 @@ pub fn get_projects() -> anyhow::Result<Vec<PersistedProject>> {
 
     let read_dir = fs::read_dir(project_dir)?;
 
-    Ok(read_dir
+    let mut paths = read_dir
         .flatten()
-        .flat_map(|entry| fs::read_to_string(entry.path()))
-        .flat_map(|content| serde_json::from_str::<PersistedProject>(&content))
-        .collect::<Vec<PersistedProject>>())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == PROJECT_EXTENSION))
+        .collect::<Vec<PathBuf>>();
+    paths.sort();
+
+    let mut projects = vec![];
+    let mut errors = vec![];
+    for path in paths {
+        match read_project(&path) {
+            Ok(project) => projects.push(project),
+            Err(error) => errors.push(ProjectFileError {
+                has_backup: backup_path(&path).exists(),
+                error: error.to_string(),
+                path,
+            }),
+        }
+    }
+
+    projects.iter().for_each(remember_saved);
+
+    Ok((projects, errors))
+}
+
+/// Replaces a project file with its backup, as long as the backup parses
+pub fn restore_backup(path: &Path) -> anyhow::Result<PersistedProject> {
+    let backup = backup_path(path);
+    let project = read_project(&backup)
+        .map_err(|error| anyhow::anyhow!("The backup can't be read either: {error}"))?;
+
+    write_atomically(path, &fs::read_to_string(&backup)?)?;
+    remember_saved(&project);
+
+    Ok(project)
+}
+
+fn read_project(path: &Path) -> anyhow::Result<PersistedProject> {
+    let content = fs::read_to_string(path)?;
+
+    Ok(serde_json::from_str::<PersistedProject>(&content)?)
+}
+
+/// The previous version of a project file, kept by the first save of a session
+fn backup_path(path: &Path) -> PathBuf {
+    path.with_extension(format!("{PROJECT_EXTENSION}.{BACKUP_EXTENSION}"))
+}
+
+/// Whether the project wasn't saved yet this session
+fn first_save(name: &str) -> bool {
+    BACKED_UP_PROJECTS
+        .lock()
+        .is_ok_and(|mut backed_up| backed_up.insert(name.to_string()))
+}
+
+fn remember_saved(project: &PersistedProject) {
+    if let Ok(mut saved) = SAVED_PROJECTS.lock() {
+        saved.insert(project.name.clone(), project.clone());
+    }
+}
+
+/// What differs between an open project and endpoint and their saved versions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UnsavedChanges {
+    pub project: bool,
+    pub endpoint: bool,
+}
+
+impl UnsavedChanges {
+    /// Compares against the project as it was last saved, a project that never was
+    /// only counts as changed once it's named or has endpoints, and a new endpoint
+    /// once something was changed
+    pub fn compare(
+        project: &PersistedProject,
+        endpoint: &PersistedEndpoint,
+        saved: Option<&PersistedProject>,
+    ) -> Self {
+        let saved_endpoint = saved.and_then(|saved| {
+            saved
+                .endpoints
+                .iter()
+                .find(|saved_endpoint| saved_endpoint.name == endpoint.name)
+        });
+
+        UnsavedChanges {
+            project: match saved {
+                Some(saved) => saved != project,
+                None => project.name != DEFAULT_PROJECT_NAME || !project.endpoints.is_empty(),
+            },
+            endpoint: match saved_endpoint {
+                Some(saved_endpoint) => saved_endpoint != endpoint,
+                None => *endpoint != PersistedEndpoint::default(),
+            },
+        }
+    }
+
+    pub fn any(&self) -> bool {
+        self.project || self.endpoint
+    }
+}
+
+pub fn unsaved_changes(project: &PersistedProject, endpoint: &PersistedEndpoint) -> UnsavedChanges {
+    match SAVED_PROJECTS.lock() {
+        Ok(saved) => UnsavedChanges::compare(project, endpoint, saved.get(&project.name)),
+        Err(_) => UnsavedChanges::default(),
+    }
 }
 
 #[allow(unused)]
@@ -192,6 +649,22 @@ impl From<&Endpoint> for PersistedEndpoint {
             method: endpoint.method.to_ref().to_string(),
             body: endpoint.body.to_ref().to_string(),
             headers,
+            follow_redirects: *endpoint.follow_redirects.to_ref(),
+            max_redirects: *endpoint.max_redirects.to_ref(),
+            body_mode: *endpoint.body_mode.to_ref(),
+            graphql: GraphQLBody {
+                query: endpoint.graphql_query.to_ref().to_string(),
+                variables: endpoint.graphql_variables.to_ref().to_string(),
+            },
+            kind: *endpoint.kind.to_ref(),
+            snippets: endpoint
+                .snippets
+                .to_ref()
+                .iter()
+                .map(|snippet| snippet.to_ref().to_string())
+                .collect(),
+            grpc_method: endpoint.grpc_method.to_ref().to_string(),
+            folder: endpoint.folder.to_ref().to_string(),
         }
     }
 }
@@ -209,7 +682,125 @@ impl From<&Project> for PersistedProject {
             });
 
         let name = project.name.to_ref().clone();
-        PersistedProject { name, endpoints }
+        let tls = project.tls.to_ref().deref().into();
+        let proxy = project.proxy.to_ref().deref().into();
+        let proto_files = project
+            .proto_files
+            .to_ref()
+            .iter()
+            .map(|file| file.to_ref().to_string())
+            .collect();
+        let variables = project
+            .variables
+            .to_ref()
+            .iter()
+            .map(|variable| {
+                let variable = variable.to_ref();
+                let name = variable.name.to_ref().to_string();
+                let value = variable.value.to_ref().to_string();
+
+                Variable { name, value }
+            })
+            .collect();
+        let folders = project
+            .folders
+            .to_ref()
+            .iter()
+            .map(|folder| {
+                let folder = folder.to_ref();
+                let path = folder.path.to_ref().to_string();
+                let headers = folder
+                    .headers
+                    .to_ref()
+                    .iter()
+                    .map(|header| header.to_ref().deref().into())
+                    .collect();
+
+                Folder { path, headers }
+            })
+            .collect();
+
+        PersistedProject {
+            name,
+            endpoints,
+            tls,
+            proxy,
+            proto_files,
+            variables,
+            folders,
+        }
+    }
+}
+
+impl From<&TlsSettingsState> for TlsSettings {
+    fn from(tls_state: &TlsSettingsState) -> Self {
+        let ca_certificates = tls_state
+            .ca_certificates
+            .to_ref()
+            .iter()
+            .map(|path| path.to_ref().to_string())
+            .collect();
+
+        let client_certificate = tls_state.client_certificate.to_ref().to_string();
+        let client_key = tls_state.client_key.to_ref().to_string();
+
+        TlsSettings {
+            ca_certificates,
+            client_certificate: (!client_certificate.is_empty()).then_some(client_certificate),
+            client_key: (!client_key.is_empty()).then_some(client_key),
+            insecure: *tls_state.insecure.to_ref(),
+        }
+    }
+}
+
+impl From<&TlsSettings> for TlsSettingsState {
+    fn from(tls: &TlsSettings) -> Self {
+        TlsSettingsState {
+            ca_certificates: List::from_iter(tls.ca_certificates.clone()),
+            client_certificate: tls.client_certificate.clone().unwrap_or_default().into(),
+            client_key: tls.client_key.clone().unwrap_or_default().into(),
+            insecure: tls.insecure.into(),
+        }
+    }
+}
+
+impl From<&ProxySettingsState> for Option<ProxySettings> {
+    fn from(proxy_state: &ProxySettingsState) -> Self {
+        let url = proxy_state.url.to_ref().to_string();
+        if url.is_empty() {
+            return None;
+        }
+
+        let username = proxy_state.username.to_ref().to_string();
+        let password = proxy_state.password.to_ref().to_string();
+        let no_proxy = proxy_state
+            .no_proxy
+            .to_ref()
+            .iter()
+            .map(|host| host.to_ref().to_string())
+            .collect();
+
+        Some(ProxySettings {
+            url,
+            username: (!username.is_empty()).then_some(username),
+            password: (!password.is_empty()).then_some(password),
+            no_proxy,
+        })
+    }
+}
+
+impl From<Option<&ProxySettings>> for ProxySettingsState {
+    fn from(proxy: Option<&ProxySettings>) -> Self {
+        let Some(proxy) = proxy else {
+            return ProxySettingsState::new();
+        };
+
+        ProxySettingsState {
+            url: proxy.url.clone().into(),
+            username: proxy.username.clone().unwrap_or_default().into(),
+            password: proxy.password.clone().unwrap_or_default().into(),
+            no_proxy: List::from_iter(proxy.no_proxy.clone()),
+        }
     }
 }
 
@@ -235,6 +826,19 @@ impl From<&PersistedProject> for Project {
             name: persisted_project.name.clone().into(),
             row_color: DEFAULT_ROW_COLOR.to_string().into(),
             endpoints,
+            tls: TlsSettingsState::from(&persisted_project.tls).into(),
+            proxy: ProxySettingsState::from(persisted_project.proxy.as_ref()).into(),
+            proto_files: List::from_iter(persisted_project.proto_files.clone()),
+            variables: List::from_iter(persisted_project.variables.iter().map(|variable| {
+                VariableState {
+                    name: variable.name.clone().into(),
+                    value: variable.value.clone().into(),
+                }
+            })),
+            folders: List::from_iter(persisted_project.folders.iter().map(|folder| FolderState {
+                path: folder.path.clone().into(),
+                headers: List::from_iter(folder.headers.iter().map(|header| header.into())),
+            })),
         }
     }
 }
@@ -255,6 +859,15 @@ impl From<&PersistedEndpoint> for Endpoint {
             method: persisted_endpoint.method.clone().into(),
             row_color: DEFAULT_ROW_COLOR.to_string().into(),
             headers,
+            follow_redirects: persisted_endpoint.follow_redirects.into(),
+            max_redirects: persisted_endpoint.max_redirects.into(),
+            body_mode: persisted_endpoint.body_mode.into(),
+            graphql_query: persisted_endpoint.graphql.query.clone().into(),
+            graphql_variables: persisted_endpoint.graphql.variables.clone().into(),
+            kind: persisted_endpoint.kind.into(),
+            snippets: List::from_iter(persisted_endpoint.snippets.clone()),
+            grpc_method: persisted_endpoint.grpc_method.clone().into(),
+            folder: persisted_endpoint.folder.clone().into(),
         }
     }
 }
@@ -267,3 +880,90 @@ impl From<&Header> for HeaderState {
         }
     }
 }
+
+#[test]
+fn test_unsaved_changes() {
+    let mut saved = PersistedProject {
+        name: String::from("api"),
+        endpoints: vec![PersistedEndpoint {
+            name: String::from("users"),
+            ..Default::default()
+        }],
+        tls: Default::default(),
+        proxy: None,
+        proto_files: vec![],
+        variables: vec![],
+        folders: vec![],
+    };
+    let mut project = saved.clone();
+    let mut endpoint = saved.endpoints[0].clone();
+
+    let changes = UnsavedChanges::compare(&project, &endpoint, Some(&saved));
+    assert!(!changes.any());
+
+    endpoint.url = String::from("https://example.com/users");
+    let changes = UnsavedChanges::compare(&project, &endpoint, Some(&saved));
+    assert_eq!((changes.project, changes.endpoint), (false, true));
+
+    project.tls.insecure = true;
+    let changes = UnsavedChanges::compare(&project, &endpoint, Some(&saved));
+    assert_eq!((changes.project, changes.endpoint), (true, true));
+
+    // A new endpoint is only changed once it's edited
+    let new = PersistedEndpoint::default();
+    assert!(!UnsavedChanges::compare(&saved, &new, Some(&saved)).endpoint);
+
+    // A project that was never saved is changed once it's named
+    saved.name = String::from(DEFAULT_PROJECT_NAME);
+    saved.endpoints.clear();
+    assert!(!UnsavedChanges::compare(&saved, &new, None).any());
+    saved.name = String::from("auth");
+    assert!(UnsavedChanges::compare(&saved, &new, None).project);
+}
+
+#[test]
+fn test_unique_name() {
+    let taken = ["Petstore", "Petstore (2)"];
+    let is_taken = |candidate: &str| taken.contains(&candidate);
+
+    assert_eq!(unique_name("Petstore", is_taken), "Petstore (3)");
+    assert_eq!(unique_name("Bookstore", is_taken), "Bookstore");
+}
+
+#[test]
+fn test_restore_backup() {
+    let dir_name = format!("tome_test_restore_backup_{}", std::process::id());
+    let dir = std::env::temp_dir().join(dir_name);
+    fs::create_dir_all(&dir).unwrap();
+
+    let path = dir.join("api.project");
+    assert_eq!(backup_path(&path), dir.join("api.project.bak"));
+
+    let project = PersistedProject {
+        name: String::from("api"),
+        endpoints: vec![],
+        tls: Default::default(),
+        proxy: None,
+        proto_files: vec![],
+        variables: vec![],
+        folders: vec![],
+    };
+    fs::write(&path, "{\"name\": \"api\", \"endp").unwrap();
+    assert!(restore_backup(&path).is_err());
+
+    write_atomically(
+        &backup_path(&path),
+        &serde_json::to_string(&project).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(restore_backup(&path).unwrap(), project);
+    assert_eq!(read_project(&path).unwrap(), project);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_first_save() {
+    assert!(first_save("tome_test_first_save"));
+    assert!(!first_save("tome_test_first_save"));
+}
@@ -0,0 +1,303 @@
+use std::collections::{BTreeSet, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Header, PersistedEndpoint};
+
+pub const FOLDER_SEPARATOR: char = '/';
+
+/// A folder of endpoints, its path lists the parent folders separated by `/`.
+/// Folders only need to be saved when they have settings or no endpoints yet.
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Folder {
+    pub path: String,
+
+    /// Sent by every endpoint in the folder and its subfolders, unless the
+    /// endpoint has a header with the same name
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<Header>,
+}
+
+/// A line in the endpoints tree
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TreeRow {
+    Folder {
+        path: String,
+        depth: usize,
+        expanded: bool,
+    },
+
+    /// Points into the list of endpoints the tree was built from
+    Endpoint { index: usize, depth: usize },
+}
+
+/// Trims the path and drops empty segments, so `" Users//Admin/"` is `"Users/Admin"`
+pub fn normalize_folder(path: &str) -> String {
+    folder_segments(path).join(&FOLDER_SEPARATOR.to_string())
+}
+
+pub fn folder_segments(path: &str) -> Vec<&str> {
+    path.split(FOLDER_SEPARATOR)
+        .map(|segment| segment.trim())
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// The last segment of the path, what the tree shows for the folder
+pub fn folder_name(path: &str) -> &str {
+    folder_segments(path).last().copied().unwrap_or_default()
+}
+
+/// The folder and its parents, outermost first
+pub fn folder_ancestors(path: &str) -> Vec<String> {
+    let segments = folder_segments(path);
+
+    (1..=segments.len())
+        .map(|length| segments[..length].join(&FOLDER_SEPARATOR.to_string()))
+        .collect()
+}
+
+/// Every folder in the project, including parents only implied by a path
+pub fn all_folders(endpoints: &[PersistedEndpoint], folders: &[Folder]) -> BTreeSet<String> {
+    endpoints
+        .iter()
+        .map(|endpoint| endpoint.folder.as_str())
+        .chain(folders.iter().map(|folder| folder.path.as_str()))
+        .flat_map(folder_ancestors)
+        .collect()
+}
+
+/// The default headers of a folder, inherited from its parents with the
+/// innermost folder winning
+pub fn folder_headers(folders: &[Folder], path: &str) -> Vec<Header> {
+    let mut headers: Vec<Header> = vec![];
+
+    for ancestor in folder_ancestors(path) {
+        let folder_headers = folders
+            .iter()
+            .filter(|folder| normalize_folder(&folder.path) == ancestor)
+            .flat_map(|folder| folder.headers.iter());
+
+        for header in folder_headers {
+            headers.retain(|existing| !existing.name.eq_ignore_ascii_case(&header.name));
+            headers.push(header.clone());
+        }
+    }
+
+    headers
+}
+
+/// The endpoint with the default headers of its folders added in front of its own
+pub fn with_folder_headers(folders: &[Folder], endpoint: &PersistedEndpoint) -> PersistedEndpoint {
+    let mut headers: Vec<Header> = folder_headers(folders, &endpoint.folder)
+        .into_iter()
+        .filter(|folder_header| {
+            !endpoint
+                .headers
+                .iter()
+                .any(|header| header.name.eq_ignore_ascii_case(&folder_header.name))
+        })
+        .collect();
+
+    headers.extend(endpoint.headers.iter().cloned());
+
+    PersistedEndpoint {
+        headers,
+        ..endpoint.clone()
+    }
+}
+
+/// Sets the default headers of a folder, keeping the folder's other settings
+pub fn set_folder_headers(folders: &mut Vec<Folder>, path: &str, headers: Vec<Header>) {
+    let path = normalize_folder(path);
+    let position = folders
+        .iter()
+        .position(|folder| normalize_folder(&folder.path) == path);
+
+    // NOTE: Folders without settings are implied by their endpoints' paths, so
+    // clearing a folder's headers removes its entry
+    match (position, headers.is_empty()) {
+        (Some(index), true) => {
+            folders.remove(index);
+        }
+        (Some(index), false) => folders[index].headers = headers,
+        (None, true) => {}
+        (None, false) => folders.push(Folder { path, headers }),
+    }
+}
+
+/// Reads one `Name: value` pair per line, as typed for a folder's headers
+pub fn parse_headers(text: &str) -> Vec<Header> {
+    text.lines()
+        .filter_map(|pair| {
+            let (name, value) = pair.split_once(':')?;
+            let name = name.trim();
+
+            (!name.is_empty()).then(|| Header {
+                name: name.to_string(),
+                value: value.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+pub fn format_headers(headers: &[Header]) -> String {
+    headers
+        .iter()
+        .map(|header| format!("{}: {}", header.name, header.value))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// The rows of the endpoints tree, folders before endpoints at each level.
+/// The contents of folders that aren't expanded are left out.
+pub fn tree_rows(
+    endpoints: &[PersistedEndpoint],
+    folders: &[Folder],
+    expanded: &HashSet<String>,
+) -> Vec<TreeRow> {
+    let all_folders = all_folders(endpoints, folders);
+    let mut rows = vec![];
+
+    add_tree_rows("", 0, endpoints, &all_folders, expanded, &mut rows);
+
+    rows
+}
+
+fn add_tree_rows(
+    parent: &str,
+    depth: usize,
+    endpoints: &[PersistedEndpoint],
+    all_folders: &BTreeSet<String>,
+    expanded: &HashSet<String>,
+    rows: &mut Vec<TreeRow>,
+) {
+    let subfolders = all_folders
+        .iter()
+        .filter(|path| folder_segments(path).len() == depth + 1)
+        .filter(|path| {
+            parent.is_empty() || path.starts_with(&format!("{parent}{FOLDER_SEPARATOR}"))
+        });
+
+    for path in subfolders {
+        let is_expanded = expanded.contains(path);
+        rows.push(TreeRow::Folder {
+            path: path.clone(),
+            depth,
+            expanded: is_expanded,
+        });
+
+        if is_expanded {
+            add_tree_rows(path, depth + 1, endpoints, all_folders, expanded, rows);
+        }
+    }
+
+    endpoints
+        .iter()
+        .enumerate()
+        .filter(|(_, endpoint)| normalize_folder(&endpoint.folder) == parent)
+        .for_each(|(index, _)| rows.push(TreeRow::Endpoint { index, depth }));
+}
+
+#[test]
+fn test_tree_rows() {
+    let endpoint = |name: &str, folder: &str| PersistedEndpoint {
+        name: name.to_string(),
+        folder: folder.to_string(),
+        ..PersistedEndpoint::default()
+    };
+
+    let endpoints = vec![
+        endpoint("Health", ""),
+        endpoint("List Users", "Users"),
+        endpoint("Delete User", "Users/Admin "),
+        endpoint("List Pets", "Pets"),
+    ];
+    let folders = vec![Folder {
+        path: String::from("Archive"),
+        headers: vec![],
+    }];
+
+    let mut expanded = HashSet::new();
+    assert_eq!(
+        tree_rows(&endpoints, &folders, &expanded),
+        vec![
+            TreeRow::Folder {
+                path: String::from("Archive"),
+                depth: 0,
+                expanded: false
+            },
+            TreeRow::Folder {
+                path: String::from("Pets"),
+                depth: 0,
+                expanded: false
+            },
+            TreeRow::Folder {
+                path: String::from("Users"),
+                depth: 0,
+                expanded: false
+            },
+            TreeRow::Endpoint { index: 0, depth: 0 },
+        ]
+    );
+
+    expanded.insert(String::from("Users"));
+    expanded.insert(String::from("Users/Admin"));
+    assert_eq!(
+        tree_rows(&endpoints, &folders, &expanded)[2..],
+        [
+            TreeRow::Folder {
+                path: String::from("Users"),
+                depth: 0,
+                expanded: true
+            },
+            TreeRow::Folder {
+                path: String::from("Users/Admin"),
+                depth: 1,
+                expanded: true
+            },
+            TreeRow::Endpoint { index: 2, depth: 2 },
+            TreeRow::Endpoint { index: 1, depth: 1 },
+            TreeRow::Endpoint { index: 0, depth: 0 },
+        ]
+    );
+}
+
+#[test]
+fn test_folder_headers() {
+    let header = |name: &str, value: &str| Header {
+        name: name.to_string(),
+        value: value.to_string(),
+    };
+
+    let mut folders = vec![Folder {
+        path: String::from("Pets"),
+        headers: vec![],
+    }];
+    set_folder_headers(
+        &mut folders,
+        "Users",
+        parse_headers("Authorization: Bearer user-token\nAccept: text/html, application/json"),
+    );
+    set_folder_headers(
+        &mut folders,
+        "Users/Admin",
+        parse_headers("authorization: Bearer admin-token\n\n"),
+    );
+
+    let endpoint = PersistedEndpoint {
+        folder: String::from("Users/Admin"),
+        headers: vec![header("X-Team", "platform")],
+        ..PersistedEndpoint::default()
+    };
+
+    assert_eq!(
+        format_headers(&with_folder_headers(&folders, &endpoint).headers),
+        "Accept: text/html, application/json\nauthorization: Bearer admin-token\nX-Team: platform"
+    );
+
+    // Only the edited folder's entry goes away once its headers are cleared
+    set_folder_headers(&mut folders, "Users/Admin", vec![]);
+    let paths: Vec<&str> = folders.iter().map(|folder| folder.path.as_str()).collect();
+    assert_eq!(paths, vec!["Pets", "Users"]);
+}
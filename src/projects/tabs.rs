@@ -0,0 +1,245 @@
+use super::{Header, PersistedEndpoint, PersistedProject};
+
+/// An endpoint open in a tab, its edits and response are kept while another tab is shown
+#[derive(Clone, Debug)]
+pub struct EndpointTab {
+    pub project: PersistedProject,
+    pub endpoint: PersistedEndpoint,
+    pub response: Option<TabResponse>,
+}
+
+/// The last response shown for a tab
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TabResponse {
+    pub body: ResponseBody,
+    pub label: String,
+    pub headers: Vec<Header>,
+}
+
+/// A response body as it was received, so it's decoded and highlighted the same
+/// way when its tab is shown again
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResponseBody {
+    pub raw: Vec<u8>,
+    pub content_encoding: Option<String>,
+    pub content_type: Option<String>,
+    pub ext: String,
+}
+
+impl EndpointTab {
+    pub fn new(project: PersistedProject, endpoint: PersistedEndpoint) -> Self {
+        EndpointTab {
+            project,
+            endpoint,
+            response: None,
+        }
+    }
+
+    fn is_for(&self, project: &str, endpoint: &str) -> bool {
+        self.project.name == project && self.endpoint.name == endpoint
+    }
+}
+
+/// The open endpoints, there is always at least one
+#[derive(Clone, Debug)]
+pub struct Tabs {
+    tabs: Vec<EndpointTab>,
+    active: usize,
+}
+
+impl Tabs {
+    pub fn new(tab: EndpointTab) -> Self {
+        Tabs {
+            tabs: vec![tab],
+            active: 0,
+        }
+    }
+
+    pub fn tabs(&self) -> &[EndpointTab] {
+        &self.tabs
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    pub fn active(&self) -> &EndpointTab {
+        &self.tabs[self.active]
+    }
+
+    /// Keeps the endpoint shown on the dashboard in the active tab, the other
+    /// tabs of the project get the project's latest saved state
+    pub fn update_active(&mut self, project: PersistedProject, endpoint: PersistedEndpoint) {
+        self.update_project(&project);
+
+        let active = &mut self.tabs[self.active];
+        active.project = project;
+        active.endpoint = endpoint;
+    }
+
+    /// Gives every tab of the project its latest version
+    pub fn update_project(&mut self, project: &PersistedProject) {
+        for tab in self.tabs.iter_mut() {
+            if tab.project.name == project.name {
+                tab.project = project.clone();
+            }
+        }
+    }
+
+    pub fn store_response(&mut self, response: Option<TabResponse>) {
+        self.tabs[self.active].response = response;
+    }
+
+    /// Shows the endpoint's tab, opening one next to the active tab if it isn't open yet
+    pub fn open(&mut self, mut tab: EndpointTab) {
+        let open = self
+            .tabs
+            .iter()
+            .position(|open| open.is_for(&tab.project.name, &tab.endpoint.name));
+
+        match open {
+            Some(index) => self.active = index,
+            None => {
+                // NOTE: The project's other tabs may have unsaved edits, the new tab
+                // shares them instead of the version read from disk
+                if let Some(project) = self.project(&tab.project.name) {
+                    tab.project = project.clone();
+                }
+
+                self.active += 1;
+                self.tabs.insert(self.active, tab);
+            }
+        }
+    }
+
+    /// The project as it is edited in its open tabs
+    pub fn project(&self, name: &str) -> Option<&PersistedProject> {
+        self.tabs
+            .iter()
+            .find(|tab| tab.project.name == name)
+            .map(|tab| &tab.project)
+    }
+
+    pub fn next(&mut self) {
+        self.active = (self.active + 1) % self.tabs.len();
+    }
+
+    pub fn previous(&mut self) {
+        self.active = (self.active + self.tabs.len() - 1) % self.tabs.len();
+    }
+
+    /// Closes the active tab and shows the one before it, the last tab stays open
+    pub fn close(&mut self) -> Option<EndpointTab> {
+        if self.tabs.len() == 1 {
+            return None;
+        }
+
+        let closed = self.tabs.remove(self.active);
+        self.active = self.active.saturating_sub(1);
+
+        Some(closed)
+    }
+
+    /// What the tab bar shows, the project is only named for tabs of other projects
+    pub fn title(&self, index: usize) -> String {
+        let tab = &self.tabs[index];
+
+        match tab.project.name == self.active().project.name {
+            true => tab.endpoint.name.clone(),
+            false => format!("{} › {}", tab.project.name, tab.endpoint.name),
+        }
+    }
+}
+
+#[cfg(test)]
+fn test_project(name: &str, endpoints: &[&str]) -> PersistedProject {
+    PersistedProject {
+        name: name.to_string(),
+        endpoints: endpoints
+            .iter()
+            .map(|name| PersistedEndpoint {
+                name: name.to_string(),
+                ..Default::default()
+            })
+            .collect(),
+        tls: Default::default(),
+        proxy: None,
+        proto_files: vec![],
+        variables: vec![],
+        folders: vec![],
+    }
+}
+
+#[test]
+fn test_tabs() {
+    let api = test_project("api", &["users", "posts"]);
+    let auth = test_project("auth", &["login"]);
+
+    let mut tabs = Tabs::new(EndpointTab::new(api.clone(), api.endpoints[0].clone()));
+    tabs.open(EndpointTab::new(auth.clone(), auth.endpoints[0].clone()));
+    tabs.open(EndpointTab::new(api.clone(), api.endpoints[1].clone()));
+    assert_eq!(tabs.tabs().len(), 3);
+    assert_eq!(tabs.active().endpoint.name, "posts");
+    assert_eq!(tabs.title(0), "users");
+    assert_eq!(tabs.title(1), "auth › login");
+
+    // Opening an endpoint that's already open shows its tab
+    tabs.open(EndpointTab::new(auth.clone(), auth.endpoints[0].clone()));
+    assert_eq!(tabs.tabs().len(), 3);
+    assert_eq!(tabs.active_index(), 1);
+
+    tabs.next();
+    tabs.next();
+    assert_eq!(tabs.active_index(), 0);
+    tabs.previous();
+    assert_eq!(tabs.active_index(), 2);
+
+    assert_eq!(tabs.close().unwrap().endpoint.name, "posts");
+    assert_eq!(tabs.close().unwrap().endpoint.name, "login");
+    assert!(tabs.close().is_none());
+    assert_eq!(tabs.active().endpoint.name, "users");
+}
+
+#[test]
+fn test_tab_project_sync() {
+    let api = test_project("api", &["users", "posts"]);
+
+    let mut tab = EndpointTab::new(api.clone(), api.endpoints[0].clone());
+    tab.endpoint.url = String::from("https://example.com/users");
+
+    let mut tabs = Tabs::new(EndpointTab::new(api.clone(), api.endpoints[1].clone()));
+    tabs.open(tab.clone());
+
+    // Saving updates the project of every tab showing it
+    let mut saved = api.clone();
+    saved.endpoints[0] = tab.endpoint.clone();
+    tabs.update_active(saved.clone(), tab.endpoint.clone());
+    assert_eq!(tabs.tabs()[0].project, saved);
+    assert_eq!(
+        tabs.tabs()[0].project.endpoints[0].url,
+        "https://example.com/users"
+    );
+}
+
+#[test]
+fn test_tab_keeps_open_project() {
+    let api = test_project("api", &["users", "posts"]);
+
+    let mut edited = api.clone();
+    edited.variables.push(super::Variable {
+        name: String::from("host"),
+        value: String::from("localhost:8080"),
+    });
+
+    let mut tabs = Tabs::new(EndpointTab::new(api.clone(), api.endpoints[0].clone()));
+    tabs.update_active(edited.clone(), edited.endpoints[0].clone());
+
+    // Opening the project again from disk keeps the edits of its open tab
+    tabs.open(EndpointTab::new(api.clone(), api.endpoints[1].clone()));
+    assert_eq!(tabs.active().project, edited);
+
+    tabs.update_active(tabs.active().project.clone(), api.endpoints[1].clone());
+    assert_eq!(tabs.tabs()[0].project, edited);
+    assert_eq!(tabs.project("api"), Some(&edited));
+    assert!(tabs.project("auth").is_none());
+}
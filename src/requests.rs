@@ -1,21 +1,85 @@
+pub mod decoding;
+pub mod graphql;
+pub mod grpc;
+pub mod last_response;
+pub mod proxy;
+pub mod sse;
+pub mod tls;
+pub mod variables;
+pub mod websocket;
+
 use std::{
+    cell::Ref,
+    collections::HashMap,
     fs::{self, OpenOptions},
     io::Write,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    time::Duration,
 };
 
-use anathema::prelude::Context;
-use ureq::Response;
+use anathema::{
+    component::ComponentId,
+    prelude::Context,
+    state::{List, State, Value},
+};
+use ureq::{Agent, AgentBuilder, Response};
+use url::Url;
 
 use crate::{
     components::{
         dashboard::{DashboardComponent, DashboardDisplay, DashboardState, FloatingWindow},
         response_renderer::ResponseRendererMessages,
         send_message,
+        syntax_highlighter::detect_extension,
+    },
+    options::get_options,
+    projects::{
+        tabs::{ResponseBody, TabResponse},
+        BodyMode, HeaderState, PersistedEndpoint, PersistedProject,
+    },
+    requests::{
+        decoding::{decode_body, hex_dump, DecodedBody, ACCEPT_ENCODING},
+        graphql::graphql_payload,
+        grpc::unary_call,
+        last_response::{save_last_response, StoredResponse},
+        proxy::resolve_proxy,
+        sse::{is_event_stream, set_pending_stream, SseStream},
+        tls::build_tls_config,
+        variables::resolve_endpoint,
     },
-    projects::{HeaderState, PersistedEndpoint},
 };
 
+/// Hex dump of the last response body as received, shown by the raw response view
+pub const RAW_RESPONSE_FILE: &str = "tome_response_raw.txt";
+
+/// Longest wait for the server between two reads of an event stream, it's also how
+/// long a stopped stream on a quiet connection takes to close its connection
+const STREAM_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone)]
+pub struct RedirectHop {
+    pub status: u16,
+    pub url: String,
+    pub location: String,
+}
+
+#[derive(Debug, State)]
+pub struct RedirectHopState {
+    pub status: Value<String>,
+    pub url: Value<String>,
+    pub location: Value<String>,
+}
+
+impl From<&RedirectHop> for RedirectHopState {
+    fn from(hop: &RedirectHop) -> Self {
+        RedirectHopState {
+            status: hop.status.to_string().into(),
+            url: hop.url.clone().into(),
+            location: hop.location.clone().into(),
+        }
+    }
+}
+
 pub fn do_request(
     state: &mut DashboardState,
     context: anathema::prelude::Context<'_, DashboardState>,
@@ -23,19 +87,254 @@ pub fn do_request(
     dashboard: &mut DashboardComponent,
 ) -> anyhow::Result<()> {
     let endpoint: PersistedEndpoint = (&*state.endpoint.to_ref()).into();
+    let project: PersistedProject = (&*state.project.to_ref()).into();
+    let endpoint = resolve_endpoint(&project, &endpoint);
+
+    // NOTE: Only requests asking for an event stream get a read timeout, other
+    // requests wait on slow servers for as long as they take
+    let agent = agent_builder(&project, &endpoint.url).map(|builder| {
+        match accepts_event_stream(&endpoint) {
+            true => builder.timeout_read(STREAM_READ_TIMEOUT).build(),
+            false => builder.build(),
+        }
+    });
+
+    let agent = match agent {
+        Ok(agent) => agent,
+        Err(error) => {
+            state
+                .error_message
+                .set(format!("Request configuration error: {error}"));
+            state.floating_window.set(FloatingWindow::Error);
+
+            return Ok(());
+        }
+    };
+
+    let body = match request_body(&endpoint) {
+        Ok(body) => body,
+        Err(error) => {
+            state
+                .error_message
+                .set(format!("Request body error: {error}"));
+            state.floating_window.set(FloatingWindow::Error);
+
+            return Ok(());
+        }
+    };
+
+    let mut redirect_chain: Vec<RedirectHop> = vec![];
+    let response = send_request(&agent, &endpoint, &body, &mut redirect_chain);
+
+    loop {
+        if state.redirect_chain.len() > 0 {
+            state.redirect_chain.pop_back();
+        } else {
+            break;
+        }
+    }
+
+    redirect_chain
+        .iter()
+        .for_each(|hop| state.redirect_chain.push(hop.into()));
+
+    match response {
+        Ok(response) => handle_successful_response(response, state, context, dashboard),
+        Err(SendError::Request(error)) => handle_error_response(*error, state, context, dashboard),
+        Err(SendError::TooManyRedirects(max_redirects)) => {
+            state
+                .error_message
+                .set(format!("Too many redirects ({max_redirects})"));
+            state.floating_window.set(FloatingWindow::Error);
+
+            Ok(())
+        }
+    }?;
+
+    Ok(())
+}
+
+/// Calls the endpoint's gRPC method and shows the response message, a failed
+/// call shows its status and message instead
+pub fn do_grpc_request(
+    state: &mut DashboardState,
+    context: anathema::prelude::Context<'_, DashboardState>,
+    dashboard: &mut DashboardComponent,
+) -> anyhow::Result<()> {
+    let endpoint: PersistedEndpoint = (&*state.endpoint.to_ref()).into();
+    let project: PersistedProject = (&*state.project.to_ref()).into();
+    let endpoint = resolve_endpoint(&project, &endpoint);
+
+    let response = match unary_call(&project, &endpoint) {
+        Ok(response) => response,
+        Err(error) => {
+            state.error_message.set(format!("gRPC error: {error}"));
+            state.floating_window.set(FloatingWindow::Error);
 
-    let url = endpoint.url.clone();
-    let method = endpoint.method.clone();
-    let headers = endpoint.headers;
+            return Ok(());
+        }
+    };
 
+    loop {
+        if state.response_headers.len() > 0 {
+            state.response_headers.pop_back();
+        } else {
+            break;
+        }
+    }
+
+    for header in response.headers.iter() {
+        state.response_headers.push(HeaderState::from(header));
+    }
+
+    let status = response.status_name();
+    let (text, window_label) = match response.body {
+        Some(body) => (body, format!("gRPC Response (Status: {status})")),
+        None => (
+            serde_json::to_string_pretty(&serde_json::json!({
+                "code": response.status,
+                "status": status,
+                "message": response.message,
+            }))?,
+            format!("gRPC Error (Status: {status})"),
+        ),
+    };
+
+    show_in_response_view(
+        text,
+        window_label,
+        "json".to_string(),
+        state,
+        context,
+        dashboard,
+    )
+}
+
+/// Builds the agent used to send a request to `url` with the project's settings applied
+pub fn build_agent(project: &PersistedProject, url: &str) -> anyhow::Result<Agent> {
+    Ok(agent_builder(project, url)?.build())
+}
+
+fn agent_builder(project: &PersistedProject, url: &str) -> anyhow::Result<AgentBuilder> {
+    // NOTE: Redirects are followed manually so every hop can be shown to the user
+    let mut builder = AgentBuilder::new().redirects(0);
+
+    if let Some(tls_config) = build_tls_config(&project.tls)? {
+        builder = builder.tls_config(tls_config);
+    }
+
+    let options = get_options();
+    if let Some(proxy) = resolve_proxy(project.proxy.as_ref(), options.proxy.as_ref(), url)? {
+        builder = builder.proxy(proxy);
+    }
+
+    Ok(builder)
+}
+
+/// Whether the endpoint's Accept header asks for an event stream
+fn accepts_event_stream(endpoint: &PersistedEndpoint) -> bool {
+    endpoint.headers.iter().any(|header| {
+        header.name.eq_ignore_ascii_case("accept")
+            && header
+                .value
+                .split(',')
+                .any(|media_type| is_event_stream(Some(media_type)))
+    })
+}
+
+/// Builds the body text sent for an endpoint, depending on its body mode
+pub fn request_body(endpoint: &PersistedEndpoint) -> anyhow::Result<String> {
+    match endpoint.body_mode {
+        BodyMode::Raw => Ok(endpoint.body.clone()),
+        BodyMode::GraphQL => graphql_payload(&endpoint.graphql),
+    }
+}
+
+/// Headers with credentials, only sent to the origin of the endpoint's URL
+const CREDENTIAL_HEADERS: [&str; 2] = ["authorization", "cookie"];
+
+// NOTE: ureq's error holds a whole response, it's boxed to keep results small
+enum SendError {
+    Request(Box<ureq::Error>),
+    TooManyRedirects(usize),
+}
+
+impl From<Box<ureq::Error>> for SendError {
+    fn from(error: Box<ureq::Error>) -> Self {
+        SendError::Request(error)
+    }
+}
+
+fn send_request(
+    agent: &Agent,
+    endpoint: &PersistedEndpoint,
+    body: &str,
+    redirect_chain: &mut Vec<RedirectHop>,
+) -> Result<Response, SendError> {
+    let mut url = endpoint.url.clone();
+    let mut method = endpoint.method.clone();
+    let mut body = Some(body);
+
+    loop {
+        // NOTE: Redirects to another host must not receive the endpoint's credentials
+        let with_credentials = is_same_origin(&endpoint.url, &url);
+        let response = send_single_request(agent, endpoint, &method, &url, body, with_credentials)?;
+        let status = response.status();
+
+        if !is_redirect(status) || !endpoint.follow_redirects {
+            return Ok(response);
+        }
+
+        if redirect_chain.len() >= endpoint.max_redirects {
+            return Err(SendError::TooManyRedirects(endpoint.max_redirects));
+        }
+
+        let Some(location) = response.header("location").map(|l| l.to_string()) else {
+            return Ok(response);
+        };
+
+        let Some(next_url) = resolve_location(&url, &location) else {
+            return Ok(response);
+        };
+
+        redirect_chain.push(RedirectHop {
+            status,
+            url: url.clone(),
+            location,
+        });
+
+        // Follow the same rules browsers use, 303 always switches to GET, and
+        // 301/302 only switch when the original request was a POST
+        let switch_to_get = status == 303 || ((status == 301 || status == 302) && method == "POST");
+        if switch_to_get {
+            method = String::from("GET");
+            body = None;
+        }
+
+        url = next_url;
+    }
+}
+
+fn send_single_request(
+    agent: &Agent,
+    endpoint: &PersistedEndpoint,
+    method: &str,
+    url: &str,
+    body: Option<&str>,
+    with_credentials: bool,
+) -> Result<Response, Box<ureq::Error>> {
     let mut content_type = String::new();
 
-    let mut request = ureq::request(&method, &url);
-    for header_value in headers.iter() {
-        let header = header_value;
+    let mut request = agent.request(method, url);
+    for header in endpoint.headers.iter() {
         let header_name = header.name.to_string();
         let header_value = header.value.to_string();
 
+        let is_credential = CREDENTIAL_HEADERS.contains(&header_name.to_lowercase().as_str());
+        if is_credential && !with_credentials {
+            continue;
+        }
+
         if header_name.to_lowercase() == "content-type" {
             content_type.push_str(header_value.as_str());
         }
@@ -43,25 +342,140 @@ pub fn do_request(
         request = request.set(&header_name, &header_value);
     }
 
-    let response = match content_type.as_str() {
-        "application/json" => {
-            let req_body = endpoint.body.clone();
+    if request.header("accept-encoding").is_none() {
+        request = request.set("Accept-Encoding", ACCEPT_ENCODING);
+    }
 
-            request.send_string(&req_body)
-        }
+    // NOTE: GraphQL payloads are always JSON, even without a content type header
+    if endpoint.body_mode == BodyMode::GraphQL && content_type.is_empty() {
+        content_type.push_str("application/json");
+        request = request.set("Content-Type", &content_type);
+    }
+
+    // NOTE: The body is sent as written whatever the content type, form bodies
+    // are already encoded, imported ones included
+    let response = request.send_string(body.unwrap_or_default());
+
+    Ok(response?)
+}
+
+fn is_redirect(status: u16) -> bool {
+    matches!(status, 301 | 302 | 303 | 307 | 308)
+}
+
+/// Same scheme, host and port
+fn is_same_origin(first_url: &str, second_url: &str) -> bool {
+    match (Url::parse(first_url), Url::parse(second_url)) {
+        (Ok(first), Ok(second)) => first.origin() == second.origin(),
+        _ => false,
+    }
+}
+
+fn resolve_location(current_url: &str, location: &str) -> Option<String> {
+    let base = Url::parse(current_url).ok()?;
+    let next = base.join(location).ok()?;
+
+    Some(next.to_string())
+}
+
+#[test]
+fn test_resolve_location_relative() {
+    let next = resolve_location("https://example.com/auth/login?x=1", "callback?code=abc");
 
-        // TODO: Figure out how to support form k/v pairs in the request body builder interface
-        // "multipart/form" => request.send_form("")
-        //
-        _ => request.send_string(""),
+    assert_eq!(
+        next,
+        Some("https://example.com/auth/callback?code=abc".to_string())
+    );
+}
+
+#[test]
+fn test_send_imported_form_body() {
+    use std::{
+        io::{BufRead, BufReader, Read},
+        net::TcpListener,
+        thread,
     };
 
-    match response {
-        Ok(response) => handle_successful_response(response, state, context, dashboard),
-        Err(error) => handle_error_response(error, state, context, dashboard),
-    }?;
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
 
-    Ok(())
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut content_length = 0;
+
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")
+            .unwrap();
+
+        String::from_utf8(body).unwrap()
+    });
+
+    let url = format!("http://127.0.0.1:{port}/");
+    let endpoint = crate::compatibility::curl::parse_curl(&format!("curl {url} -d a=b"))
+        .unwrap()
+        .endpoint;
+    let agent = AgentBuilder::new().redirects(0).build();
+    let response = send_request(&agent, &endpoint, &endpoint.body, &mut vec![]);
+
+    assert!(response.is_ok());
+    assert_eq!(server.join().unwrap(), "a=b");
+}
+
+#[test]
+fn test_accepts_event_stream() {
+    let mut endpoint = PersistedEndpoint::default();
+    assert!(!accepts_event_stream(&endpoint));
+
+    endpoint.headers.push(crate::projects::Header {
+        name: String::from("Accept"),
+        value: String::from("application/json, text/event-stream;q=0.9"),
+    });
+    assert!(accepts_event_stream(&endpoint));
+}
+
+#[test]
+fn test_is_same_origin() {
+    assert!(is_same_origin(
+        "https://example.com/auth",
+        "https://example.com:443/home"
+    ));
+    assert!(!is_same_origin(
+        "https://example.com/auth",
+        "https://other.com/auth"
+    ));
+    assert!(!is_same_origin(
+        "https://example.com/auth",
+        "http://example.com/auth"
+    ));
+    assert!(!is_same_origin(
+        "https://example.com/auth",
+        "https://example.com:8443/auth"
+    ));
+}
+
+#[test]
+fn test_resolve_location_absolute() {
+    let next = resolve_location("https://example.com/auth", "https://other.com/home");
+
+    assert_eq!(next, Some("https://other.com/home".to_string()));
 }
 
 fn handle_successful_response(
@@ -80,47 +494,44 @@ fn handle_successful_response(
         }
     }
 
-    let mut ext = String::from("txt");
     for name in response.headers_names() {
         let Some(value) = response.header(&name) else {
             continue;
         };
 
-        if name.to_lowercase() == "content-type" {
-            if let Some((_, extension)) = value.to_string().split_once("/") {
-                ext = extension.to_string();
-            }
-        }
-
         state.response_headers.push(HeaderState {
             name: name.clone().into(),
             value: value.to_string().clone().into(),
         });
     }
 
-    let mut response_path = PathBuf::from("/tmp");
-    response_path.push("tome_response.txt");
+    let content_type = response.header("content-type").map(|h| h.to_string());
+    if is_event_stream(content_type.as_deref()) {
+        return start_event_stream(response, state, context, dashboard);
+    }
 
-    let mut response_reader = response.into_reader();
-    let mut buf: Vec<u8> = vec![];
-    response_reader.read_to_end(&mut buf)?;
+    let body = read_body(response)?;
+    let ext = detect_extension(content_type.as_deref(), &body.text);
+
+    store_last_response(state, status, content_type.as_deref(), &body.text);
 
     let mut file_path = PathBuf::from("/tmp");
     file_path.push("tome_response.txt");
 
-    let mut file = OpenOptions::new()
-        .create(true)
-        .truncate(true)
-        .write(true)
-        .open(file_path.clone())?;
-
-    let write_result = file.write_all(buf.as_slice());
+    let write_result = write_response_files(&file_path, &body);
     // TODO: Fix the error handling to message the user
     if write_result.is_err() {
         return Ok(());
     }
 
-    let window_label = format!("Response Body (Status Code: {status})");
+    let window_label = format!("Response Body (Status Code: {status}, {})", body.summary());
+
+    dashboard.set_response_body(Some(ResponseBody {
+        raw: body.raw.clone(),
+        content_encoding: body.content_encoding.clone(),
+        content_type,
+        ext: ext.clone(),
+    }));
 
     // TODO: Fix the response handling so it doesnt have to be read from file since
     // response renderer is reading it all into lines anyway
@@ -142,6 +553,173 @@ fn handle_successful_response(
     Ok(())
 }
 
+/// Hands an event stream response to the response renderer, which shows its
+/// events as they arrive
+fn start_event_stream(
+    response: Response,
+    state: &mut DashboardState,
+    mut context: Context<'_, DashboardState>,
+    dashboard: &mut DashboardComponent,
+) -> anyhow::Result<()> {
+    let status = response.status();
+
+    // NOTE: The renderer reads the events into the lines it shows, the file only
+    // needs to exist for it
+    let mut file_path = PathBuf::from("/tmp");
+    file_path.push("tome_response.txt");
+    write_response_files(&file_path, &decode_body(vec![], None, None))?;
+
+    set_pending_stream(SseStream::start(response));
+    dashboard.set_response_body(None);
+
+    state.response.set(String::new());
+    state
+        .response_body_window_label
+        .set(format!("Event Stream (Status Code: {status})"));
+    state.main_display.set(DashboardDisplay::ResponseBody);
+
+    context.set_focus("id", "response_renderer");
+
+    let response_msg = ResponseRendererMessages::StreamStart;
+    if let Ok(msg) = serde_json::to_string(&response_msg) {
+        if let Ok(component_ids) = dashboard.component_ids.try_borrow() {
+            let _ = send_message("response_renderer", msg, &component_ids, context.emitter);
+        };
+    };
+
+    Ok(())
+}
+
+/// Shows text that wasn't received from the endpoint in the response view, like
+/// the GraphQL schema listing
+pub fn show_in_response_view(
+    text: String,
+    window_label: String,
+    ext: String,
+    state: &mut DashboardState,
+    mut context: Context<'_, DashboardState>,
+    dashboard: &mut DashboardComponent,
+) -> anyhow::Result<()> {
+    let body = decode_body(text.into_bytes(), None, None);
+
+    let mut file_path = PathBuf::from("/tmp");
+    file_path.push("tome_response.txt");
+    write_response_files(&file_path, &body)?;
+
+    dashboard.set_response_body(Some(ResponseBody {
+        raw: body.raw,
+        ext: ext.clone(),
+        ..Default::default()
+    }));
+
+    state.response.set(body.text);
+    state.response_body_window_label.set(window_label);
+    state.main_display.set(DashboardDisplay::ResponseBody);
+
+    context.set_focus("id", "response_renderer");
+
+    let response_msg = ResponseRendererMessages::ResponseUpdate(ext);
+    if let Ok(msg) = serde_json::to_string(&response_msg) {
+        if let Ok(component_ids) = dashboard.component_ids.try_borrow() {
+            let _ = send_message("response_renderer", msg, &component_ids, context.emitter);
+        };
+    };
+
+    Ok(())
+}
+
+/// Shows the last response of a tab again, a tab without one shows its request
+pub fn restore_response(
+    response: Option<&TabResponse>,
+    state: &mut DashboardState,
+    context: &Context<'_, DashboardState>,
+    component_ids: &Ref<'_, HashMap<String, ComponentId<String>>>,
+) -> anyhow::Result<()> {
+    state.redirect_chain = List::empty();
+
+    let Some(response) = response else {
+        state.response_headers = List::empty();
+        state.response.set(String::new());
+        state.response_body_window_label.set(String::new());
+        state.main_display.set(DashboardDisplay::RequestBody);
+
+        return Ok(());
+    };
+
+    state.response_headers = List::from_iter(response.headers.iter().map(HeaderState::from));
+
+    let body = decode_body(
+        response.body.raw.clone(),
+        response.body.content_encoding.as_deref(),
+        response.body.content_type.as_deref(),
+    );
+    let ext = response.body.ext.clone();
+
+    let mut file_path = PathBuf::from("/tmp");
+    file_path.push("tome_response.txt");
+    write_response_files(&file_path, &body)?;
+
+    state.response.set(body.text);
+    state.response_body_window_label.set(response.label.clone());
+    state.main_display.set(DashboardDisplay::ResponseBody);
+
+    let response_msg = ResponseRendererMessages::ResponseUpdate(ext);
+    if let Ok(msg) = serde_json::to_string(&response_msg) {
+        let _ = send_message("response_renderer", msg, component_ids, context.emitter);
+    };
+
+    Ok(())
+}
+
+/// Keeps the response for the current endpoint so exports can describe it
+fn store_last_response(
+    state: &DashboardState,
+    status: u16,
+    content_type: Option<&str>,
+    body: &str,
+) {
+    let project_name = state.project.to_ref().name.to_ref().to_string();
+    let endpoint_name = state.endpoint.to_ref().name.to_ref().to_string();
+    let response = StoredResponse::new(status, content_type, body);
+
+    // NOTE: A response that can't be stored only means exports won't describe it
+    let _ = save_last_response(&project_name, &endpoint_name, &response);
+}
+
+/// Reads the whole body and undoes its content and charset encodings
+fn read_body(response: Response) -> anyhow::Result<DecodedBody> {
+    let content_encoding = response.header("content-encoding").map(|h| h.to_string());
+    let content_type = response.header("content-type").map(|h| h.to_string());
+
+    let mut response_reader = response.into_reader();
+    let mut buf: Vec<u8> = vec![];
+    response_reader.read_to_end(&mut buf)?;
+
+    Ok(decode_body(
+        buf,
+        content_encoding.as_deref(),
+        content_type.as_deref(),
+    ))
+}
+
+/// Writes the decoded body for the response renderer, and a hex dump of the
+/// bytes received next to it for the raw view
+fn write_response_files(file_path: &Path, body: &DecodedBody) -> anyhow::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .truncate(true)
+        .write(true)
+        .open(file_path)?;
+    file.write_all(body.text.as_bytes())?;
+
+    fs::write(
+        file_path.with_file_name(RAW_RESPONSE_FILE),
+        hex_dump(&body.raw),
+    )?;
+
+    Ok(())
+}
+
 fn handle_error_response(
     error: ureq::Error,
     state: &mut DashboardState,
@@ -150,10 +728,28 @@ fn handle_error_response(
 ) -> anyhow::Result<()> {
     match error {
         ureq::Error::Status(code, response) => {
-            let body = response
-                .into_string()
-                .unwrap_or("Could not read error response body".to_string());
-            let window_label = format!("Response Body (Status Code: {code})");
+            let content_type = response.header("content-type").map(|h| h.to_string());
+            let (body, window_label) = match read_body(response) {
+                Ok(body) => {
+                    store_last_response(state, code, content_type.as_deref(), &body.text);
+                    dashboard.set_response_body(Some(ResponseBody {
+                        raw: body.raw.clone(),
+                        content_encoding: body.content_encoding.clone(),
+                        ext: detect_extension(content_type.as_deref(), &body.text),
+                        content_type,
+                    }));
+
+                    let label = format!("Response Body (Status Code: {code}, {})", body.summary());
+                    (body.text, label)
+                }
+                Err(_) => {
+                    dashboard.set_response_body(None);
+                    (
+                        "Could not read error response body".to_string(),
+                        format!("Response Body (Status Code: {code})"),
+                    )
+                }
+            };
 
             // TODO: The error response handling needs to extract headers from the response
             // to display the response headers when there is an error
@@ -0,0 +1,226 @@
+use std::io::Read;
+
+use brotli::Decompressor;
+use encoding_rs::{Encoding, UTF_8};
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+
+/// Value sent in the Accept-Encoding header unless the endpoint sets its own
+pub const ACCEPT_ENCODING: &str = "gzip, deflate, br";
+
+#[derive(Debug)]
+pub struct DecodedBody {
+    /// Body text after decompression and charset conversion
+    pub text: String,
+
+    /// Body bytes exactly as they came over the wire
+    pub raw: Vec<u8>,
+
+    pub decoded_size: usize,
+    pub content_encoding: Option<String>,
+    pub charset: &'static str,
+}
+
+impl DecodedBody {
+    /// Short summary of the body sizes and encodings for the response window label
+    pub fn summary(&self) -> String {
+        let decoded = format_size(self.decoded_size);
+
+        match &self.content_encoding {
+            Some(encoding) => format!(
+                "{decoded}, {} {encoding}, {}",
+                format_size(self.raw.len()),
+                self.charset
+            ),
+            None => format!("{decoded}, {}", self.charset),
+        }
+    }
+}
+
+/// Decompresses and decodes a response body using its Content-Encoding and
+/// Content-Type headers. Bodies that fail to decompress are shown as they are.
+pub fn decode_body(
+    raw: Vec<u8>,
+    content_encoding: Option<&str>,
+    content_type: Option<&str>,
+) -> DecodedBody {
+    let content_encoding = content_encoding
+        .map(|encoding| encoding.trim().to_lowercase())
+        .filter(|encoding| !encoding.is_empty() && encoding != "identity");
+
+    let decompressed = match &content_encoding {
+        Some(encoding) => decompress(&raw, encoding).ok(),
+        None => None,
+    };
+
+    let bytes = decompressed.as_deref().unwrap_or(&raw);
+    let (text, charset) = decode_text(bytes, content_type);
+
+    DecodedBody {
+        decoded_size: bytes.len(),
+        text,
+        raw,
+        content_encoding,
+        charset,
+    }
+}
+
+/// Undoes every coding listed in a Content-Encoding header, codings are listed
+/// in the order they were applied so they are removed in reverse
+pub fn decompress(body: &[u8], content_encoding: &str) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = body.to_vec();
+
+    for coding in content_encoding.rsplit(',').map(|coding| coding.trim()) {
+        let mut decoded: Vec<u8> = vec![];
+
+        match coding {
+            "gzip" | "x-gzip" => {
+                GzDecoder::new(bytes.as_slice()).read_to_end(&mut decoded)?;
+            }
+
+            // NOTE: Some servers send raw deflate streams instead of the zlib
+            // wrapped ones the spec asks for, so fall back to raw deflate
+            "deflate" => {
+                if ZlibDecoder::new(bytes.as_slice())
+                    .read_to_end(&mut decoded)
+                    .is_err()
+                {
+                    decoded.clear();
+                    DeflateDecoder::new(bytes.as_slice()).read_to_end(&mut decoded)?;
+                }
+            }
+
+            "br" => {
+                Decompressor::new(bytes.as_slice(), 4096).read_to_end(&mut decoded)?;
+            }
+
+            "identity" | "" => continue,
+
+            coding => {
+                return Err(anyhow::Error::msg(format!(
+                    "Unsupported content encoding: {coding}"
+                )))
+            }
+        }
+
+        bytes = decoded;
+    }
+
+    Ok(bytes)
+}
+
+/// Wraps a body reader so it is decompressed while it is read, for bodies that
+/// are shown as they arrive instead of after the whole body was received
+pub fn decompressing_reader(
+    reader: Box<dyn Read + Send>,
+    content_encoding: Option<&str>,
+) -> Box<dyn Read + Send> {
+    let coding = content_encoding
+        .map(|encoding| encoding.trim().to_lowercase())
+        .unwrap_or_default();
+
+    match coding.as_str() {
+        "gzip" | "x-gzip" => Box::new(GzDecoder::new(reader)),
+        "deflate" => Box::new(ZlibDecoder::new(reader)),
+        "br" => Box::new(Decompressor::new(reader, 4096)),
+        _ => reader,
+    }
+}
+
+/// Converts body bytes to text using the charset parameter of the Content-Type
+/// header, then a byte order mark, then UTF-8
+pub fn decode_text(body: &[u8], content_type: Option<&str>) -> (String, &'static str) {
+    let declared = content_type
+        .and_then(charset_param)
+        .and_then(|charset| Encoding::for_label(charset.as_bytes()));
+
+    let encoding = Encoding::for_bom(body)
+        .map(|(encoding, _)| encoding)
+        .or(declared)
+        .unwrap_or(UTF_8);
+
+    let (text, encoding, _) = encoding.decode(body);
+
+    (text.into_owned(), encoding.name())
+}
+
+fn charset_param(content_type: &str) -> Option<String> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+
+        match name.trim().eq_ignore_ascii_case("charset") {
+            true => Some(value.trim().trim_matches('"').to_string()),
+            false => None,
+        }
+    })
+}
+
+pub fn format_size(bytes: usize) -> String {
+    match bytes {
+        0..=1023 => format!("{bytes} B"),
+        1024..=1_048_575 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+/// Formats bytes like `xxd` so compressed bodies can be inspected as they were sent
+pub fn hex_dump(bytes: &[u8]) -> String {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|byte| format!("{byte:02x}")).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|byte| match byte.is_ascii_graphic() || *byte == b' ' {
+                    true => *byte as char,
+                    false => '.',
+                })
+                .collect();
+
+            format!("{:08x}: {:<47}  {ascii}", index * 16, hex.join(" "))
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[test]
+fn test_decompress_gzip() {
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    let mut encoder = GzEncoder::new(vec![], Compression::default());
+    encoder.write_all(b"{\"hello\": \"world\"}").unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    let body = decode_body(compressed, Some("gzip"), Some("application/json"));
+
+    assert_eq!(body.text, "{\"hello\": \"world\"}");
+    assert_eq!(body.decoded_size, 18);
+}
+
+#[test]
+fn test_decode_text_shift_jis() {
+    let (encoded, _, _) = encoding_rs::SHIFT_JIS.encode("こんにちは");
+
+    let (text, charset) = decode_text(&encoded, Some("text/plain; charset=\"Shift_JIS\""));
+
+    assert_eq!(text, "こんにちは");
+    assert_eq!(charset, "Shift_JIS");
+}
+
+#[test]
+fn test_decode_text_latin1() {
+    let (text, _) = decode_text(
+        &[0x63, 0x61, 0x66, 0xe9],
+        Some("text/html; charset=ISO-8859-1"),
+    );
+
+    assert_eq!(text, "café");
+}
+
+#[test]
+fn test_hex_dump() {
+    let padding = " ".repeat(41);
+
+    assert_eq!(hex_dump(b"hi\n"), format!("00000000: 68 69 0a{padding}hi."));
+}
@@ -0,0 +1,253 @@
+use serde_json::{json, Map, Value};
+
+use crate::{
+    projects::{GraphQLBody, PersistedEndpoint, PersistedProject},
+    requests::build_agent,
+};
+
+const INTROSPECTION_QUERY: &str = r#"query IntrospectionQuery {
+  __schema {
+    types {
+      kind
+      name
+      fields(includeDeprecated: true) {
+        name
+        args { name type { ...TypeRef } }
+        type { ...TypeRef }
+      }
+      inputFields { name type { ...TypeRef } }
+      enumValues(includeDeprecated: true) { name }
+      possibleTypes { name }
+    }
+  }
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType { kind name ofType { kind name ofType { kind name ofType { kind name } } } }
+}"#;
+
+/// Builds the standard `{query, variables, operationName}` JSON payload
+pub fn graphql_payload(body: &GraphQLBody) -> anyhow::Result<String> {
+    let mut payload = Map::new();
+    payload.insert("query".to_string(), Value::String(body.query.clone()));
+
+    if !body.variables.trim().is_empty() {
+        let variables: Value = serde_json::from_str(&body.variables).map_err(|error| {
+            anyhow::Error::msg(format!("Variables are not valid JSON: {error}"))
+        })?;
+
+        if !variables.is_object() {
+            return Err(anyhow::Error::msg("Variables must be a JSON object"));
+        }
+
+        payload.insert("variables".to_string(), variables);
+    }
+
+    if let Some(operation_name) = operation_name(&body.query) {
+        payload.insert("operationName".to_string(), Value::String(operation_name));
+    }
+
+    Ok(Value::Object(payload).to_string())
+}
+
+/// Name of the first named operation in a query document, if there is one
+pub fn operation_name(query: &str) -> Option<String> {
+    let mut tokens = query
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|token| !token.is_empty());
+
+    while let Some(token) = tokens.next() {
+        if matches!(token, "query" | "mutation" | "subscription") {
+            let name = tokens.next()?;
+
+            return match query.contains(&format!("{token} {name}")) {
+                true => Some(name.to_string()),
+                false => None,
+            };
+        }
+    }
+
+    None
+}
+
+/// Runs the introspection query against the endpoint and lists the schema
+/// types and their fields in SDL form
+pub fn introspect(
+    project: &PersistedProject,
+    endpoint: &PersistedEndpoint,
+) -> anyhow::Result<String> {
+    let agent = build_agent(project, &endpoint.url)?;
+
+    let mut request = agent.post(&endpoint.url);
+    for header in endpoint.headers.iter() {
+        request = request.set(&header.name, &header.value);
+    }
+
+    let payload = json!({ "query": INTROSPECTION_QUERY }).to_string();
+    let response = request
+        .set("Content-Type", "application/json")
+        .send_string(&payload)?;
+
+    let schema: Value = serde_json::from_str(&response.into_string()?)?;
+
+    format_schema(&schema)
+}
+
+fn format_schema(response: &Value) -> anyhow::Result<String> {
+    if let Some(errors) = response.get("errors") {
+        return Err(anyhow::Error::msg(format!(
+            "Introspection failed: {errors}"
+        )));
+    }
+
+    let Some(types) = response
+        .pointer("/data/__schema/types")
+        .and_then(Value::as_array)
+    else {
+        return Err(anyhow::Error::msg("The response does not contain a schema"));
+    };
+
+    let definitions: Vec<String> = types
+        .iter()
+        .filter(|schema_type| !str_field(schema_type, "name").starts_with("__"))
+        .filter_map(format_type)
+        .collect();
+
+    Ok(definitions.join("\n\n"))
+}
+
+fn format_type(schema_type: &Value) -> Option<String> {
+    let name = str_field(schema_type, "name");
+
+    let (keyword, members) = match str_field(schema_type, "kind") {
+        "OBJECT" => ("type", format_fields(schema_type.get("fields"))),
+        "INTERFACE" => ("interface", format_fields(schema_type.get("fields"))),
+        "INPUT_OBJECT" => ("input", format_fields(schema_type.get("inputFields"))),
+        "ENUM" => ("enum", names(schema_type.get("enumValues"))),
+        "SCALAR" => return Some(format!("scalar {name}")),
+        "UNION" => {
+            let members = names(schema_type.get("possibleTypes")).join(" | ");
+            return Some(format!("union {name} = {members}"));
+        }
+        _ => return None,
+    };
+
+    let members: Vec<String> = members.iter().map(|member| format!("  {member}")).collect();
+
+    Some(format!("{keyword} {name} {{\n{}\n}}", members.join("\n")))
+}
+
+fn format_fields(fields: Option<&Value>) -> Vec<String> {
+    let Some(fields) = fields.and_then(Value::as_array) else {
+        return vec![];
+    };
+
+    fields
+        .iter()
+        .map(|field| {
+            let args: Vec<String> = field
+                .get("args")
+                .and_then(Value::as_array)
+                .map(|args| args.iter().map(format_argument).collect())
+                .unwrap_or_default();
+
+            let args = match args.is_empty() {
+                true => String::new(),
+                false => format!("({})", args.join(", ")),
+            };
+
+            let type_name = field.get("type").map(type_ref).unwrap_or_default();
+
+            format!("{}{args}: {type_name}", str_field(field, "name"))
+        })
+        .collect()
+}
+
+fn format_argument(arg: &Value) -> String {
+    let type_name = arg.get("type").map(type_ref).unwrap_or_default();
+
+    format!("{}: {type_name}", str_field(arg, "name"))
+}
+
+fn names(values: Option<&Value>) -> Vec<String> {
+    values
+        .and_then(Value::as_array)
+        .map(|values| {
+            values
+                .iter()
+                .map(|value| str_field(value, "name").to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Renders a nested `__Type` reference, e.g. `[User!]!`
+fn type_ref(type_value: &Value) -> String {
+    let inner = || type_value.get("ofType").map(type_ref).unwrap_or_default();
+
+    match str_field(type_value, "kind") {
+        "NON_NULL" => format!("{}!", inner()),
+        "LIST" => format!("[{}]", inner()),
+        _ => str_field(type_value, "name").to_string(),
+    }
+}
+
+fn str_field<'a>(value: &'a Value, field: &str) -> &'a str {
+    value.get(field).and_then(Value::as_str).unwrap_or_default()
+}
+
+#[test]
+fn test_graphql_payload() {
+    let body = GraphQLBody {
+        query: "query GetUser($id: ID!) { user(id: $id) { name } }".to_string(),
+        variables: "{\"id\": \"1\"}".to_string(),
+    };
+
+    let payload: Value = serde_json::from_str(&graphql_payload(&body).unwrap()).unwrap();
+
+    assert_eq!(payload["operationName"], "GetUser");
+    assert_eq!(payload["variables"]["id"], "1");
+}
+
+#[test]
+fn test_graphql_payload_invalid_variables() {
+    let body = GraphQLBody {
+        query: "{ users { name } }".to_string(),
+        variables: "{id: 1}".to_string(),
+    };
+
+    assert!(graphql_payload(&body).is_err());
+}
+
+#[test]
+fn test_operation_name_anonymous() {
+    assert_eq!(operation_name("{ users { name } }"), None);
+    assert_eq!(operation_name("query { users { name } }"), None);
+}
+
+#[test]
+fn test_format_schema() {
+    let response = json!({
+        "data": { "__schema": { "types": [
+            {
+                "kind": "OBJECT",
+                "name": "Query",
+                "fields": [{
+                    "name": "users",
+                    "args": [{ "name": "first", "type": { "kind": "SCALAR", "name": "Int" } }],
+                    "type": { "kind": "NON_NULL", "name": null, "ofType": {
+                        "kind": "LIST", "name": null, "ofType": { "kind": "OBJECT", "name": "User" }
+                    } }
+                }]
+            },
+            { "kind": "OBJECT", "name": "__Schema", "fields": [] }
+        ] } }
+    });
+
+    assert_eq!(
+        format_schema(&response).unwrap(),
+        "type Query {\n  users(first: Int): [User]!\n}"
+    );
+}
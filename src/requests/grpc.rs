@@ -0,0 +1,565 @@
+use std::{fs, path::PathBuf, sync::Arc, time::Duration};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use h2::client::{self, SendRequest};
+use http::{HeaderMap, HeaderName, HeaderValue, Request, Uri};
+use prost::Message;
+use prost_reflect::{
+    DescriptorPool, DynamicMessage, MessageDescriptor, MethodDescriptor, SerializeOptions,
+};
+use protox::Compiler;
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpStream,
+    runtime, time,
+};
+use tokio_rustls::{rustls::pki_types::ServerName, TlsConnector};
+
+use crate::{
+    projects::{Header, PersistedEndpoint, PersistedProject, TlsSettings},
+    requests::tls::tls_client_config,
+};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long the TLS handshake and the whole unary call may take once connected
+const CALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Status codes from the gRPC spec, indexed by their value
+const STATUS_NAMES: [&str; 17] = [
+    "OK",
+    "CANCELLED",
+    "UNKNOWN",
+    "INVALID_ARGUMENT",
+    "DEADLINE_EXCEEDED",
+    "NOT_FOUND",
+    "ALREADY_EXISTS",
+    "PERMISSION_DENIED",
+    "RESOURCE_EXHAUSTED",
+    "FAILED_PRECONDITION",
+    "ABORTED",
+    "OUT_OF_RANGE",
+    "UNIMPLEMENTED",
+    "INTERNAL",
+    "UNAVAILABLE",
+    "DATA_LOSS",
+    "UNAUTHENTICATED",
+];
+
+#[derive(Debug)]
+pub struct GrpcResponse {
+    pub status: u32,
+    pub message: String,
+    pub headers: Vec<Header>,
+
+    /// Response message as JSON, missing when the call failed
+    pub body: Option<String>,
+}
+
+impl GrpcResponse {
+    pub fn status_name(&self) -> &'static str {
+        STATUS_NAMES
+            .get(self.status as usize)
+            .copied()
+            .unwrap_or("UNKNOWN")
+    }
+}
+
+/// Compiles the project's .proto files, imports are looked up next to each file
+pub fn load_descriptor_pool(proto_files: &[String]) -> anyhow::Result<DescriptorPool> {
+    if proto_files.is_empty() {
+        return Err(anyhow::Error::msg(
+            "Add .proto files to the project in the (P)roject Settings first",
+        ));
+    }
+
+    let mut files: Vec<PathBuf> = vec![];
+    let mut includes: Vec<PathBuf> = vec![];
+
+    for file in proto_files {
+        let path = fs::canonicalize(file)
+            .map_err(|error| anyhow::Error::msg(format!("Could not open {file}: {error}")))?;
+
+        if let Some(parent) = path.parent() {
+            if !includes.iter().any(|include| include == parent) {
+                includes.push(parent.to_path_buf());
+            }
+        }
+
+        files.push(path);
+    }
+
+    let mut compiler = Compiler::new(includes)?;
+    compiler.include_imports(true);
+    compiler.open_files(files)?;
+
+    Ok(compiler.descriptor_pool())
+}
+
+/// Unary methods of every service, as `package.Service/Method`
+pub fn list_methods(pool: &DescriptorPool) -> Vec<String> {
+    pool.services()
+        .flat_map(|service| {
+            service
+                .methods()
+                .filter(|method| !method.is_client_streaming() && !method.is_server_streaming())
+                .map(|method| format!("{}/{}", service.full_name(), method.name()))
+                .collect::<Vec<String>>()
+        })
+        .collect()
+}
+
+pub fn find_method(pool: &DescriptorPool, path: &str) -> anyhow::Result<MethodDescriptor> {
+    let Some((service_name, method_name)) = path.split_once('/') else {
+        return Err(anyhow::Error::msg(
+            "Pick a gRPC service and method with (M)ethod first",
+        ));
+    };
+
+    pool.get_service_by_name(service_name)
+        .and_then(|service| {
+            service
+                .methods()
+                .find(|method| method.name() == method_name)
+        })
+        .ok_or_else(|| {
+            anyhow::Error::msg(format!(
+                "{path} was not found in the project's .proto files"
+            ))
+        })
+}
+
+/// JSON for the method's request message with every field at its default, used
+/// as a starting point for the request body
+pub fn request_template(method: &MethodDescriptor) -> anyhow::Result<String> {
+    message_to_json(&DynamicMessage::new(method.input()))
+}
+
+pub fn json_to_message(
+    descriptor: MessageDescriptor,
+    json: &str,
+) -> anyhow::Result<DynamicMessage> {
+    let json = match json.trim().is_empty() {
+        true => "{}",
+        false => json,
+    };
+
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let message = DynamicMessage::deserialize(descriptor, &mut deserializer)
+        .map_err(|error| anyhow::Error::msg(format!("Request message error: {error}")))?;
+    deserializer.end()?;
+
+    Ok(message)
+}
+
+pub fn message_to_json(message: &DynamicMessage) -> anyhow::Result<String> {
+    let options = SerializeOptions::new().skip_default_fields(false);
+    let mut serializer = serde_json::Serializer::pretty(vec![]);
+    message.serialize_with_options(&mut serializer, &options)?;
+
+    Ok(String::from_utf8(serializer.into_inner())?)
+}
+
+/// Prefixes a message with the uncompressed flag and its length, as gRPC sends it
+pub fn frame_message(message: &[u8]) -> Bytes {
+    let mut frame = BytesMut::with_capacity(message.len() + 5);
+    frame.put_u8(0);
+    frame.put_u32(message.len() as u32);
+    frame.put_slice(message);
+
+    frame.freeze()
+}
+
+/// Takes the first message out of a length prefixed response body
+pub fn unframe_message(mut body: Bytes) -> anyhow::Result<Option<Bytes>> {
+    if body.is_empty() {
+        return Ok(None);
+    }
+
+    if body.len() < 5 {
+        return Err(anyhow::Error::msg("The response message is truncated"));
+    }
+
+    let compressed = body.get_u8();
+    let length = body.get_u32() as usize;
+
+    if compressed != 0 {
+        return Err(anyhow::Error::msg(
+            "Compressed gRPC responses are not supported",
+        ));
+    }
+
+    if body.len() < length {
+        return Err(anyhow::Error::msg("The response message is truncated"));
+    }
+
+    Ok(Some(body.split_to(length)))
+}
+
+/// Sends the endpoint's body as a unary call to the method picked for it
+pub fn unary_call(
+    project: &PersistedProject,
+    endpoint: &PersistedEndpoint,
+) -> anyhow::Result<GrpcResponse> {
+    let pool = load_descriptor_pool(&project.proto_files)?;
+    let method = find_method(&pool, &endpoint.grpc_method)?;
+
+    call(
+        &endpoint.url,
+        &method,
+        &endpoint.headers,
+        &endpoint.body,
+        &project.tls,
+    )
+}
+
+fn call(
+    url: &str,
+    method: &MethodDescriptor,
+    headers: &[Header],
+    body: &str,
+    tls: &TlsSettings,
+) -> anyhow::Result<GrpcResponse> {
+    let request = json_to_message(method.input(), body)?;
+    let payload = frame_message(&request.encode_to_vec());
+
+    let (secure, authority) = parse_target(url)?;
+    let scheme = if secure { "https" } else { "http" };
+    let uri: Uri = format!(
+        "{scheme}://{authority}/{}/{}",
+        method.parent_service().full_name(),
+        method.name()
+    )
+    .parse()?;
+
+    let runtime = runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    let (response_headers, response_body, trailers) = runtime.block_on(async {
+        let stream = time::timeout(CONNECT_TIMEOUT, TcpStream::connect(&authority))
+            .await
+            .map_err(|_| anyhow::Error::msg("Timed out connecting to the gRPC server"))??;
+
+        let unary_call = async {
+            match secure {
+                true => {
+                    let server_name = ServerName::try_from(server_host(&authority).to_string())?;
+
+                    let mut config = (*tls_client_config(tls)?).clone();
+                    config.alpn_protocols = vec![b"h2".to_vec()];
+
+                    let stream = TlsConnector::from(Arc::new(config))
+                        .connect(server_name, stream)
+                        .await?;
+
+                    send_unary(stream, uri, headers, payload).await
+                }
+
+                false => send_unary(stream, uri, headers, payload).await,
+            }
+        };
+
+        time::timeout(CALL_TIMEOUT, unary_call)
+            .await
+            .map_err(|_| anyhow::Error::msg("The gRPC call timed out"))?
+    })?;
+
+    // NOTE: Servers that fail a call straight away put the status in the headers
+    let status_headers = match trailers.contains_key("grpc-status") {
+        true => &trailers,
+        false => &response_headers,
+    };
+
+    let status = status_headers
+        .get("grpc-status")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u32>().ok())
+        .unwrap_or(2);
+    let message = status_headers
+        .get("grpc-message")
+        .and_then(|value| value.to_str().ok())
+        .map(percent_decode)
+        .unwrap_or_default();
+
+    let body = match (status, unframe_message(response_body)?) {
+        (0, Some(bytes)) => Some(message_to_json(&DynamicMessage::decode(
+            method.output(),
+            bytes,
+        )?)?),
+        _ => None,
+    };
+
+    let headers = response_headers
+        .iter()
+        .chain(trailers.iter())
+        .map(|(name, value)| Header {
+            name: name.as_str().to_string(),
+            value: String::from_utf8_lossy(value.as_bytes()).to_string(),
+        })
+        .collect();
+
+    Ok(GrpcResponse {
+        status,
+        message,
+        headers,
+        body,
+    })
+}
+
+async fn send_unary<T>(
+    stream: T,
+    uri: Uri,
+    headers: &[Header],
+    payload: Bytes,
+) -> anyhow::Result<(HeaderMap, Bytes, HeaderMap)>
+where
+    T: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (client, connection) = client::handshake(stream).await?;
+    tokio::spawn(async move {
+        let _ = connection.await;
+    });
+
+    let mut client: SendRequest<Bytes> = client.ready().await?;
+
+    let mut request = Request::post(uri)
+        .header("content-type", "application/grpc")
+        .header("te", "trailers")
+        .body(())?;
+
+    for header in headers {
+        let name = header.name.to_lowercase();
+        if matches!(name.as_str(), "content-type" | "te" | "host" | "connection") {
+            continue;
+        }
+
+        request.headers_mut().insert(
+            HeaderName::from_bytes(name.as_bytes())?,
+            HeaderValue::from_str(&header.value)?,
+        );
+    }
+
+    let (response, mut send_stream) = client.send_request(request, false)?;
+    send_stream.send_data(payload, true)?;
+
+    let (parts, mut body) = response.await?.into_parts();
+
+    let mut data = BytesMut::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk?;
+        body.flow_control().release_capacity(chunk.len())?;
+        data.extend_from_slice(&chunk);
+    }
+
+    let trailers = body.trailers().await?.unwrap_or_default();
+
+    Ok((parts.headers, data.freeze(), trailers))
+}
+
+/// Host and port to connect to, and whether TLS is used, from the endpoint URL.
+/// http:// and grpc:// are plaintext, https:// and grpcs:// use TLS.
+fn parse_target(url: &str) -> anyhow::Result<(bool, String)> {
+    let url = url.trim();
+    let (scheme, rest) = url.split_once("://").unwrap_or(("http", url));
+
+    let secure = match scheme.to_lowercase().as_str() {
+        "http" | "grpc" => false,
+        "https" | "grpcs" => true,
+        scheme => {
+            return Err(anyhow::Error::msg(format!(
+                "Unsupported gRPC scheme: {scheme}"
+            )))
+        }
+    };
+
+    let authority = rest.split('/').next().unwrap_or_default();
+    if authority.is_empty() {
+        return Err(anyhow::Error::msg("The gRPC URL is missing a host"));
+    }
+
+    // NOTE: The colons of an IPv6 address sit inside the brackets, the port after them
+    let has_port = authority
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| !port.contains(']'));
+    let authority = match has_port {
+        true => authority.to_string(),
+        false => format!("{authority}:{}", if secure { 443 } else { 80 }),
+    };
+
+    Ok((secure, authority))
+}
+
+/// The host of a `host:port` authority, without the brackets around an IPv6 address
+fn server_host(authority: &str) -> &str {
+    let host = authority
+        .rsplit_once(':')
+        .map_or(authority, |(host, _)| host);
+
+    host.strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host)
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[cfg(test)]
+const TEST_PROTO: &str = r#"
+syntax = "proto3";
+
+package echo;
+
+service Echo {
+  rpc Say(Message) returns (Message);
+  rpc Listen(Message) returns (stream Message);
+}
+
+message Message {
+  string text = 1;
+  int32 count = 2;
+}
+"#;
+
+#[cfg(test)]
+fn test_pool(test_name: &str) -> DescriptorPool {
+    let dir = std::env::temp_dir().join(format!("tome_grpc_{test_name}_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let file = dir.join("echo.proto");
+    fs::write(&file, TEST_PROTO).unwrap();
+
+    load_descriptor_pool(&[file.to_string_lossy().to_string()]).unwrap()
+}
+
+#[test]
+fn test_list_methods_skips_streaming() {
+    assert_eq!(
+        list_methods(&test_pool("list_methods")),
+        vec!["echo.Echo/Say"]
+    );
+}
+
+#[test]
+fn test_request_template() {
+    let method = find_method(&test_pool("template"), "echo.Echo/Say").unwrap();
+    let template: serde_json::Value =
+        serde_json::from_str(&request_template(&method).unwrap()).unwrap();
+
+    assert_eq!(template, serde_json::json!({ "text": "", "count": 0 }));
+}
+
+#[test]
+fn test_frame_roundtrip() {
+    let framed = frame_message(b"hello");
+
+    assert_eq!(&framed[..5], &[0, 0, 0, 0, 5]);
+    assert_eq!(
+        unframe_message(framed).unwrap(),
+        Some(Bytes::from_static(b"hello"))
+    );
+}
+
+#[test]
+fn test_parse_target() {
+    assert_eq!(
+        parse_target("grpcs://api.example.com/").unwrap(),
+        (true, "api.example.com:443".to_string())
+    );
+    assert_eq!(
+        parse_target("localhost:50051").unwrap(),
+        (false, "localhost:50051".to_string())
+    );
+    assert_eq!(
+        parse_target("grpcs://[::1]").unwrap(),
+        (true, "[::1]:443".to_string())
+    );
+    assert_eq!(
+        parse_target("http://[::1]:50051/").unwrap(),
+        (false, "[::1]:50051".to_string())
+    );
+}
+
+#[test]
+fn test_server_host() {
+    assert_eq!(server_host("api.example.com:443"), "api.example.com");
+    assert_eq!(server_host("[::1]:50051"), "::1");
+}
+
+#[test]
+fn test_unary_call_against_echo_server() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    // Echoes the request message back with the trailers a gRPC server sends
+    std::thread::spawn(move || {
+        let runtime = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        runtime.block_on(async move {
+            listener.set_nonblocking(true).unwrap();
+            let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut connection = h2::server::handshake(socket).await.unwrap();
+
+            while let Some(Ok((request, mut respond))) = connection.accept().await {
+                let mut body = request.into_body();
+                let mut data = BytesMut::new();
+                while let Some(Ok(chunk)) = body.data().await {
+                    let _ = body.flow_control().release_capacity(chunk.len());
+                    data.extend_from_slice(&chunk);
+                }
+
+                let response = http::Response::builder()
+                    .header("content-type", "application/grpc")
+                    .body(())
+                    .unwrap();
+                let mut send = respond.send_response(response, false).unwrap();
+                send.send_data(data.freeze(), false).unwrap();
+
+                let mut trailers = HeaderMap::new();
+                trailers.insert("grpc-status", HeaderValue::from_static("0"));
+                send.send_trailers(trailers).unwrap();
+            }
+        });
+    });
+
+    let method = find_method(&test_pool("unary_call"), "echo.Echo/Say").unwrap();
+    let response = call(
+        &format!("http://{address}"),
+        &method,
+        &[],
+        r#"{"text": "hi", "count": 2}"#,
+        &TlsSettings::default(),
+    )
+    .unwrap();
+
+    let body: serde_json::Value = serde_json::from_str(response.body.as_deref().unwrap()).unwrap();
+
+    assert_eq!(response.status_name(), "OK");
+    assert_eq!(body, serde_json::json!({ "text": "hi", "count": 2 }));
+}
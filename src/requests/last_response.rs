@@ -0,0 +1,64 @@
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::fs::get_app_dir;
+
+// NOTE: Only enough of the body is kept to describe its shape, large downloads
+// would otherwise be copied into the app directory
+const MAX_STORED_BODY: usize = 1024 * 1024;
+
+/// The last response received for an endpoint, kept so exports can describe it
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: String,
+}
+
+impl StoredResponse {
+    pub fn new(status: u16, content_type: Option<&str>, body: &str) -> Self {
+        let mut end = body.len().min(MAX_STORED_BODY);
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        StoredResponse {
+            status,
+            content_type: content_type.map(|content_type| content_type.to_string()),
+            body: body[..end].to_string(),
+        }
+    }
+}
+
+fn response_path(project_name: &str, endpoint_name: &str) -> anyhow::Result<PathBuf> {
+    let file_name = format!("{project_name}__{endpoint_name}.json").replace(['/', '\\'], "_");
+
+    Ok(get_app_dir("responses")?.join(file_name))
+}
+
+pub fn save_last_response(
+    project_name: &str,
+    endpoint_name: &str,
+    response: &StoredResponse,
+) -> anyhow::Result<()> {
+    let path = response_path(project_name, endpoint_name)?;
+    fs::write(path, serde_json::to_string(response)?)?;
+
+    Ok(())
+}
+
+pub fn load_last_response(project_name: &str, endpoint_name: &str) -> Option<StoredResponse> {
+    let path = response_path(project_name, endpoint_name).ok()?;
+    let json = fs::read_to_string(path).ok()?;
+
+    serde_json::from_str(&json).ok()
+}
+
+#[test]
+fn test_stored_body_is_truncated_on_a_char_boundary() {
+    let body = format!("{}é", "a".repeat(MAX_STORED_BODY - 1));
+    let response = StoredResponse::new(200, Some("text/plain"), &body);
+
+    assert_eq!(response.body.len(), MAX_STORED_BODY - 1);
+}
@@ -0,0 +1,230 @@
+use percent_encoding::percent_decode_str;
+use ureq::Proxy;
+use url::Url;
+
+use crate::options::ProxySettings;
+
+/// Picks the proxy for a request URL, a project override wins over the global
+/// option, and both win over the HTTP_PROXY/HTTPS_PROXY/NO_PROXY env vars
+pub fn resolve_proxy(
+    project_proxy: Option<&ProxySettings>,
+    global_proxy: Option<&ProxySettings>,
+    url: &str,
+) -> anyhow::Result<Option<Proxy>> {
+    let Ok(url) = Url::parse(url) else {
+        return Ok(None);
+    };
+
+    let env_proxy = proxy_from_env(url.scheme(), |name| std::env::var(name).ok());
+    let settings = project_proxy.or(global_proxy).or(env_proxy.as_ref());
+
+    let Some(settings) = settings else {
+        return Ok(None);
+    };
+
+    let host = url.host_str().unwrap_or_default();
+    if settings.url.trim().is_empty() || bypasses_proxy(&settings.no_proxy, host) {
+        return Ok(None);
+    }
+
+    Ok(Some(build_proxy(settings)?))
+}
+
+fn build_proxy(settings: &ProxySettings) -> anyhow::Result<Proxy> {
+    let proxy_url = Url::parse(settings.url.trim())
+        .map_err(|error| anyhow::Error::msg(format!("Invalid proxy URL: {error}")))?;
+
+    // NOTE: ureq can't speak TLS to the proxy itself, so https:// proxies are
+    // connected to as http:// ones. HTTPS requests are still encrypted end to end,
+    // they're tunneled through the proxy with CONNECT
+    let scheme = match proxy_url.scheme() {
+        "http" | "https" => "http",
+        "socks5" | "socks5h" | "socks" => "socks5",
+        scheme => {
+            return Err(anyhow::Error::msg(format!(
+                "Unsupported proxy scheme: {scheme}"
+            )))
+        }
+    };
+
+    let Some(host) = proxy_url.host_str() else {
+        return Err(anyhow::Error::msg("The proxy URL is missing a host"));
+    };
+
+    let address = match proxy_url.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+    };
+
+    // NOTE: ureq splits the credentials at the first ':' and the last '@' and uses
+    // them as they are, so the ones from the URL are decoded instead of passed on
+    let decode = |value: &str| percent_decode_str(value).decode_utf8_lossy().to_string();
+    let username = settings
+        .username
+        .clone()
+        .or_else(|| (!proxy_url.username().is_empty()).then(|| decode(proxy_url.username())));
+    let password = settings
+        .password
+        .clone()
+        .or_else(|| proxy_url.password().map(decode));
+
+    if username
+        .as_deref()
+        .is_some_and(|username| username.contains(':'))
+    {
+        return Err(anyhow::Error::msg("Proxy usernames can't contain ':'"));
+    }
+
+    let proxy = match (username, password) {
+        (Some(username), password) => format!(
+            "{scheme}://{username}:{}@{address}",
+            password.unwrap_or_default()
+        ),
+        _ => format!("{scheme}://{address}"),
+    };
+
+    Ok(Proxy::new(proxy)?)
+}
+
+fn proxy_from_env(scheme: &str, lookup: impl Fn(&str) -> Option<String>) -> Option<ProxySettings> {
+    let var = |name: &str| {
+        lookup(name)
+            .or_else(|| lookup(&name.to_lowercase()))
+            .filter(|value| !value.trim().is_empty())
+    };
+
+    let url = match scheme {
+        "https" => var("HTTPS_PROXY").or_else(|| var("HTTP_PROXY")),
+        _ => var("HTTP_PROXY"),
+    }?;
+
+    let no_proxy = var("NO_PROXY")
+        .map(|hosts| hosts.split(',').map(|host| host.to_string()).collect())
+        .unwrap_or_default();
+
+    Some(ProxySettings {
+        url,
+        no_proxy,
+        ..Default::default()
+    })
+}
+
+fn bypasses_proxy(no_proxy: &[String], host: &str) -> bool {
+    let host = host.to_lowercase();
+
+    no_proxy.iter().any(|entry| {
+        let entry = entry.trim().to_lowercase();
+        let entry = entry.trim_start_matches("*.").trim_start_matches('.');
+
+        !entry.is_empty() && (entry == "*" || host == entry || host.ends_with(&format!(".{entry}")))
+    })
+}
+
+#[test]
+fn test_bypasses_proxy() {
+    let no_proxy = vec![
+        "localhost".to_string(),
+        ".internal.dev".to_string(),
+        "*.corp.com".to_string(),
+    ];
+
+    assert!(bypasses_proxy(&no_proxy, "localhost"));
+    assert!(bypasses_proxy(&no_proxy, "api.internal.dev"));
+    assert!(bypasses_proxy(&no_proxy, "git.corp.com"));
+    assert!(!bypasses_proxy(&no_proxy, "example.com"));
+    assert!(!bypasses_proxy(&no_proxy, "notlocalhost"));
+}
+
+#[test]
+fn test_proxy_from_env_prefers_https_proxy() {
+    let lookup = |name: &str| match name {
+        "HTTPS_PROXY" => Some("http://secure-proxy:8080".to_string()),
+        "http_proxy" => Some("http://proxy:8080".to_string()),
+        "NO_PROXY" => Some("localhost,127.0.0.1".to_string()),
+        _ => None,
+    };
+
+    let https = proxy_from_env("https", lookup).unwrap();
+    assert_eq!(https.url, "http://secure-proxy:8080");
+    assert_eq!(https.no_proxy, vec!["localhost", "127.0.0.1"]);
+
+    let http = proxy_from_env("http", lookup).unwrap();
+    assert_eq!(http.url, "http://proxy:8080");
+}
+
+#[test]
+fn test_resolve_proxy_project_overrides_global() {
+    let project = ProxySettings {
+        url: "socks5://127.0.0.1:1080".to_string(),
+        ..Default::default()
+    };
+    let global = ProxySettings {
+        url: "not a url".to_string(),
+        ..Default::default()
+    };
+
+    let proxy = resolve_proxy(Some(&project), Some(&global), "https://example.com");
+
+    assert!(matches!(proxy, Ok(Some(_))));
+}
+
+#[test]
+fn test_build_proxy_credentials() {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use std::{
+        io::{BufRead, BufReader},
+        net::TcpListener,
+        thread,
+    };
+
+    // The head of the CONNECT request a fake proxy at `url` gets for an HTTPS request
+    fn connect_request(url: &str, username: Option<&str>, password: Option<&str>) -> Vec<String> {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let proxy = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+
+            BufReader::new(stream)
+                .lines()
+                .map_while(Result::ok)
+                .take_while(|line| !line.is_empty())
+                .collect::<Vec<String>>()
+        });
+
+        let settings = ProxySettings {
+            url: url.replace("{address}", &address.to_string()),
+            username: username.map(String::from),
+            password: password.map(String::from),
+            ..Default::default()
+        };
+        let agent = ureq::AgentBuilder::new()
+            .proxy(build_proxy(&settings).unwrap())
+            .build();
+        let _ = agent.get("https://example.com/").call();
+
+        proxy.join().unwrap()
+    }
+
+    let request = connect_request("http://{address}", Some("user@corp"), Some("p@ss:w/rd%"));
+    assert_eq!(request[0], "CONNECT example.com:443 HTTP/1.1");
+    assert!(request.contains(&format!(
+        "Proxy-Authorization: basic {}",
+        STANDARD.encode("user@corp:p@ss:w/rd%")
+    )));
+
+    // NOTE: Credentials in the URL are percent decoded, https:// proxies are reached as http://
+    let request = connect_request("https://us%40er:p%3Ass%2F@{address}", None, None);
+    assert_eq!(request[0], "CONNECT example.com:443 HTTP/1.1");
+    assert!(request.contains(&format!(
+        "Proxy-Authorization: basic {}",
+        STANDARD.encode("us@er:p:ss/")
+    )));
+
+    let settings = ProxySettings {
+        url: "http://proxy.local:8080".to_string(),
+        username: Some("us:er".to_string()),
+        ..Default::default()
+    };
+    assert!(build_proxy(&settings).is_err());
+}
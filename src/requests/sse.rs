@@ -0,0 +1,299 @@
+use std::{
+    io::{self, BufRead, BufReader, Read},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use ureq::Response;
+
+use crate::requests::decoding::decompressing_reader;
+
+pub const EVENT_STREAM_MEDIA_TYPE: &str = "text/event-stream";
+
+// NOTE: Streams can't be sent to the response renderer as a serialized message,
+// so the request side parks the stream here until the renderer takes it
+static PENDING_STREAM: Mutex<Option<SseStream>> = Mutex::new(None);
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    pub id: Option<String>,
+    pub event: Option<String>,
+    pub data: String,
+}
+
+impl SseEvent {
+    /// Lines shown in the response view for the event, followed by a blank line
+    pub fn lines(&self) -> Vec<String> {
+        let mut header: Vec<String> = vec![];
+        if let Some(id) = &self.id {
+            header.push(format!("id: {id}"));
+        }
+        header.push(format!(
+            "event: {}",
+            self.event.as_deref().unwrap_or("message")
+        ));
+
+        let mut lines = vec![header.join("  ")];
+        lines.extend(self.data.lines().map(|line| format!("  {line}")));
+        lines.push(String::new());
+
+        lines
+    }
+}
+
+/// Builds events out of an event stream one line at a time, following the
+/// field rules of the HTML event stream format
+#[derive(Default)]
+pub struct SseParser {
+    id: Option<String>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// Returns the finished event when the line is the blank line closing it
+    pub fn feed_line(&mut self, line: &str) -> Option<SseEvent> {
+        let line = line.trim_end_matches(['\r', '\n']);
+
+        if line.is_empty() {
+            let event = self.event.take();
+            if self.data.is_empty() {
+                return None;
+            }
+
+            return Some(SseEvent {
+                id: self.id.clone(),
+                event,
+                data: std::mem::take(&mut self.data).join("\n"),
+            });
+        }
+
+        // Comments are used as keep alives
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+
+        match field {
+            "id" => self.id = Some(value.to_string()),
+            "event" => self.event = Some(value.to_string()),
+            "data" => self.data.push(value.to_string()),
+            _ => {}
+        }
+
+        None
+    }
+}
+
+#[derive(Debug)]
+pub enum StreamUpdate {
+    Event(SseEvent),
+    Closed(Option<String>),
+}
+
+/// An event stream response read on a background thread
+pub struct SseStream {
+    updates: Receiver<StreamUpdate>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl SseStream {
+    pub fn start(response: Response) -> SseStream {
+        let content_encoding = response.header("content-encoding").map(|h| h.to_string());
+        let reader = decompressing_reader(response.into_reader(), content_encoding.as_deref());
+
+        let (sender, updates) = mpsc::channel();
+        let stopped = Arc::new(AtomicBool::new(false));
+        let thread_stopped = stopped.clone();
+
+        thread::spawn(move || read_events(reader, sender, thread_stopped));
+
+        SseStream { updates, stopped }
+    }
+
+    /// Updates that arrived since the last call, never blocks
+    pub fn updates(&self) -> Vec<StreamUpdate> {
+        self.updates.try_iter().collect()
+    }
+
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Drop for SseStream {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Sends the events read from `reader` until the stream ends or is stopped
+fn read_events(reader: impl Read, sender: Sender<StreamUpdate>, stopped: Arc<AtomicBool>) {
+    let mut parser = SseParser::default();
+    let mut reader = BufReader::new(reader);
+    let mut line: Vec<u8> = vec![];
+
+    // NOTE: Requests asking for an event stream have a read timeout, so a stopped
+    // stream returns on a quiet connection too, which drops the reader and closes
+    // the connection. Without it the reader closes on the next line the server
+    // sends. The bytes of a line read before a timeout stay in `line`
+    let error = loop {
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) => break None,
+            Ok(_) => {}
+            Err(error) if is_timeout(&error) => {
+                if stopped.load(Ordering::Relaxed) {
+                    return;
+                }
+                continue;
+            }
+            Err(error) => break Some(error.to_string()),
+        }
+
+        if stopped.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let event = parser.feed_line(&String::from_utf8_lossy(&line));
+        line.clear();
+
+        if let Some(event) = event {
+            if sender.send(StreamUpdate::Event(event)).is_err() {
+                return;
+            }
+        }
+    };
+
+    let _ = sender.send(StreamUpdate::Closed(error));
+}
+
+fn is_timeout(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+    )
+}
+
+pub fn is_event_stream(content_type: Option<&str>) -> bool {
+    content_type
+        .and_then(|content_type| content_type.split(';').next())
+        .is_some_and(|media_type| {
+            media_type
+                .trim()
+                .eq_ignore_ascii_case(EVENT_STREAM_MEDIA_TYPE)
+        })
+}
+
+pub fn set_pending_stream(stream: SseStream) {
+    if let Ok(mut pending) = PENDING_STREAM.lock() {
+        *pending = Some(stream);
+    }
+}
+
+pub fn take_pending_stream() -> Option<SseStream> {
+    PENDING_STREAM.lock().ok()?.take()
+}
+
+#[test]
+fn test_parse_events() {
+    let stream =
+        "id: 1\nevent: update\ndata: {\"a\": 1}\n\n: keep alive\n\ndata: first\ndata:second\n\n";
+
+    let mut parser = SseParser::default();
+    let events: Vec<SseEvent> = stream
+        .split_inclusive('\n')
+        .filter_map(|line| parser.feed_line(line))
+        .collect();
+
+    assert_eq!(
+        events,
+        vec![
+            SseEvent {
+                id: Some("1".to_string()),
+                event: Some("update".to_string()),
+                data: "{\"a\": 1}".to_string(),
+            },
+            SseEvent {
+                id: Some("1".to_string()),
+                event: None,
+                data: "first\nsecond".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_event_lines() {
+    let event = SseEvent {
+        id: Some("7".to_string()),
+        event: None,
+        data: "a\nb".to_string(),
+    };
+
+    assert_eq!(
+        event.lines(),
+        vec!["id: 7  event: message", "  a", "  b", ""]
+    );
+}
+
+#[test]
+fn test_is_event_stream() {
+    assert!(is_event_stream(Some("text/event-stream; charset=utf-8")));
+    assert!(!is_event_stream(Some("application/json")));
+    assert!(!is_event_stream(None));
+}
+
+#[cfg(test)]
+struct TimingOutReader {
+    reads: Vec<io::Result<&'static str>>,
+}
+
+#[cfg(test)]
+impl Read for TimingOutReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.reads.is_empty() {
+            return Ok(0);
+        }
+
+        let text = self.reads.remove(0)?;
+        buf[..text.len()].copy_from_slice(text.as_bytes());
+        Ok(text.len())
+    }
+}
+
+#[cfg(test)]
+fn timed_out() -> io::Result<&'static str> {
+    Err(io::Error::new(io::ErrorKind::TimedOut, "timed out"))
+}
+
+#[test]
+fn test_read_events_across_timeouts() {
+    let reader = TimingOutReader {
+        reads: vec![Ok("data: fi"), timed_out(), Ok("rst\n\n"), timed_out()],
+    };
+    let (sender, updates) = mpsc::channel();
+    read_events(reader, sender, Arc::new(AtomicBool::new(false)));
+
+    let updates: Vec<StreamUpdate> = updates.try_iter().collect();
+    assert!(matches!(
+        updates.as_slice(),
+        [StreamUpdate::Event(event), StreamUpdate::Closed(None)] if event.data == "first"
+    ));
+}
+
+#[test]
+fn test_read_events_stops_on_timeout() {
+    let reader = TimingOutReader {
+        reads: vec![timed_out(), Ok("data: late\n\n")],
+    };
+    let (sender, updates) = mpsc::channel();
+    read_events(reader, sender, Arc::new(AtomicBool::new(true)));
+
+    assert_eq!(updates.try_iter().count(), 0);
+}
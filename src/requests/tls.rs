@@ -0,0 +1,252 @@
+use std::{fs::File, io::BufReader, sync::Arc};
+
+use rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider},
+    pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime},
+    ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme,
+};
+
+use crate::projects::TlsSettings;
+
+/// Builds the rustls config for a project's TLS settings, returns None when the
+/// settings are all defaults so the agent keeps ureq's own configuration
+pub fn build_tls_config(settings: &TlsSettings) -> anyhow::Result<Option<Arc<ClientConfig>>> {
+    let has_client_auth = settings.client_certificate.is_some() || settings.client_key.is_some();
+    if settings.ca_certificates.is_empty() && !has_client_auth && !settings.insecure {
+        return Ok(None);
+    }
+
+    Ok(Some(tls_client_config(settings)?))
+}
+
+/// Builds the rustls config for a project's TLS settings even when they are
+/// all defaults, for connections that do not go through ureq
+pub fn tls_client_config(settings: &TlsSettings) -> anyhow::Result<Arc<ClientConfig>> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?;
+
+    let builder = match settings.insecure {
+        true => builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(NoVerification(provider))),
+
+        false => {
+            let mut root_store = RootCertStore {
+                roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+            };
+
+            for path in settings.ca_certificates.iter() {
+                for certificate in load_certificates(path)? {
+                    root_store.add(certificate)?;
+                }
+            }
+
+            builder.with_root_certificates(root_store)
+        }
+    };
+
+    let config = match (&settings.client_certificate, &settings.client_key) {
+        (Some(certificate_path), Some(key_path)) => {
+            let certificates = load_certificates(certificate_path)?;
+            let key = load_private_key(key_path)?;
+
+            builder.with_client_auth_cert(certificates, key)?
+        }
+
+        (Some(_), None) => {
+            return Err(anyhow::Error::msg(
+                "A client key is required to use a client certificate",
+            ))
+        }
+
+        (None, Some(_)) => {
+            return Err(anyhow::Error::msg(
+                "A client certificate is required to use a client key",
+            ))
+        }
+
+        (None, None) => builder.with_no_client_auth(),
+    };
+
+    Ok(Arc::new(config))
+}
+
+fn load_certificates(path: &str) -> anyhow::Result<Vec<CertificateDer<'static>>> {
+    let file = File::open(path)
+        .map_err(|error| anyhow::Error::msg(format!("Could not open {path}: {error}")))?;
+    let mut reader = BufReader::new(file);
+
+    let certificates = rustls_pemfile::certs(&mut reader).collect::<Result<Vec<_>, _>>()?;
+    if certificates.is_empty() {
+        return Err(anyhow::Error::msg(format!(
+            "No certificates found in {path}"
+        )));
+    }
+
+    Ok(certificates)
+}
+
+fn load_private_key(path: &str) -> anyhow::Result<PrivateKeyDer<'static>> {
+    let file = File::open(path)
+        .map_err(|error| anyhow::Error::msg(format!("Could not open {path}: {error}")))?;
+    let mut reader = BufReader::new(file);
+
+    rustls_pemfile::private_key(&mut reader)?.ok_or(anyhow::Error::msg(format!(
+        "No private key found in {path}"
+    )))
+}
+
+/// Accepts any server certificate, only used when a project explicitly turns
+/// off verification. Handshake signatures are still checked so the connection
+/// itself is sound.
+#[derive(Debug)]
+struct NoVerification(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for NoVerification {
+    fn verify_server_cert(
+        &self,
+        _: &CertificateDer<'_>,
+        _: &[CertificateDer<'_>],
+        _: &ServerName<'_>,
+        _: &[u8],
+        _: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+#[test]
+fn test_build_tls_config_defaults_to_none() {
+    let config = build_tls_config(&TlsSettings::default()).unwrap();
+
+    assert!(config.is_none());
+}
+
+#[test]
+fn test_build_tls_config_insecure() {
+    let settings = TlsSettings {
+        insecure: true,
+        ..Default::default()
+    };
+
+    let config = build_tls_config(&settings).unwrap();
+
+    assert!(config.is_some());
+}
+
+#[test]
+fn test_build_tls_config_requires_key_with_certificate() {
+    let settings = TlsSettings {
+        client_certificate: Some("client.pem".to_string()),
+        ..Default::default()
+    };
+
+    assert!(build_tls_config(&settings).is_err());
+}
+
+#[cfg(test)]
+fn fixture(name: &str) -> String {
+    format!(
+        "{}/src/requests/tls_fixtures/{name}",
+        env!("CARGO_MANIFEST_DIR")
+    )
+}
+
+/// Connects to a local server using the localhost certificate signed by the test CA
+#[cfg(test)]
+fn handshake_with_local_server(settings: &TlsSettings) -> anyhow::Result<()> {
+    use std::{
+        io::{Read, Write},
+        net::{TcpListener, TcpStream},
+        thread,
+    };
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let server_config = rustls::ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .with_no_client_auth()
+        .with_single_cert(
+            load_certificates(&fixture("localhost.pem"))?,
+            load_private_key(&fixture("localhost.key"))?,
+        )?;
+    let client_config = tls_client_config(settings)?;
+
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let address = listener.local_addr()?;
+    let server = thread::spawn(move || -> anyhow::Result<()> {
+        let (socket, _) = listener.accept()?;
+        let connection = rustls::ServerConnection::new(Arc::new(server_config))?;
+        let mut stream = rustls::StreamOwned::new(connection, socket);
+        stream.write_all(b"ok")?;
+        stream.conn.send_close_notify();
+        stream.flush()?;
+
+        Ok(())
+    });
+
+    let connection =
+        rustls::ClientConnection::new(client_config, ServerName::try_from("localhost")?)?;
+    let mut stream = rustls::StreamOwned::new(connection, TcpStream::connect(address)?);
+    let mut response = [0; 2];
+    let result = stream.read_exact(&mut response);
+
+    drop(stream);
+    let _ = server.join();
+
+    result?;
+    assert_eq!(&response, b"ok");
+
+    Ok(())
+}
+
+#[test]
+fn test_local_tls_server() {
+    let settings = TlsSettings::default();
+    assert!(handshake_with_local_server(&settings).is_err());
+
+    let settings = TlsSettings {
+        ca_certificates: vec![fixture("ca.pem")],
+        ..Default::default()
+    };
+    handshake_with_local_server(&settings).unwrap();
+
+    let settings = TlsSettings {
+        insecure: true,
+        ..Default::default()
+    };
+    handshake_with_local_server(&settings).unwrap();
+}
@@ -0,0 +1,133 @@
+use crate::projects::{
+    folders::with_folder_headers, Header, PersistedEndpoint, PersistedProject, Variable,
+};
+
+/// Replaces every `{{name}}` placeholder that has a project variable, unknown
+/// placeholders are left as they are so they stand out in the request
+pub fn substitute_variables(text: &str, variables: &[Variable]) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            break;
+        };
+
+        let name = rest[start + 2..start + 2 + length].trim();
+        let end = start + 2 + length + 2;
+
+        result.push_str(&rest[..start]);
+        match variables.iter().find(|variable| variable.name == name) {
+            Some(variable) => result.push_str(&variable.value),
+            None => result.push_str(&rest[start..end]),
+        }
+
+        rest = &rest[end..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// The endpoint as it is sent, with its folders' default headers added and the
+/// project's variables filled in
+pub fn resolve_endpoint(
+    project: &PersistedProject,
+    endpoint: &PersistedEndpoint,
+) -> PersistedEndpoint {
+    let endpoint = &with_folder_headers(&project.folders, endpoint);
+
+    let variables = &project.variables;
+    if variables.is_empty() {
+        return endpoint.clone();
+    }
+
+    let mut resolved = endpoint.clone();
+    resolved.url = substitute_variables(&endpoint.url, variables);
+    resolved.body = substitute_variables(&endpoint.body, variables);
+    resolved.graphql.variables = substitute_variables(&endpoint.graphql.variables, variables);
+    resolved.headers = endpoint
+        .headers
+        .iter()
+        .map(|header| Header {
+            name: header.name.clone(),
+            value: substitute_variables(&header.value, variables),
+        })
+        .collect();
+
+    resolved
+}
+
+/// Reads one `name=value` pair per line, as typed in the project settings
+pub fn parse_variables(text: &str) -> Vec<Variable> {
+    text.lines()
+        .filter_map(|pair| {
+            let (name, value) = pair.split_once('=')?;
+            let name = name.trim();
+
+            match name.is_empty() {
+                true => None,
+                false => Some(Variable {
+                    name: name.to_string(),
+                    value: value.trim().to_string(),
+                }),
+            }
+        })
+        .collect()
+}
+
+pub fn format_variables(variables: &[Variable]) -> String {
+    variables
+        .iter()
+        .map(|variable| format!("{}={}", variable.name, variable.value))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[test]
+fn test_substitute_variables() {
+    let variables = vec![
+        Variable {
+            name: "baseUrl".to_string(),
+            value: "https://api.example.com/v1".to_string(),
+        },
+        Variable {
+            name: "petId".to_string(),
+            value: "7".to_string(),
+        },
+    ];
+
+    assert_eq!(
+        substitute_variables("{{baseUrl}}/pets/{{ petId }}?tag={{tag}}", &variables),
+        "https://api.example.com/v1/pets/7?tag={{tag}}"
+    );
+    assert_eq!(substitute_variables("{{unclosed", &variables), "{{unclosed");
+}
+
+#[test]
+fn test_parse_variables() {
+    let variables =
+        parse_variables("baseUrl = https://api.example.com/v1?a=b\n\ntags=cats,dogs\ntoken=abc");
+
+    assert_eq!(
+        variables,
+        vec![
+            Variable {
+                name: "baseUrl".to_string(),
+                value: "https://api.example.com/v1?a=b".to_string(),
+            },
+            Variable {
+                name: "tags".to_string(),
+                value: "cats,dogs".to_string(),
+            },
+            Variable {
+                name: "token".to_string(),
+                value: "abc".to_string(),
+            },
+        ]
+    );
+    assert_eq!(
+        format_variables(&variables),
+        "baseUrl=https://api.example.com/v1?a=b\ntags=cats,dogs\ntoken=abc"
+    );
+}
@@ -0,0 +1,352 @@
+use std::{
+    io::ErrorKind,
+    net::TcpStream,
+    sync::mpsc::{self, Receiver, Sender, TryRecvError},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tungstenite::{
+    client::IntoClientRequest,
+    client_tls_with_config,
+    handshake::HandshakeError,
+    http::{HeaderName, HeaderValue},
+    stream::MaybeTlsStream,
+    Connector, Message, WebSocket,
+};
+use url::Url;
+
+use crate::{
+    options::get_options,
+    projects::{Header, PersistedEndpoint, PersistedProject, TlsSettings},
+    requests::{decoding::format_size, proxy::resolve_proxy, tls::tls_client_config},
+};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest wait for the server between two reads of the opening handshake
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+// NOTE: Reads time out quickly so the connection thread can interleave
+// sending the composer's messages with waiting for the server's
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameDirection {
+    Sent,
+    Received,
+    Info,
+}
+
+#[derive(Clone, Debug)]
+pub struct WebSocketFrame {
+    pub direction: FrameDirection,
+    pub timestamp: String,
+    pub text: String,
+}
+
+impl WebSocketFrame {
+    pub fn new(direction: FrameDirection, text: impl Into<String>) -> Self {
+        WebSocketFrame {
+            direction,
+            timestamp: timestamp(SystemTime::now()),
+            text: text.into(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum WebSocketEvent {
+    Frame(WebSocketFrame),
+    Closed,
+}
+
+enum Command {
+    Send(String),
+    Close,
+}
+
+/// An open WebSocket, the socket itself lives on a background thread and
+/// frames are handed back through a channel so the UI never blocks on it
+pub struct WebSocketConnection {
+    commands: Sender<Command>,
+    events: Receiver<WebSocketEvent>,
+}
+
+impl WebSocketConnection {
+    /// Connects to the endpoint's ws:// or wss:// URL with its headers and the
+    /// project's TLS settings
+    pub fn connect(
+        project: &PersistedProject,
+        endpoint: &PersistedEndpoint,
+    ) -> anyhow::Result<WebSocketConnection> {
+        // NOTE: WebSockets always connect directly, the console says so when a
+        // proxy would have been used for the URL
+        let options = get_options();
+        let bypasses_proxy = resolve_proxy(
+            project.proxy.as_ref(),
+            options.proxy.as_ref(),
+            &endpoint.url,
+        )
+        .is_ok_and(|proxy| proxy.is_some());
+
+        WebSocketConnection::open(
+            &endpoint.url,
+            &endpoint.headers,
+            &project.tls,
+            bypasses_proxy,
+        )
+    }
+
+    fn open(
+        url: &str,
+        headers: &[Header],
+        tls: &TlsSettings,
+        bypasses_proxy: bool,
+    ) -> anyhow::Result<WebSocketConnection> {
+        let url = Url::parse(url.trim())?;
+
+        let mut request = url.as_str().into_client_request()?;
+        for header in headers.iter() {
+            request.headers_mut().insert(
+                HeaderName::from_bytes(header.name.as_bytes())?,
+                HeaderValue::from_str(&header.value)?,
+            );
+        }
+
+        let connector = match url.scheme() {
+            "ws" => Connector::Plain,
+            "wss" => Connector::Rustls(tls_client_config(tls)?),
+            scheme => {
+                return Err(anyhow::Error::msg(format!(
+                    "Unsupported WebSocket scheme: {scheme}"
+                )))
+            }
+        };
+
+        let Some(address) = url.socket_addrs(|| None)?.into_iter().next() else {
+            return Err(anyhow::Error::msg("Could not resolve the WebSocket host"));
+        };
+
+        let stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)?;
+        stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        stream.set_write_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        let poll_stream = stream.try_clone()?;
+
+        // NOTE: A read that times out interrupts the handshake
+        let (socket, response) = client_tls_with_config(request, stream, None, Some(connector))
+            .map_err(|error| match error {
+                HandshakeError::Interrupted(_) => {
+                    anyhow::Error::msg("WebSocket handshake timed out")
+                }
+                HandshakeError::Failure(error) => {
+                    anyhow::Error::msg(format!("WebSocket handshake failed: {error}"))
+                }
+            })?;
+
+        poll_stream.set_read_timeout(Some(POLL_INTERVAL))?;
+
+        let (command_sender, command_receiver) = mpsc::channel();
+        let (event_sender, event_receiver) = mpsc::channel();
+
+        let status = response.status();
+        let _ = event_sender.send(WebSocketEvent::Frame(WebSocketFrame::new(
+            FrameDirection::Info,
+            format!("Connected to {url} ({status})"),
+        )));
+
+        if bypasses_proxy {
+            let _ = event_sender.send(WebSocketEvent::Frame(WebSocketFrame::new(
+                FrameDirection::Info,
+                "Proxy settings don't apply to WebSockets, connected directly",
+            )));
+        }
+
+        thread::spawn(move || run_connection(socket, command_receiver, event_sender));
+
+        Ok(WebSocketConnection {
+            commands: command_sender,
+            events: event_receiver,
+        })
+    }
+
+    pub fn send(&self, text: String) -> anyhow::Result<()> {
+        self.commands
+            .send(Command::Send(text))
+            .map_err(|_| anyhow::Error::msg("The WebSocket connection is closed"))
+    }
+
+    pub fn close(&self) {
+        let _ = self.commands.send(Command::Close);
+    }
+
+    /// Events that arrived since the last call, never blocks
+    pub fn events(&self) -> Vec<WebSocketEvent> {
+        self.events.try_iter().collect()
+    }
+}
+
+impl Drop for WebSocketConnection {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+fn run_connection(
+    mut socket: WebSocket<MaybeTlsStream<TcpStream>>,
+    commands: Receiver<Command>,
+    events: Sender<WebSocketEvent>,
+) {
+    let emit = |direction: FrameDirection, text: String| {
+        events
+            .send(WebSocketEvent::Frame(WebSocketFrame::new(direction, text)))
+            .is_ok()
+    };
+
+    loop {
+        match commands.try_recv() {
+            Ok(Command::Send(text)) => match socket.send(Message::Text(text.clone())) {
+                Ok(_) => {
+                    emit(FrameDirection::Sent, text);
+                }
+                Err(error) => {
+                    emit(FrameDirection::Info, format!("Send failed: {error}"));
+                }
+            },
+
+            Ok(Command::Close) => {
+                let _ = socket.close(None);
+            }
+
+            // NOTE: The connection handle was dropped, so nobody is listening
+            Err(TryRecvError::Disconnected) => {
+                let _ = socket.close(None);
+                let _ = socket.flush();
+                break;
+            }
+
+            Err(TryRecvError::Empty) => {}
+        }
+
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                if !emit(FrameDirection::Received, text) {
+                    let _ = socket.close(None);
+                }
+            }
+
+            Ok(Message::Binary(bytes)) => {
+                emit(
+                    FrameDirection::Received,
+                    format!("<binary frame, {}>", format_size(bytes.len())),
+                );
+            }
+
+            Ok(Message::Close(frame)) => {
+                let reason = frame
+                    .map(|frame| format!("{} {}", frame.code, frame.reason))
+                    .unwrap_or_default();
+
+                emit(
+                    FrameDirection::Info,
+                    format!("Connection closed {reason}").trim().to_string(),
+                );
+            }
+
+            // NOTE: Pings are answered by tungstenite on the next read
+            Ok(Message::Ping(_) | Message::Pong(_) | Message::Frame(_)) => {}
+
+            Err(tungstenite::Error::Io(error))
+                if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+
+            Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => break,
+
+            Err(error) => {
+                emit(FrameDirection::Info, format!("Connection error: {error}"));
+                break;
+            }
+        }
+    }
+
+    let _ = events.send(WebSocketEvent::Closed);
+}
+
+/// Wall clock time of day in UTC, e.g. `14:03:27.120`
+fn timestamp(time: SystemTime) -> String {
+    let elapsed = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = elapsed.as_secs() % 86_400;
+
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60,
+        elapsed.subsec_millis()
+    )
+}
+
+#[test]
+fn test_timestamp() {
+    let time =
+        UNIX_EPOCH + Duration::from_millis(86_400_000 + 14 * 3_600_000 + 3 * 60_000 + 27_120);
+
+    assert_eq!(timestamp(time), "14:03:27.120");
+}
+
+#[test]
+fn test_echo_server() {
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut socket = tungstenite::accept(stream).unwrap();
+
+        while let Ok(message) = socket.read() {
+            if message.is_text() && socket.send(message).is_err() {
+                break;
+            }
+        }
+    });
+
+    let url = format!("ws://127.0.0.1:{port}");
+    let connection = WebSocketConnection::open(&url, &[], &TlsSettings::default(), false).unwrap();
+    connection.send("hello".to_string()).unwrap();
+
+    let mut frames: Vec<WebSocketFrame> = vec![];
+    for _ in 0..100 {
+        frames.extend(
+            connection
+                .events()
+                .into_iter()
+                .filter_map(|event| match event {
+                    WebSocketEvent::Frame(frame) => Some(frame),
+                    WebSocketEvent::Closed => None,
+                }),
+        );
+
+        if frames
+            .iter()
+            .any(|frame| frame.direction == FrameDirection::Received)
+        {
+            break;
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+
+    let frames: Vec<(FrameDirection, &str)> = frames
+        .iter()
+        .map(|frame| (frame.direction, frame.text.as_str()))
+        .collect();
+
+    assert_eq!(
+        frames[1..],
+        [
+            (FrameDirection::Sent, "hello"),
+            (FrameDirection::Received, "hello")
+        ]
+    );
+}